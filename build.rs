@@ -0,0 +1,11 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto/chessclock.proto");
+    #[cfg(feature = "grpc")]
+    {
+        let files = protox::compile(["proto/chessclock.proto"], ["proto"])?;
+        tonic_prost_build::configure()
+            .build_client(false)
+            .compile_fds(files)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,1339 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::tabs::{self, PlayerCtrls, TimeCtrl};
+
+/// Interval of the timer ticks updating the clock, in milliseconds.
+pub const TIMER_TICK: u64 = 10;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Time(pub Duration);
+
+/// When the fraction of a second is shown in times under an hour, and how precisely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SubSeconds {
+    pub show: ShowSubSeconds,
+    /// The seconds left from which it is shown with [`ShowSubSeconds::Under`].
+    pub under_seconds: u64,
+    pub precision: Precision,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShowSubSeconds {
+    Always,
+    /// In the last seconds, see [`SubSeconds::under_seconds`].
+    Under,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Precision {
+    Tenths,
+    Hundredths,
+}
+
+/// Tenths in the last 20 seconds.
+const DEFAULT_SUBSECONDS: SubSeconds = SubSeconds {
+    show: ShowSubSeconds::Under,
+    under_seconds: 20,
+    precision: Precision::Tenths,
+};
+
+impl SubSeconds {
+    fn shows(&self, time: Duration) -> bool {
+        match self.show {
+            ShowSubSeconds::Always => true,
+            ShowSubSeconds::Under => time.as_secs() <= self.under_seconds,
+            ShowSubSeconds::Never => false,
+        }
+    }
+}
+
+impl Default for SubSeconds {
+    fn default() -> Self {
+        DEFAULT_SUBSECONDS
+    }
+}
+
+impl Time {
+    /// The time without the fraction of a second shown in the last seconds.
+    pub fn in_seconds(&self) -> String {
+        let secs = self.0.as_secs();
+        if secs >= 3_600 {
+            return self.to_string();
+        }
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+
+    /// The time with the fraction of a second shown the way `subseconds` tells, e.g.
+    /// `00:09.87` with hundredths in the last seconds.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use chessclock_core::clock::{Precision, ShowSubSeconds, SubSeconds, Time};
+    /// let time = Time(Duration::from_millis(65_430));
+    /// let always = SubSeconds {
+    ///     show: ShowSubSeconds::Always,
+    ///     under_seconds: 0,
+    ///     precision: Precision::Hundredths,
+    /// };
+    /// assert_eq!(time.with_subseconds(always), "01:05.43");
+    /// assert_eq!(time.to_string(), "01:05");
+    /// ```
+    pub fn with_subseconds(&self, subseconds: SubSeconds) -> String {
+        let total_s = self.0.as_secs();
+        let hh = total_s / 3_600;
+        let mm = (total_s % 3_600) / 60;
+        let ss = total_s % 60;
+        let ms = self.0.as_millis() % 1000;
+
+        if hh > 0 {
+            format!("{:02}:{:02}:{:02}", hh, mm, ss)
+        } else if !subseconds.shows(self.0) {
+            format!("{:02}:{:02}", mm, ss)
+        } else if subseconds.precision == Precision::Hundredths {
+            format!("{:02}:{:02}.{:02}", mm, ss, ms / 10)
+        } else {
+            format!("{:02}:{:02}.{}", mm, ss, ms / 100)
+        }
+    }
+}
+
+/// The time with the default [`SubSeconds`]: tenths in the last 20 seconds.
+impl Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.with_subseconds(SubSeconds::default()))
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, strum::Display, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Player {
+    #[default]
+    #[strum(to_string = "Player 1")]
+    Player1,
+    #[strum(to_string = "Player 2")]
+    Player2,
+}
+
+impl Player {
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::Player1 => Player::Player2,
+            Player::Player2 => Player::Player1,
+        }
+    }
+
+    /// 0 for Player 1 and 1 for Player 2, for arrays holding something of each player.
+    pub fn index(self) -> usize {
+        match self {
+            Player::Player1 => 0,
+            Player::Player2 => 1,
+        }
+    }
+}
+
+/// How a game ended other than by a flag.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum Outcome {
+    Checkmate { winner: Player },
+    Resignation { winner: Player },
+    Stalemate,
+    DrawAgreed,
+    /// A flag fell but the opponent couldn't mate, which draws the game.
+    NoMatingMaterial,
+}
+
+impl Outcome {
+    /// The winner, none for a draw.
+    pub fn winner(&self) -> Option<Player> {
+        match self {
+            Outcome::Checkmate { winner } | Outcome::Resignation { winner } => Some(*winner),
+            Outcome::Stalemate | Outcome::DrawAgreed | Outcome::NoMatingMaterial => None,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Checkmate { winner } => write!(f, "checkmate, {winner} wins"),
+            Outcome::Resignation { winner } => write!(f, "resignation, {winner} wins"),
+            Outcome::Stalemate => write!(f, "stalemate"),
+            Outcome::DrawAgreed => write!(f, "draw agreed"),
+            Outcome::NoMatingMaterial => write!(f, "draw, no mating material against the flag"),
+        }
+    }
+}
+
+/// How the increment is given when a player finishes their move.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, strum::Display, strum::EnumIter, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IncrementKind {
+    /// The whole increment is added.
+    #[default]
+    Fischer,
+    /// The increment is added up to the time spent on the move, so the clock never gains time.
+    Bronstein,
+    /// The clock waits for the increment to pass before counting down, nothing is added.
+    #[strum(to_string = "US delay")]
+    Delay,
+}
+
+impl IncrementKind {
+    /// The next kind, wrapping around after the last one.
+    pub fn next(self) -> Self {
+        let mut kinds = IncrementKind::iter()
+            .cycle()
+            .skip_while(|&kind| kind != self);
+        kinds.nth(1).unwrap_or_default()
+    }
+}
+
+/// Time given once the main time of a player ran out.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Overtime {
+    /// The flag falls with the main time.
+    #[default]
+    None,
+    /// Japanese byoyomi: `periods` periods of `seconds`, each move made within a period gives
+    /// it back whole and a period that runs out is lost.
+    Byoyomi { periods: u32, seconds: u64 },
+    /// Canadian overtime: `moves` moves to make in each period of `seconds`, the flag falls
+    /// when a period runs out before they are made.
+    Canadian { moves: u32, seconds: u64 },
+}
+
+impl Display for Overtime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Overtime::None => Ok(()),
+            Overtime::Byoyomi { periods, seconds } => write!(f, "{periods}×{seconds}s"),
+            Overtime::Canadian { moves, seconds } if seconds.is_multiple_of(60) => {
+                write!(f, "{moves}/{}m", seconds / 60)
+            }
+            Overtime::Canadian { moves, seconds } => write!(f, "{moves}/{seconds}s"),
+        }
+    }
+}
+
+/// A stage of a time control played in stages, whose time is added to a player's clock once
+/// they made the moves of the stage before.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stage {
+    /// Moves to make in the stage before this one.
+    pub moves: u32,
+    pub minutes: u64,
+    /// Increment from this stage on, in seconds.
+    #[serde(default)]
+    pub increment: u64,
+}
+
+/// The time a player took for a move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveTime {
+    pub player: Player,
+    /// Number of the move, starting at 1.
+    pub move_number: u32,
+    pub time: Duration,
+    /// Time left to the player after the move, increment included.
+    pub left: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClockState {
+    NotStarted,
+    Pause,
+    Player(Player),
+}
+
+#[derive(Debug, Clone)]
+pub struct Clock {
+    player1: Time,
+    player2: Time,
+    state: ClockState,
+    resume_player: Player, // player turn before pause
+    first_to_move: Player,
+    /// Increment of each player.
+    increment: [Duration; 2],
+    increment_kind: IncrementKind,
+    /// Time spent on the move being played.
+    move_elapsed: Duration,
+    overtime: Overtime,
+    /// Time running off the clock of the player to move is added to the opponent's.
+    hourglass: bool,
+    /// Black has a fifth less time and wins on a draw.
+    armageddon: bool,
+    /// Most time a hit can raise a clock to.
+    max_time: Option<Duration>,
+    /// Stages after the one of the time control.
+    stages: Vec<Stage>,
+    /// Stages reached by each player.
+    stage: [usize; 2],
+    /// Byoyomi periods or Canadian moves of the period left to each player, once their main
+    /// time ran out.
+    overtime_left: [Option<u32>; 2],
+    time_ctrl: PlayerCtrls,
+    moves: u32, // moves made by both players
+    /// Time taken for each move of the game, by both players.
+    move_times: Vec<MoveTime>,
+    outcome: Option<Outcome>,
+    /// Last update by [`Clock::tick_timer`], none while the clock isn't run on wall time.
+    ticked_at: Option<Instant>,
+}
+
+/// Serializable view of the clock, shared by the non-TUI frontends.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub running: bool,
+    pub paused: bool,
+    /// Player whose clock is running (or would be, when paused).
+    pub turn: Option<Player>,
+    pub player1_ms: u64,
+    pub player2_ms: u64,
+    /// Number of the move being played, starting at 1.
+    pub move_number: u32,
+    pub flagged: Option<Player>,
+    /// How the game ended, when it wasn't by a flag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<Outcome>,
+    /// Player playing white, i.e. moving first.
+    pub white: Player,
+    pub time_control: String,
+    /// Player 2's time control, when it differs from Player 1's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player2_time_control: Option<String>,
+    /// The stages following the one of the time control.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<Stage>,
+    /// The increment of each player in the stage they reached, Player 1's first.
+    #[serde(default)]
+    pub increment_ms: [u64; 2],
+    #[serde(default)]
+    pub increment_kind: IncrementKind,
+    #[serde(default)]
+    pub overtime: Overtime,
+    /// Periods or moves of overtime left to each player once in overtime.
+    #[serde(default)]
+    pub overtime_left: [Option<u32>; 2],
+    #[serde(default)]
+    pub hourglass: bool,
+    /// Black has draw odds: a draw is a win for Black.
+    #[serde(default)]
+    pub armageddon: bool,
+    /// Most time a hit can raise a clock to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ms: Option<u64>,
+    /// The names typed for Player 1 and Player 2, set by the app showing the clock.
+    #[serde(default, skip_serializing_if = "unnamed")]
+    pub names: [Option<String>; 2],
+}
+
+fn unnamed(names: &[Option<String>; 2]) -> bool {
+    names.iter().all(Option::is_none)
+}
+
+impl Snapshot {
+    /// Result in PGN notation once a player flagged or the game ended otherwise.
+    pub fn result(&self) -> Option<&'static str> {
+        let winner = match (self.outcome, self.flagged) {
+            (Some(outcome), _) => outcome.winner(),
+            (None, Some(loser)) => Some(loser.opponent()),
+            (None, None) => return None,
+        };
+        let winner = match winner {
+            None if self.armageddon => Some(self.white.opponent()),
+            winner => winner,
+        };
+        Some(match winner {
+            Some(winner) if winner == self.white => "1-0",
+            Some(_) => "0-1",
+            None => "1/2-1/2",
+        })
+    }
+
+    /// The times as arguments of the UCI `go` command, in milliseconds:
+    /// `go wtime 179000 btime 180000 winc 2000 binc 2000`.
+    pub fn uci(&self) -> String {
+        let [inc1, inc2] = self.increment_ms;
+        let ((white, winc), (black, binc)) = match self.white {
+            Player::Player1 => ((self.player1_ms, inc1), (self.player2_ms, inc2)),
+            Player::Player2 => ((self.player2_ms, inc2), (self.player1_ms, inc1)),
+        };
+        format!("go wtime {white} btime {black} winc {winc} binc {binc}")
+    }
+}
+
+/// Discrete changes of the clock, derived by comparing two snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ClockEvent {
+    NewGame { time_control: String },
+    Start { player: Player },
+    /// `player` finished their move and passed the turn.
+    Hit { player: Player, move_number: u32 },
+    Pause,
+    Resume,
+    Flag { player: Player },
+    /// The game ended other than by a flag.
+    End { outcome: Outcome },
+}
+
+impl ClockEvent {
+    /// Events that happened between the `prev` and `next` states.
+    pub fn between(prev: &Snapshot, next: &Snapshot) -> Vec<ClockEvent> {
+        let mut events = vec![];
+        let restarted = prev.turn.is_some() && next.turn.is_none();
+        if restarted || prev.time_control != next.time_control {
+            events.push(ClockEvent::NewGame {
+                time_control: next.time_control.clone(),
+            });
+        }
+        match (prev.turn, next.turn) {
+            (None, Some(player)) => events.push(ClockEvent::Start { player }),
+            (Some(player), Some(next_player)) if player != next_player => {
+                events.push(ClockEvent::Hit {
+                    player,
+                    move_number: prev.move_number,
+                })
+            }
+            _ => {}
+        }
+        match (prev.paused, next.paused) {
+            (false, true) => events.push(ClockEvent::Pause),
+            (true, false) => events.push(ClockEvent::Resume),
+            _ => {}
+        }
+        if let (None, Some(player)) = (prev.flagged, next.flagged) {
+            events.push(ClockEvent::Flag { player });
+        }
+        if let (None, Some(outcome)) = (prev.outcome, next.outcome) {
+            events.push(ClockEvent::End { outcome });
+        }
+        events
+    }
+}
+
+impl Clock {
+    pub fn burning(time: Duration) -> bool {
+        time < Duration::from_secs(21)
+    }
+
+    /// Sets the clock up for a new game, with a preset [`TimeCtrl`], any base time and
+    /// increment, or a different one for each player.
+    pub fn set(&mut self, ctrl: impl Into<PlayerCtrls>) {
+        let ctrl = ctrl.into();
+        self.time_ctrl = ctrl;
+        let [(base1, inc1), (base2, inc2)] = ctrl.0;
+        self.player1.0 = base1;
+        self.player2.0 = base2;
+        self.increment = [inc1, inc2];
+        self.move_elapsed = Duration::ZERO;
+        self.overtime = Overtime::None;
+        self.overtime_left = [None; 2];
+        self.stages.clear();
+        self.stage = [0; 2];
+        self.hourglass = false;
+        self.armageddon = false;
+        self.max_time = None;
+        self.state = ClockState::NotStarted;
+        self.moves = 0;
+        self.move_times.clear();
+        self.outcome = None;
+    }
+
+    /// Gives both players `time`, for time controls that aren't built in.
+    pub fn set_time(&mut self, time: Duration) {
+        self.player1.0 = time;
+        self.player2.0 = time;
+    }
+
+    /// Adds overtime after the main time of the game set up with [`Clock::set`].
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use chessclock_core::{clock::{Clock, Overtime}, sim::Simulator, tabs::TimeCtrl};
+    /// let mut clock = Clock::default();
+    /// clock.set(TimeCtrl::parse("5s+0").unwrap());
+    /// clock.set_overtime(Overtime::Byoyomi { periods: 3, seconds: 10 });
+    /// let mut sim = Simulator::from_clock(clock);
+    /// // Player 1 loses a period, then moves in the next one and gets it back whole
+    /// sim.play(&[Duration::from_secs(17), Duration::from_secs(1)]);
+    /// let snapshot = sim.clock().snapshot();
+    /// assert_eq!(snapshot.overtime_left, [Some(2), None]);
+    /// assert_eq!(snapshot.player1_ms, 10_000);
+    /// ```
+    pub fn set_overtime(&mut self, overtime: Overtime) {
+        self.overtime = overtime;
+        // Without main time the game starts in overtime
+        for player in [Player::Player1, Player::Player2] {
+            if self.time(player).is_zero() {
+                self.next_period(player);
+            }
+        }
+    }
+
+    /// Makes the time running off the clock of the player to move flow into the opponent's,
+    /// as in an hourglass, for the game set up with [`Clock::set`].
+    pub fn set_hourglass(&mut self, hourglass: bool) {
+        self.hourglass = hourglass;
+    }
+
+    /// Caps the time the increments of the game set up with [`Clock::set`] can raise a clock
+    /// to, none removes the cap.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use chessclock_core::{clock::Clock, sim::Simulator, tabs::TimeCtrl};
+    /// let mut clock = Clock::default();
+    /// clock.set(TimeCtrl::parse("3+2").unwrap());
+    /// clock.set_max_time(Some(Duration::from_secs(181)));
+    /// let mut sim = Simulator::from_clock(clock);
+    /// // Player 1 moves at once twice and gets a second of the first increment only
+    /// sim.play(&[Duration::ZERO, Duration::ZERO, Duration::ZERO]);
+    /// assert_eq!(sim.clock().snapshot().player1_ms, 181_000);
+    /// ```
+    pub fn set_max_time(&mut self, max_time: Option<Duration>) {
+        self.max_time = max_time;
+    }
+
+    /// Sets the clock up for a game of a preset, see [`Clock::set`].
+    pub fn set_preset(&mut self, ctrl: TimeCtrl) {
+        self.set(ctrl);
+        if ctrl == TimeCtrl::Armageddon {
+            self.set_armageddon();
+        }
+    }
+
+    /// Makes the game set up with [`Clock::set`] an Armageddon game: Black has a fifth less
+    /// time and wins on a draw.
+    ///
+    /// ```
+    /// # use chessclock_core::{clock::{Clock, Player}, tabs::TimeCtrl};
+    /// let mut clock = Clock::default();
+    /// clock.set(TimeCtrl::parse("10+0").unwrap());
+    /// clock.set_armageddon();
+    /// let snapshot = clock.snapshot();
+    /// assert_eq!((snapshot.player1_ms, snapshot.player2_ms), (600_000, 480_000));
+    /// // White keeps the longer time when Black moves first
+    /// clock.flip_first_to_move();
+    /// assert_eq!(clock.snapshot().player1_ms, 480_000);
+    /// assert_eq!(clock.black(), Player::Player1);
+    /// ```
+    pub fn set_armageddon(&mut self) {
+        if self.armageddon {
+            return;
+        }
+        self.armageddon = true;
+        let black = self.black();
+        let time = self.time_mut(black);
+        *time -= *time / 5;
+    }
+
+    pub fn is_armageddon(&self) -> bool {
+        self.armageddon
+    }
+
+    /// The player playing black, i.e. moving second.
+    pub fn black(&self) -> Player {
+        self.first_to_move.opponent()
+    }
+
+    /// Adds the stages following the one of the time control set up with [`Clock::set`], e.g.
+    /// 30 minutes after the first 40 moves.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use chessclock_core::{clock::{Clock, Stage}, sim::Simulator, tabs::TimeCtrl};
+    /// let mut clock = Clock::default();
+    /// clock.set(TimeCtrl::parse("10+0").unwrap());
+    /// clock.set_stages(vec![Stage { moves: 2, minutes: 5, increment: 3 }]);
+    /// let mut sim = Simulator::from_clock(clock);
+    /// let secs = Duration::from_secs;
+    /// sim.play(&[secs(10), secs(10), secs(10), secs(10), secs(10)]);
+    /// // Player 1 reached the second stage on their second move and got its increment since
+    /// assert_eq!(sim.clock().snapshot().player1_ms, (600 - 30 + 300 + 3) * 1000);
+    /// ```
+    pub fn set_stages(&mut self, stages: Vec<Stage>) {
+        self.stages = stages;
+    }
+
+    /// The time taken for each move of the game so far, oldest first.
+    pub fn move_times(&self) -> &[MoveTime] {
+        &self.move_times
+    }
+
+    /// The stages after the one of the time control.
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    pub fn state(&self) -> ClockState {
+        self.state
+    }
+
+    /// The times of both players, Player 1's first.
+    pub fn times(&self) -> [Time; 2] {
+        [self.player1, self.player2]
+    }
+
+    /// The time control set up with [`Clock::set`].
+    pub fn time_ctrl(&self) -> PlayerCtrls {
+        self.time_ctrl
+    }
+
+    pub fn first_to_move(&self) -> Player {
+        self.first_to_move
+    }
+
+    /// The player whose clock is running, or would be when paused.
+    pub fn turn(&self) -> Option<Player> {
+        match self.state {
+            ClockState::Player(p) => Some(p),
+            ClockState::Pause => Some(self.resume_player),
+            ClockState::NotStarted => None,
+        }
+    }
+
+    /// How the game ended, when it wasn't by a flag.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    pub fn overtime(&self) -> Overtime {
+        self.overtime
+    }
+
+    /// Byoyomi periods or Canadian moves of the period left to `player`, once their main time
+    /// ran out.
+    pub fn overtime_left(&self, player: Player) -> Option<u32> {
+        self.overtime_left[player.index()]
+    }
+
+    pub fn is_hourglass(&self) -> bool {
+        self.hourglass
+    }
+
+    /// Time spent on the move being played.
+    pub fn move_elapsed(&self) -> Duration {
+        self.move_elapsed
+    }
+
+    /// The next stage `player` would reach with their next move, if it's due.
+    fn stage_due(&self, player: Player) -> Option<&Stage> {
+        let reached = self.stage[player.index()];
+        let due: u32 = self
+            .stages
+            .iter()
+            .take(reached + 1)
+            .map(|stage| stage.moves)
+            .sum();
+        let made = self.moves_by(player);
+        self.stages.get(reached).filter(|_| made + 1 == due)
+    }
+
+    /// Moves `player` made so far.
+    fn moves_by(&self, player: Player) -> u32 {
+        (self.moves + u32::from(player == self.first_to_move)) / 2
+    }
+
+    /// The increment of `player` in the stage they reached.
+    fn increment_of(&self, player: Player) -> Duration {
+        match self.stage[player.index()].checked_sub(1) {
+            Some(stage) => Duration::from_secs(self.stages[stage].increment),
+            None => self.increment[player.index()],
+        }
+    }
+
+    /// Refills the time of `player`, which just ran out, from their overtime if any is left.
+    fn next_period(&mut self, player: Player) {
+        let (left, seconds) = match (self.overtime, self.overtime_left[player.index()]) {
+            (Overtime::None, _) => return,
+            (Overtime::Byoyomi { periods, seconds }, None) => (periods, seconds),
+            (Overtime::Byoyomi { seconds, .. }, Some(left)) => (left.saturating_sub(1), seconds),
+            (Overtime::Canadian { moves, seconds }, None) => (moves, seconds),
+            // The period ran out before the moves were made
+            (Overtime::Canadian { .. }, Some(_)) => return,
+        };
+        self.overtime_left[player.index()] = Some(left);
+        if left > 0 {
+            *self.time_mut(player) = Duration::from_secs(seconds);
+        }
+    }
+
+    fn time(&self, player: Player) -> Duration {
+        match player {
+            Player::Player1 => self.player1.0,
+            Player::Player2 => self.player2.0,
+        }
+    }
+
+    fn time_mut(&mut self, player: Player) -> &mut Duration {
+        match player {
+            Player::Player1 => &mut self.player1.0,
+            Player::Player2 => &mut self.player2.0,
+        }
+    }
+
+    pub fn increment_kind(&self) -> IncrementKind {
+        self.increment_kind
+    }
+
+    /// Changes how the increment is given, from the next hit on.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use chessclock_core::{clock::{Clock, IncrementKind}, sim::Simulator, tabs::TimeCtrl};
+    /// let mut clock = Clock::default();
+    /// clock.set(TimeCtrl::parse("3+2").unwrap());
+    /// clock.set_increment_kind(IncrementKind::Bronstein);
+    /// let mut sim = Simulator::from_clock(clock);
+    /// sim.play(&[Duration::from_secs(1), Duration::from_secs(5)]);
+    /// let snapshot = sim.clock().snapshot();
+    /// // A move made in a second only gets that second back
+    /// assert_eq!(snapshot.player1_ms, 180_000);
+    /// assert_eq!(snapshot.player2_ms, 180_000 - 5_000 + 2_000);
+    /// ```
+    pub fn set_increment_kind(&mut self, kind: IncrementKind) {
+        self.increment_kind = kind;
+    }
+
+    /// The time added to the player to move when they hit the clock now.
+    fn credit(&self) -> Duration {
+        let Some(player) = self.curr_player() else {
+            return Duration::ZERO;
+        };
+        let period = |seconds| Duration::from_secs(seconds).saturating_sub(self.time(player));
+        match (self.overtime, self.overtime_left[player.index()]) {
+            // A move within the period gives it back whole
+            (Overtime::Byoyomi { seconds, .. }, Some(_)) => return period(seconds),
+            // The last move of the period starts the next one
+            (Overtime::Canadian { seconds, .. }, Some(left)) if left <= 1 => {
+                return period(seconds);
+            }
+            (Overtime::Canadian { .. }, Some(_)) => return Duration::ZERO,
+            _ => {}
+        }
+        let increment = self.increment_of(player);
+        let increment = match self.increment_kind {
+            IncrementKind::Fischer => increment,
+            IncrementKind::Bronstein => increment.min(self.move_elapsed),
+            IncrementKind::Delay => Duration::ZERO,
+        };
+        // The time of the next stage comes with the last move of the stage before
+        let stage = self.stage_due(player).map_or(0, |stage| stage.minutes * 60);
+        let credit = increment + Duration::from_secs(stage);
+        match self.max_time {
+            Some(max) => credit.min(max.saturating_sub(self.time(player))),
+            None => credit,
+        }
+    }
+
+    /// What's left of the delay of the player to move before their time counts down.
+    pub fn delay_left(&self) -> Option<Duration> {
+        let player = self.curr_player().unwrap_or(self.resume_player);
+        let increment = self.increment_of(player);
+        let left = increment.saturating_sub(self.move_elapsed);
+        let delayed = self.increment_kind == IncrementKind::Delay && !left.is_zero();
+        (delayed && matches!(self.state, ClockState::Player(_) | ClockState::Pause)).then_some(left)
+    }
+
+    pub fn curr_player(&self) -> Option<Player> {
+        match self.state {
+            ClockState::Player(p) => Some(p),
+            _ => None,
+        }
+    }
+
+    pub fn hit(&mut self) {
+        self.settle();
+        if self.is_over() {
+            return;
+        }
+        #[cfg(debug_assertions)]
+        let before = self.clone();
+        let credit = self.credit();
+        if let ClockState::Player(p) = self.state {
+            if self.stage_due(p).is_some() {
+                self.stage[p.index()] += 1;
+            }
+            self.count_overtime_move(p);
+            let left = *self.time_mut(p) + credit;
+            self.move_times.push(MoveTime {
+                player: p,
+                move_number: self.move_number(),
+                time: self.move_elapsed,
+                left,
+            });
+            self.moves += 1;
+        }
+        match self.state {
+            ClockState::NotStarted => self.state = ClockState::Player(self.first_to_move),
+            ClockState::Pause => (),
+            ClockState::Player(p) => match p {
+                Player::Player1 => {
+                    self.state = ClockState::Player(Player::Player2);
+                    self.player1.0 += credit;
+                }
+                Player::Player2 => {
+                    self.state = ClockState::Player(Player::Player1);
+                    self.player2.0 += credit;
+                }
+            },
+        }
+        if !matches!(self.state, ClockState::Pause) {
+            self.move_elapsed = Duration::ZERO;
+        }
+        #[cfg(debug_assertions)]
+        self.check_hit(&before);
+    }
+
+    /// Counts a move of `player` towards the moves of their Canadian period.
+    fn count_overtime_move(&mut self, player: Player) {
+        let left = &mut self.overtime_left[player.index()];
+        if let (Overtime::Canadian { moves, .. }, Some(n)) = (self.overtime, left.as_mut()) {
+            *n = if *n <= 1 { moves } else { *n - 1 };
+        }
+    }
+
+    /// Hit on behalf of `player`, ignored unless it's their turn or the clock isn't started.
+    pub fn hit_by(&mut self, player: Player) {
+        match self.state {
+            ClockState::Player(p) if p != player => (),
+            _ => self.hit(),
+        }
+    }
+
+    /// Takes the time elapsed since the last update off the running clock, so it keeps to
+    /// wall time however late the ticks come.
+    pub fn tick_timer(&mut self) {
+        let now = Instant::now();
+        let elapsed = self.ticked_at.map_or(Duration::ZERO, |at| now - at);
+        self.ticked_at = Some(now);
+        self.tick(elapsed);
+    }
+
+    /// Takes `elapsed` off the running clock, after what's left of the delay and on into the
+    /// next overtime periods.
+    pub fn tick(&mut self, elapsed: Duration) {
+        if self.outcome.is_some() {
+            return;
+        }
+        #[cfg(debug_assertions)]
+        let before = self.clone();
+        if let ClockState::Player(p) = self.state {
+            let delay = self.delay_left().unwrap_or_default().min(elapsed);
+            self.move_elapsed += elapsed;
+            let mut left = elapsed - delay;
+            while !left.is_zero() && !self.time(p).is_zero() {
+                let time = self.time_mut(p);
+                let used = left.min(*time);
+                *time -= used;
+                left -= used;
+                if time.is_zero() {
+                    self.next_period(p);
+                }
+                if self.hourglass {
+                    *self.time_mut(p.opponent()) += used;
+                }
+            }
+        }
+        #[cfg(debug_assertions)]
+        self.check_tick(&before, elapsed);
+    }
+
+    /// Brings the clock up to wall time before a hit or a pause, when it runs on it.
+    fn settle(&mut self) {
+        if self.ticked_at.is_some() {
+            self.tick_timer();
+        }
+    }
+
+    pub fn is_time_out(&self) -> bool {
+        self.player1.0 == Duration::ZERO || self.player2.0 == Duration::ZERO
+    }
+
+    /// Whether a flag fell or the game ended otherwise.
+    pub fn is_over(&self) -> bool {
+        self.is_time_out() || self.outcome.is_some()
+    }
+
+    /// Stops the clock on a game decided on the board.
+    pub fn end(&mut self, outcome: Outcome) {
+        if !self.is_over() {
+            self.outcome = Some(outcome);
+        }
+    }
+
+    /// Draws the game lost on time when the opponent of the flagged player can't mate.
+    pub fn draw_flag(&mut self) {
+        if self.is_time_out() && self.outcome.is_none() {
+            self.outcome = Some(Outcome::NoMatingMaterial);
+        }
+    }
+
+    /// Rebuilds a clock from a snapshot, e.g. one received from another instance.
+    pub fn from_snapshot(snapshot: &Snapshot) -> Clock {
+        let mut clock = Clock::default();
+        let player1 = tabs::parse_pgn(&snapshot.time_control).unwrap_or(TimeCtrl::default().into());
+        let player2 = snapshot
+            .player2_time_control
+            .as_deref()
+            .and_then(tabs::parse_pgn);
+        clock.set(PlayerCtrls([player1, player2.unwrap_or(player1)]));
+        clock.set_stages(snapshot.stages.clone());
+        clock.player1 = Time(Duration::from_millis(snapshot.player1_ms));
+        clock.player2 = Time(Duration::from_millis(snapshot.player2_ms));
+        clock.first_to_move = snapshot.white;
+        clock.increment_kind = snapshot.increment_kind;
+        clock.overtime = snapshot.overtime;
+        clock.overtime_left = snapshot.overtime_left;
+        clock.hourglass = snapshot.hourglass;
+        clock.armageddon = snapshot.armageddon;
+        clock.max_time = snapshot.max_ms.map(Duration::from_millis);
+        clock.outcome = snapshot.outcome;
+        clock.state = match snapshot.turn {
+            None => ClockState::NotStarted,
+            Some(p) if snapshot.paused => {
+                clock.resume_player = p;
+                ClockState::Pause
+            }
+            Some(p) => ClockState::Player(p),
+        };
+        let black_to_move = snapshot.turn == Some(snapshot.white.opponent());
+        // A peer or a file could send move 0, taken as the first move
+        clock.moves = snapshot.move_number.saturating_sub(1) * 2 + u32::from(black_to_move);
+        for player in [Player::Player1, Player::Player2] {
+            let made = clock.moves_by(player);
+            let mut due = 0;
+            clock.stage[player.index()] = (clock.stages.iter())
+                .take_while(|stage| {
+                    due += stage.moves;
+                    due <= made
+                })
+                .count();
+        }
+        clock
+    }
+
+    /// Number of the move being played, starting at 1.
+    pub fn move_number(&self) -> u32 {
+        self.moves / 2 + 1
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            running: matches!(self.state, ClockState::Player(_)) && !self.is_over(),
+            paused: matches!(self.state, ClockState::Pause),
+            turn: self.turn(),
+            player1_ms: self.player1.0.as_millis() as u64,
+            player2_ms: self.player2.0.as_millis() as u64,
+            move_number: self.move_number(),
+            flagged: self.flagged(),
+            outcome: self.outcome,
+            white: self.first_to_move,
+            time_control: tabs::pgn(self.time_ctrl.0[0]),
+            player2_time_control: match self.time_ctrl.0 {
+                [p1, p2] if p1 == p2 => None,
+                [_, p2] => Some(tabs::pgn(p2)),
+            },
+            stages: self.stages.clone(),
+            increment_ms: [Player::Player1, Player::Player2]
+                .map(|player| self.increment_of(player).as_millis() as u64),
+            increment_kind: self.increment_kind,
+            overtime: self.overtime,
+            overtime_left: self.overtime_left,
+            hourglass: self.hourglass,
+            armageddon: self.armageddon,
+            max_ms: self.max_time.map(|max| max.as_millis() as u64),
+            names: Default::default(),
+        }
+    }
+
+    /// The player whose time ran out, if any.
+    pub fn flagged(&self) -> Option<Player> {
+        if self.player1.0 == Duration::ZERO {
+            Some(Player::Player1)
+        } else if self.player2.0 == Duration::ZERO {
+            Some(Player::Player2)
+        } else {
+            None
+        }
+    }
+
+    /// Adds `delta_ms` milliseconds to `player`'s time, or subtracts them when negative.
+    pub fn adjust(&mut self, player: Player, delta_ms: i64) {
+        let time = self.time_mut(player);
+        let delta = Duration::from_millis(delta_ms.unsigned_abs());
+        *time = if delta_ms < 0 {
+            time.saturating_sub(delta)
+        } else {
+            time.saturating_add(delta)
+        };
+    }
+
+    pub fn pause(&mut self, resume_player: Player) {
+        self.settle();
+        #[cfg(debug_assertions)]
+        let before = self.clone();
+        match self.state {
+            ClockState::Pause => self.state = ClockState::Player(self.resume_player),
+            ClockState::Player(_) => {
+                self.resume_player = resume_player;
+                self.state = ClockState::Pause;
+            }
+            ClockState::NotStarted => (),
+        }
+        #[cfg(debug_assertions)]
+        self.check_pause(&before);
+    }
+
+    /// Gives the first move to the other player, before the game.
+    pub fn flip_first_to_move(&mut self) {
+        if matches!(self.state, ClockState::NotStarted) {
+            self.first_to_move = self.first_to_move.opponent();
+            // Black's shorter time goes with the colour
+            if self.armageddon {
+                std::mem::swap(&mut self.player1, &mut self.player2);
+                self.increment.swap(0, 1);
+                self.time_ctrl.0.swap(0, 1);
+            }
+        }
+    }
+
+    /// Gives the first move to `player`, before the game.
+    pub fn set_first_to_move(&mut self, player: Player) {
+        if self.first_to_move != player {
+            self.flip_first_to_move();
+        }
+    }
+
+    /// Swaps the left and right clocks before the game, with their times and colours, when
+    /// the players sat down the other way round.
+    pub fn swap_sides(&mut self) {
+        if matches!(self.state, ClockState::NotStarted) {
+            std::mem::swap(&mut self.player1, &mut self.player2);
+            self.increment.swap(0, 1);
+            self.time_ctrl.0.swap(0, 1);
+            self.first_to_move = self.first_to_move.opponent();
+        }
+    }
+}
+
+/// Rules every change of the clock follows, checked in debug builds to catch the bugs of new
+/// time controls early.
+#[cfg(debug_assertions)]
+impl Clock {
+    /// Panics with the states before and after `change` unless `ok`.
+    fn invariant(&self, before: &Clock, change: &str, ok: bool, rule: &str) {
+        assert!(
+            ok,
+            "{change} broke the rule that {rule}\nbefore: {before:?}\nafter:  {self:?}"
+        );
+    }
+
+    fn check_turn(&self, before: &Clock, change: &str) {
+        let turn = match self.state {
+            ClockState::Player(p) => p,
+            ClockState::Pause => self.resume_player,
+            ClockState::NotStarted => return,
+        };
+        let expected = if self.moves.is_multiple_of(2) {
+            self.first_to_move
+        } else {
+            self.first_to_move.opponent()
+        };
+        self.invariant(before, change, turn == expected, "the players move in turn");
+    }
+
+    fn check_hit(&self, before: &Clock) {
+        let change = "a hit";
+        if let ClockState::Player(p) = before.state {
+            let passed = self.state == ClockState::Player(p.opponent());
+            self.invariant(
+                before,
+                change,
+                passed && self.moves == before.moves + 1,
+                "a hit passes the turn",
+            );
+            let kept = self.time(p) == before.time(p) + before.credit()
+                && self.time(p.opponent()) == before.time(p.opponent());
+            self.invariant(
+                before,
+                change,
+                kept,
+                "only the increment is added to the time of the player who moved",
+            );
+        } else {
+            let kept = self.player1.0 == before.player1.0
+                && self.player2.0 == before.player2.0
+                && self.moves == before.moves;
+            self.invariant(
+                before,
+                change,
+                kept,
+                "times don't change before the game or while paused",
+            );
+        }
+        self.check_turn(before, change);
+    }
+
+    fn check_tick(&self, before: &Clock, tick: Duration) {
+        let change = "a timer tick";
+        for player in [Player::Player1, Player::Player2] {
+            let next_period = self.overtime_left != before.overtime_left;
+            let ok = match before.time(player).checked_sub(self.time(player)) {
+                Some(used) if self.curr_player() == Some(player) => used <= tick,
+                Some(used) => used.is_zero(),
+                // Sand flowing in from the running clock
+                None if self.hourglass && self.curr_player() == Some(player.opponent()) => {
+                    self.time(player) - before.time(player) <= tick
+                }
+                None => next_period,
+            };
+            self.invariant(
+                before,
+                change,
+                ok,
+                "only the running clock loses time, the time elapsed at most, until the next period or \
+                 into the other clock of an hourglass",
+            );
+        }
+        self.invariant(
+            before,
+            change,
+            self.state == before.state && self.moves == before.moves,
+            "a timer tick doesn't pass the turn",
+        );
+    }
+
+    fn check_pause(&self, before: &Clock) {
+        let change = "a pause";
+        let kept = self.player1.0 == before.player1.0
+            && self.player2.0 == before.player2.0
+            && self.moves == before.moves;
+        self.invariant(before, change, kept, "pausing doesn't change the times");
+        self.check_turn(before, change);
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self {
+            increment: [Duration::from_secs(1); 2],
+            increment_kind: IncrementKind::default(),
+            move_elapsed: Duration::ZERO,
+            overtime: Overtime::None,
+            overtime_left: [None; 2],
+            hourglass: false,
+            armageddon: false,
+            max_time: None,
+            stages: vec![],
+            stage: [0; 2],
+            player1: Time(Duration::from_secs(1)),
+            player2: Time(Duration::from_secs(1)),
+            state: ClockState::NotStarted,
+            resume_player: Player::Player1,
+            time_ctrl: TimeCtrl::Tab1.into(),
+            first_to_move: Player::default(),
+            moves: 0,
+            move_times: vec![],
+            outcome: None,
+            ticked_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sim::Simulator, tabs::CtrlOption};
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    fn ctrl(base: u64, increment: u64) -> CtrlOption {
+        (secs(base), secs(increment))
+    }
+
+    /// A clock set up with `ctrl` and changed by `setup` before the game.
+    fn clock_with(ctrl: CtrlOption, setup: impl FnOnce(&mut Clock)) -> Clock {
+        let mut clock = Clock::default();
+        clock.set(ctrl);
+        setup(&mut clock);
+        clock
+    }
+
+    #[test]
+    fn bronstein_gives_back_at_most_the_time_used() {
+        let clock = clock_with(ctrl(180, 2), |c| {
+            c.set_increment_kind(IncrementKind::Bronstein)
+        });
+        let mut sim = Simulator::from_clock(clock);
+        sim.play(&[secs(1), secs(5), secs(3)]);
+        let snapshot = sim.clock().snapshot();
+        assert_eq!(snapshot.player1_ms, 180_000 - 1_000 + 1_000 - 3_000 + 2_000);
+        assert_eq!(snapshot.player2_ms, 180_000 - 5_000 + 2_000);
+    }
+
+    #[test]
+    fn us_delay_counts_down_only_after_the_delay() {
+        let clock = clock_with(ctrl(180, 2), |c| c.set_increment_kind(IncrementKind::Delay));
+        let mut sim = Simulator::from_clock(clock);
+        sim.hit().advance(Duration::from_millis(1_500));
+        assert_eq!(sim.clock().snapshot().player1_ms, 180_000);
+        assert_eq!(sim.clock().delay_left(), Some(Duration::from_millis(500)));
+        sim.advance(Duration::from_millis(1_500)).hit();
+        // Nothing is added for the move either
+        assert_eq!(sim.clock().snapshot().player1_ms, 179_000);
+        sim.play(&[secs(5)]);
+        assert_eq!(sim.clock().snapshot().player2_ms, 177_000);
+    }
+
+    #[test]
+    fn byoyomi_refills_the_period_and_loses_the_ones_run_out() {
+        let clock = clock_with(ctrl(5, 0), |c| {
+            c.set_overtime(Overtime::Byoyomi {
+                periods: 3,
+                seconds: 10,
+            })
+        });
+        let mut sim = Simulator::from_clock(clock);
+        // 5 s of main time, a period lost and 2 s into the next one
+        sim.play(&[secs(17)]);
+        let snapshot = sim.clock().snapshot();
+        assert_eq!(snapshot.overtime_left, [Some(2), None]);
+        assert_eq!(snapshot.player1_ms, 10_000);
+        sim.play(&[secs(0), secs(25)]);
+        let snapshot = sim.clock().snapshot();
+        assert_eq!(snapshot.overtime_left[0], Some(0));
+        assert_eq!(snapshot.flagged, Some(Player::Player1));
+    }
+
+    #[test]
+    fn canadian_period_starts_over_after_its_moves() {
+        let clock = clock_with(ctrl(5, 0), |c| {
+            c.set_overtime(Overtime::Canadian {
+                moves: 2,
+                seconds: 20,
+            })
+        });
+        let mut sim = Simulator::from_clock(clock);
+        sim.play(&[secs(6)]);
+        let snapshot = sim.clock().snapshot();
+        // The first move of the period gives nothing back
+        assert_eq!(snapshot.overtime_left, [Some(1), None]);
+        assert_eq!(snapshot.player1_ms, 19_000);
+        sim.play(&[secs(0), secs(4)]);
+        let snapshot = sim.clock().snapshot();
+        assert_eq!(snapshot.overtime_left[0], Some(2));
+        assert_eq!(snapshot.player1_ms, 20_000);
+        // The moves of a period not made in time lose the game
+        sim.play(&[secs(0), secs(21)]);
+        assert_eq!(sim.clock().snapshot().flagged, Some(Player::Player1));
+    }
+
+    #[test]
+    fn stage_adds_its_time_and_increment_after_its_moves() {
+        let stage = Stage {
+            moves: 2,
+            minutes: 5,
+            increment: 3,
+        };
+        let clock = clock_with(ctrl(600, 0), |c| c.set_stages(vec![stage]));
+        let mut sim = Simulator::from_clock(clock);
+        sim.play(&[secs(10), secs(10), secs(10)]);
+        let snapshot = sim.clock().snapshot();
+        assert_eq!(snapshot.player1_ms, (600 - 20 + 300) * 1_000);
+        assert_eq!(snapshot.player2_ms, 590_000);
+        assert_eq!(snapshot.increment_ms, [3_000, 0]);
+        sim.play(&[secs(10), secs(10)]);
+        assert_eq!(sim.clock().snapshot().player1_ms, (880 - 10 + 3) * 1_000);
+    }
+
+    #[test]
+    fn hourglass_moves_the_time_used_to_the_opponent() {
+        let clock = clock_with(ctrl(60, 0), |c| c.set_hourglass(true));
+        let mut sim = Simulator::from_clock(clock);
+        sim.play(&[secs(10), secs(4)]);
+        let snapshot = sim.clock().snapshot();
+        assert_eq!(snapshot.player1_ms, 54_000);
+        assert_eq!(snapshot.player2_ms, 66_000);
+    }
+
+    #[test]
+    fn max_time_caps_the_increment_but_takes_nothing_off() {
+        let clock = clock_with(ctrl(180, 2), |c| c.set_max_time(Some(secs(179))));
+        let mut sim = Simulator::from_clock(clock);
+        sim.play(&[secs(0), secs(2)]);
+        let snapshot = sim.clock().snapshot();
+        assert_eq!(snapshot.player1_ms, 180_000);
+        assert_eq!(snapshot.player2_ms, 179_000);
+    }
+
+    /// Rebuilds the simulated clock from its snapshot and checks both go on the same way.
+    fn assert_round_trip(mut sim: Simulator, moves: &[Duration]) {
+        let snapshot = sim.clock().snapshot();
+        let restored = Clock::from_snapshot(&snapshot);
+        assert_eq!(restored.snapshot(), snapshot);
+        let mut restored = Simulator::from_clock(restored);
+        sim.play(moves);
+        restored.play(moves);
+        assert_eq!(restored.clock().snapshot(), sim.clock().snapshot());
+    }
+
+    #[test]
+    fn snapshot_round_trips_mid_stage() {
+        let stage = Stage {
+            moves: 2,
+            minutes: 5,
+            increment: 3,
+        };
+        let clock = clock_with(ctrl(600, 0), |c| c.set_stages(vec![stage]));
+        let mut sim = Simulator::from_clock(clock);
+        sim.play(&[secs(10), secs(10), secs(10)]);
+        assert_round_trip(sim, &[secs(10), secs(10), secs(10)]);
+    }
+
+    #[test]
+    fn snapshot_round_trips_in_overtime() {
+        let clock = clock_with(ctrl(5, 0), |c| {
+            c.set_overtime(Overtime::Canadian {
+                moves: 3,
+                seconds: 20,
+            })
+        });
+        let mut sim = Simulator::from_clock(clock);
+        sim.play(&[secs(6), secs(1)]);
+        assert_round_trip(sim, &[secs(4), secs(1), secs(4), secs(1)]);
+    }
+
+    #[test]
+    fn snapshot_round_trips_paused_with_odds() {
+        let mut clock = Clock::default();
+        clock.set(PlayerCtrls([ctrl(300, 3), ctrl(120, 1)]));
+        clock.set_increment_kind(IncrementKind::Bronstein);
+        clock.set_hourglass(true);
+        clock.set_max_time(Some(secs(310)));
+        clock.set_armageddon();
+        clock.flip_first_to_move();
+        let mut sim = Simulator::from_clock(clock);
+        sim.play(&[secs(2), secs(7)]).advance(secs(1)).pause();
+        assert!(sim.clock().snapshot().paused);
+        // Resumed first
+        assert_round_trip(sim, &[secs(3), secs(1)]);
+    }
+}
@@ -0,0 +1,6 @@
+//! The logic of the chess clock without any user interface, for GUI frontends, bots and
+//! tournament managers: the clock, its time controls and games played on virtual time.
+
+pub mod clock;
+pub mod sim;
+pub mod tabs;
@@ -0,0 +1,140 @@
+//! Games played on virtual time, to test time control rules without waiting for the clock.
+
+use std::time::Duration;
+
+use crate::{
+    clock::{Clock, ClockEvent, Player, Snapshot, TIMER_TICK},
+    tabs::PlayerCtrls,
+};
+
+/// A change of the clock in the [`Simulator`]'s timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// Virtual time since the simulation started.
+    pub at: Duration,
+    pub snapshot: Snapshot,
+    /// What changed since the previous step.
+    pub events: Vec<ClockEvent>,
+}
+
+/// Drives a [`Clock`] with scripted hits on virtual time and records every change.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use chessclock_core::{sim::Simulator, tabs::TimeCtrl};
+/// let mut sim = Simulator::new(TimeCtrl::parse("3+2").unwrap());
+/// let secs = Duration::from_secs;
+/// sim.play(&[secs(10), secs(20), secs(5)]);
+/// let last = sim.timeline().last().unwrap();
+/// // Player 1 used 15 seconds and got two increments
+/// assert_eq!(last.snapshot.player1_ms, 180_000 - 15_000 + 4_000);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    clock: Clock,
+    elapsed: Duration,
+    last: Snapshot,
+    timeline: Vec<Step>,
+}
+
+impl Simulator {
+    pub fn new(ctrl: impl Into<PlayerCtrls>) -> Self {
+        let mut clock = Clock::default();
+        clock.set(ctrl);
+        Self::from_clock(clock)
+    }
+
+    /// Simulates from the state of `clock`, e.g. one set up with odds.
+    pub fn from_clock(clock: Clock) -> Self {
+        Self {
+            last: clock.snapshot(),
+            clock,
+            elapsed: Duration::ZERO,
+            timeline: vec![],
+        }
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Virtual time since the simulation started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn timeline(&self) -> &[Step] {
+        &self.timeline
+    }
+
+    pub fn into_timeline(self) -> Vec<Step> {
+        self.timeline
+    }
+
+    /// Lets `time` pass in timer ticks, stopping early when a flag falls.
+    pub fn advance(&mut self, time: Duration) -> &mut Self {
+        let tick = Duration::from_millis(TIMER_TICK);
+        let end = self.elapsed + time;
+        while self.elapsed < end && !self.clock.is_over() {
+            // The last tick is shorter when `time` isn't a whole number of ticks
+            let tick = tick.min(end - self.elapsed);
+            self.clock.tick(tick);
+            self.elapsed += tick;
+        }
+        self.record();
+        self
+    }
+
+    /// Starts the clock or passes the turn.
+    pub fn hit(&mut self) -> &mut Self {
+        self.clock.hit();
+        self.record();
+        self
+    }
+
+    /// Hit on behalf of `player`, see [`Clock::hit_by`].
+    pub fn hit_by(&mut self, player: Player) -> &mut Self {
+        self.clock.hit_by(player);
+        self.record();
+        self
+    }
+
+    /// Pauses the running clock, or resumes it.
+    pub fn pause(&mut self) -> &mut Self {
+        self.clock
+            .pause(self.clock.curr_player().unwrap_or_default());
+        self.record();
+        self
+    }
+
+    /// Starts the clock if needed and plays one move per duration, each thought about for
+    /// that long before the hit. Stops when a flag falls.
+    pub fn play(&mut self, moves: &[Duration]) -> &mut Self {
+        if self.clock.curr_player().is_none() {
+            self.hit();
+        }
+        for &think in moves {
+            self.advance(think);
+            if self.clock.is_over() {
+                break;
+            }
+            self.hit();
+        }
+        self
+    }
+
+    /// Adds a step for the changes since the last one, if any.
+    fn record(&mut self) {
+        let snapshot = self.clock.snapshot();
+        if snapshot == self.last {
+            return;
+        }
+        let events = ClockEvent::between(&self.last, &snapshot);
+        self.last = snapshot.clone();
+        self.timeline.push(Step {
+            at: self.elapsed,
+            snapshot,
+            events,
+        });
+    }
+}
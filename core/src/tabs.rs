@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, FromRepr, IntoEnumIterator};
+
+use crate::clock::Stage;
+
+/// Base time and increment.
+pub type CtrlOption = (Duration, Duration);
+
+/// Base time and increment of each player, Player 1 first, to give one of them time odds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerCtrls(pub [CtrlOption; 2]);
+
+impl PlayerCtrls {
+    /// The time control of both players, or of each, e.g. `5 +0 / 1 +0`.
+    pub fn label(self) -> String {
+        match self.0 {
+            [p1, p2] if p1 == p2 => label(p1),
+            [p1, p2] => format!("{} / {}", label(p1), label(p2)),
+        }
+    }
+}
+
+impl From<CtrlOption> for PlayerCtrls {
+    fn from(ctrl: CtrlOption) -> Self {
+        Self([ctrl; 2])
+    }
+}
+
+impl From<TimeCtrl> for PlayerCtrls {
+    fn from(ctrl: TimeCtrl) -> Self {
+        ctrl.to_duration().into()
+    }
+}
+
+/// A time control saved by the player under a name, offered by the picker after the built-in
+/// ones. The times are Player 1's first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub minutes: [u64; 2],
+    /// Increments in seconds.
+    pub increment: [u64; 2],
+}
+
+impl Preset {
+    pub fn new(name: String, ctrls: PlayerCtrls) -> Self {
+        let [p1, p2] = ctrls.0;
+        Self {
+            name,
+            minutes: [p1.0.as_secs() / 60, p2.0.as_secs() / 60],
+            increment: [p1.1.as_secs(), p2.1.as_secs()],
+        }
+    }
+
+    pub fn ctrls(&self) -> PlayerCtrls {
+        let ctrl = |i: usize| {
+            (
+                Duration::from_secs(self.minutes[i] * 60),
+                Duration::from_secs(self.increment[i]),
+            )
+        };
+        PlayerCtrls([ctrl(0), ctrl(1)])
+    }
+}
+
+/// A time control as shown on the clocks, e.g. `3 +2`, or `5s +0` for bases under a minute.
+pub fn label((base, inc): CtrlOption) -> String {
+    let secs = base.as_secs();
+    if secs.is_multiple_of(60) {
+        format!("{} +{}", secs / 60, inc.as_secs())
+    } else {
+        format!("{secs}s +{}", inc.as_secs())
+    }
+}
+
+/// A time control played in stages, e.g. `90/40 30 +30` for 90 minutes for 40 moves then 30
+/// minutes with an increment of 30 seconds.
+pub fn stages_label(ctrl: CtrlOption, stages: &[Stage]) -> String {
+    let (Some(first), Some(last)) = (stages.first(), stages.last()) else {
+        return label(ctrl);
+    };
+    let mut parts = vec![format!("{}/{}", ctrl.0.as_secs() / 60, first.moves)];
+    parts.extend(
+        stages
+            .windows(2)
+            .map(|pair| format!("{}/{}", pair[0].minutes, pair[1].moves)),
+    );
+    parts.push(format!("{} +{}", last.minutes, last.increment));
+    parts.join(" ")
+}
+
+/// A time control in PGN `TimeControl` tag notation, e.g. `180+2`.
+pub fn pgn((base, inc): CtrlOption) -> String {
+    format!("{}+{}", base.as_secs(), inc.as_secs())
+}
+
+/// A time control played in stages in PGN `TimeControl` tag notation, e.g. `40/5400+30:1800+30`
+/// for 90 minutes for 40 moves then 30 minutes, with an increment of 30 seconds.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use chessclock_core::{clock::Stage, tabs};
+/// let ctrl = (Duration::from_secs(90 * 60), Duration::from_secs(30));
+/// let stages = [Stage { moves: 40, minutes: 30, increment: 30 }];
+/// assert_eq!(tabs::pgn_stages(ctrl, &stages), "40/5400+30:1800+30");
+/// ```
+pub fn pgn_stages(ctrl: CtrlOption, stages: &[Stage]) -> String {
+    let period = |moves: Option<u32>, secs: u64, inc: u64| {
+        let moves = moves.map(|moves| format!("{moves}/")).unwrap_or_default();
+        let inc = if inc > 0 {
+            format!("+{inc}")
+        } else {
+            String::new()
+        };
+        format!("{moves}{secs}{inc}")
+    };
+    let Some(last) = stages.last() else {
+        return pgn(ctrl);
+    };
+    let mut periods = vec![period(
+        stages.first().map(|first| first.moves),
+        ctrl.0.as_secs(),
+        ctrl.1.as_secs(),
+    )];
+    periods.extend(
+        stages
+            .windows(2)
+            .map(|pair| period(Some(pair[1].moves), pair[0].minutes * 60, pair[0].increment)),
+    );
+    periods.push(format!("{}+{}", last.minutes * 60, last.increment));
+    periods.join(":")
+}
+
+/// Reads a time control written in PGN notation, see [`pgn`].
+pub fn parse_pgn(s: &str) -> Option<CtrlOption> {
+    let (base, inc) = s.split_once('+')?;
+    Some((
+        Duration::from_secs(base.parse().ok()?),
+        Duration::from_secs(inc.parse().ok()?),
+    ))
+}
+
+#[derive(Debug, PartialEq, Default, Clone, Copy, Display, FromRepr, EnumIter)]
+pub enum TimeCtrl {
+    #[default]
+    #[strum(to_string = "5s +0")]
+    Tab0,
+    #[strum(to_string = "1 +0")]
+    Tab1,
+    #[strum(to_string = "3 +2")]
+    Tab2,
+    #[strum(to_string = "5 +3")]
+    Tab3,
+    #[strum(to_string = "10 +0")]
+    Tab4,
+    /// White has 5 minutes, Black 4 and wins on a draw.
+    #[strum(to_string = "Armageddon")]
+    Armageddon,
+}
+
+impl TimeCtrl {
+    pub fn previous(&mut self) {
+        let current_index: usize = *self as usize;
+        let previous_index = current_index.saturating_sub(1);
+        *self = Self::from_repr(previous_index).unwrap_or(*self);
+    }
+
+    pub fn next(&mut self) {
+        let current_index = *self as usize;
+        let next_index = current_index.saturating_add(1);
+        *self = Self::from_repr(next_index).unwrap_or(*self);
+    }
+
+    pub fn to_duration(self) -> CtrlOption {
+        match self {
+            TimeCtrl::Tab0 => (Duration::from_secs(5), Duration::from_secs(0)),
+            TimeCtrl::Tab1 => (Duration::from_secs(60), Duration::from_secs(0)),
+            TimeCtrl::Tab2 => (Duration::from_secs(180), Duration::from_secs(2)),
+            TimeCtrl::Tab3 => (Duration::from_secs(300), Duration::from_secs(3)),
+            TimeCtrl::Tab4 => (Duration::from_secs(600), Duration::from_secs(0)),
+            // Black's time is cut by the clock, see `Clock::set_armageddon`
+            TimeCtrl::Armageddon => (Duration::from_secs(300), Duration::from_secs(0)),
+        }
+    }
+
+    /// Parses a time control label such as `3+2`, ignoring whitespace.
+    pub fn parse(s: &str) -> Result<TimeCtrl, String> {
+        let wanted: String = s.split_whitespace().collect();
+        TimeCtrl::iter()
+            .find(|ctrl| ctrl.to_string().split_whitespace().collect::<String>() == wanted)
+            .ok_or_else(|| {
+                let known: Vec<_> = TimeCtrl::iter().map(|ctrl| ctrl.to_string()).collect();
+                format!(
+                    "unknown time control '{s}', expected one of: {}",
+                    known.join(", ")
+                )
+            })
+    }
+
+    /// Finds the time control written in PGN notation, see [`TimeCtrl::pgn`].
+    pub fn from_pgn(s: &str) -> Option<TimeCtrl> {
+        // Armageddon is told apart by the draw odds, not by its time control
+        TimeCtrl::iter()
+            .filter(|&ctrl| ctrl != TimeCtrl::Armageddon)
+            .find(|ctrl| ctrl.pgn() == s)
+    }
+
+    /// Time control in PGN `TimeControl` tag notation, e.g. `180+2`.
+    pub fn pgn(self) -> String {
+        pgn(self.to_duration())
+    }
+}
+
+impl From<TimeCtrl> for CtrlOption {
+    fn from(ctrl: TimeCtrl) -> Self {
+        ctrl.to_duration()
+    }
+}
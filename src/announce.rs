@@ -0,0 +1,142 @@
+use std::{
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    clock::{Clock, ClockEvent, Player, Snapshot, Time},
+    config::AccessibilityConfig,
+    i18n::{self, tr, trf},
+};
+
+/// Announcements of the clocks written under them and spoken by a text-to-speech command,
+/// for players who can't see the clocks.
+#[derive(Debug, Default)]
+pub struct Announcer {
+    /// When the time of the player to move was last announced, while the clock runs.
+    announced_at: Option<Instant>,
+    /// The latest announcement.
+    pub last: Option<String>,
+}
+
+impl Announcer {
+    /// Shows `text` and speaks it with the TTS command, if there is one.
+    pub fn announce(&mut self, config: &AccessibilityConfig, text: String) -> anyhow::Result<()> {
+        if let Some(tts) = &config.tts {
+            let mut args = tts.split_whitespace();
+            let program = args.next().unwrap_or_default();
+            let mut child = Command::new(program)
+                .args(args)
+                .arg(&text)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| anyhow::anyhow!("could not run {program}: {e}"))?;
+            thread::spawn(move || child.wait());
+        }
+        self.last = Some(text);
+        Ok(())
+    }
+
+    /// Announces the time of the player to move every `interval` seconds while the clock runs.
+    pub fn tick(&mut self, config: &AccessibilityConfig, clock: &Clock) -> anyhow::Result<()> {
+        let snapshot = clock.snapshot();
+        let enabled = config.screen_reader || config.tts.is_some();
+        let (Some(player), true, true) = (snapshot.turn, snapshot.running, enabled) else {
+            self.announced_at = None;
+            return Ok(());
+        };
+        if config.interval == 0 {
+            return Ok(());
+        }
+        let announced_at = *self.announced_at.get_or_insert_with(Instant::now);
+        if announced_at.elapsed() < Duration::from_secs(config.interval) {
+            return Ok(());
+        }
+        self.announced_at = Some(Instant::now());
+        let ms = match player {
+            Player::Player1 => snapshot.player1_ms,
+            Player::Player2 => snapshot.player2_ms,
+        };
+        let text = trf(
+            "{}, {} remaining",
+            &[&i18n::player(player), &spoken(Duration::from_millis(ms))],
+        );
+        self.announce(config, text)
+    }
+
+    /// Announces the fall of a flag or the end of the game between `prev` and `next`.
+    pub fn clock_changed(
+        &mut self,
+        config: &AccessibilityConfig,
+        prev: &Snapshot,
+        next: &Snapshot,
+    ) -> anyhow::Result<()> {
+        if !config.screen_reader && config.tts.is_none() {
+            return Ok(());
+        }
+        for event in ClockEvent::between(prev, next) {
+            match event {
+                ClockEvent::Flag { player } => {
+                    self.announce(config, trf("{} flagged", &[&i18n::player(player)]))?
+                }
+                ClockEvent::End { outcome } => self.announce(config, i18n::outcome(outcome))?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Both times and the player to move, e.g. "White 03:02, Black 01:47, White to move".
+pub fn summary(snapshot: &Snapshot) -> String {
+    let (white, black) = match snapshot.white {
+        Player::Player1 => (snapshot.player1_ms, snapshot.player2_ms),
+        Player::Player2 => (snapshot.player2_ms, snapshot.player1_ms),
+    };
+    let time = |ms| Time(Duration::from_millis(ms)).in_seconds();
+    let times = trf("White {}, Black {}", &[&time(white), &time(black)]);
+    let color = |player| {
+        if player == snapshot.white {
+            tr("White")
+        } else {
+            tr("Black")
+        }
+    };
+    let state = if let Some(outcome) = snapshot.outcome {
+        i18n::outcome(outcome)
+    } else if let Some(flagged) = snapshot.flagged {
+        trf("{} flagged", &[&color(flagged)])
+    } else if snapshot.paused {
+        tr("paused").to_string()
+    } else if let Some(turn) = snapshot.turn {
+        trf("{} to move", &[&color(turn)])
+    } else {
+        return times;
+    };
+    format!("{times}, {state}")
+}
+
+/// `time` as it is said, e.g. "2 minutes 5 seconds".
+fn spoken(time: Duration) -> String {
+    let secs = time.as_secs();
+    let parts = [
+        (secs / 3_600, "1 hour", "{} hours"),
+        (secs % 3_600 / 60, "1 minute", "{} minutes"),
+        (secs % 60, "1 second", "{} seconds"),
+    ];
+    let said: Vec<_> = parts
+        .into_iter()
+        .filter(|&(n, _, _)| n > 0)
+        .map(|(n, one, many)| match n {
+            1 => tr(one).to_string(),
+            n => trf(many, &[&n]),
+        })
+        .collect();
+    if said.is_empty() {
+        return trf("{} seconds", &[&0]);
+    }
+    said.join(" ")
+}
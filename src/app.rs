@@ -1,18 +1,159 @@
-use crate::clock::Clock;
+#[cfg(feature = "callbacks")]
+use crate::callback;
+use crate::announce::{self, Announcer};
+use crate::arbiter::Dashboard;
+#[cfg(feature = "board")]
+use crate::board;
+use crate::clock::{Clock, ClockEvent, ClockWidget, Outcome, Overtime, Player, Snapshot, Time};
+use crate::command::Command;
+use crate::config::Config;
+use crate::control;
+use crate::custom::CustomInput;
+use crate::date;
+use crate::discovery::{self, Daemon, Host};
+use crate::eco::{self, EcoMode};
+use crate::engine::Engine;
+use crate::feed::EventFeed;
+use crate::focus::FocusTimer;
+use crate::font::{Font, FontName};
+use crate::games::GamesView;
+use crate::handicap;
+use crate::hints::HintBar;
 use crate::event::{AppEvent, Event, EventHandler};
-use crate::tabs::TimeCtrl;
+use crate::export::{self, Format};
+use crate::history::{self, GameEvent, GameMeta, GameRecord, LogEntry, Retention};
+use crate::i18n::{self, tr, trf};
+use crate::keymap::{Action, KeyMap};
+#[cfg(feature = "lichess")]
+use crate::lichess;
+use crate::moves::{self, MoveTimesPanel};
+use crate::names::NamesInput;
+use crate::net::{self, NetEvent, Role};
+use crate::remote::{Publisher, RemoteArgs, Servers};
+use crate::resume;
+#[cfg(feature = "plugins")]
+use crate::plugin::{self, Plugin};
+use crate::presets;
+use crate::round::RoundTimer;
+use crate::score::Scoresheet;
+#[cfg(feature = "lua")]
+use crate::script::Script;
+use crate::series::Series;
+use crate::sound::{Sound, SoundKind};
+use crate::tabs::{self, CtrlOption, Preset, TimeCtrl};
+use crate::term::{self, Terminal};
+use crate::wallclock::{self, Discrepancy, WallClock};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
+
 use ratatui::{
-    DefaultTerminal, Frame,
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
-    layout::{Constraint, Flex, Layout, Rect},
-    widgets::Widget,
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent},
+    layout::{Alignment, Constraint, Flex, Layout, Position, Rect},
+    style::{Color, Modifier, Stylize},
+    text::Line,
+    widgets::{Block, Clear, List, ListState, Padding, Paragraph, StatefulWidget, Widget},
 };
 
+/// How often the game being played is saved, to resume it after a crash.
+const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The actions of the keys on each screen, looked up in this order.
+const CLOCKS_ACTIONS: &[Action] = &[
+    Action::Hit,
+    Action::Pause,
+    Action::FirstToMove,
+    Action::Previous,
+    Action::Next,
+    Action::SwapSides,
+    Action::Draw,
+    Action::MoreTime,
+    Action::Reset,
+    Action::ResignPlayer1,
+    Action::ResignPlayer2,
+    Action::Handicap,
+    Action::Quit,
+    Action::Events,
+    Action::MoveTimes,
+    Action::Times,
+    Action::Score,
+    Action::EngineMove,
+];
+const PICKER_ACTIONS: &[Action] = &[
+    Action::Quit,
+    Action::Start,
+    Action::Delete,
+    Action::Increment,
+    Action::History,
+    Action::Keys,
+    Action::Next,
+    Action::Previous,
+];
+const GAME_OVER_ACTIONS: &[Action] = &[
+    Action::NewGame,
+    Action::Rematch,
+    Action::ExportCsv,
+    Action::ExportJson,
+    Action::Quit,
+];
+const TIMER_ACTIONS: &[Action] = &[
+    Action::Hit,
+    Action::Pause,
+    Action::Skip,
+    Action::Quit,
+    Action::Confirm,
+    Action::Back,
+];
+const DISCOVER_ACTIONS: &[Action] = &[Action::Up, Action::Down, Action::Start, Action::Quit];
+/// The answers of the questions popping up over a screen.
+const QUESTION_ACTIONS: &[Action] = &[Action::Yes, Action::No];
+/// The keys leaving a text field, the others being typed into it.
+const FIELD_ACTIONS: &[Action] = &[Action::Confirm, Action::Back];
+
 #[derive(Debug, PartialEq)]
 pub enum Screen {
     Clocks,
     SelectTimeCtrl,
+    CustomTimeCtrl,
+    /// The players' names, asked for before the game.
+    PlayerNames,
     TimeOut,
+    /// The result of a game ended by a resignation or an agreed draw.
+    GameOver,
+    /// Pick a game found on the local network.
+    Discover,
+    /// The clocks of several boards.
+    Arbiter,
+    /// The finished games, opened from the picker.
+    History,
+    /// The countdown of a tournament round.
+    Round,
+    /// Work and break periods for study sessions.
+    Focus,
+    /// The key bindings, opened from the picker.
+    Keys,
+}
+
+/// An entry of the time control picker, in the order of [`App::picker_entries`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PickerEntry {
+    BuiltIn(TimeCtrl),
+    /// The time control typed on the custom screen.
+    Custom,
+    Byoyomi,
+    Canadian,
+    Classical,
+    Hourglass,
+    /// The round timer, without players.
+    Round,
+    /// The focus timer, without players.
+    Focus,
+    /// A saved preset, by its index.
+    Preset(usize),
+    /// A time control of a plugin, by its index.
+    #[cfg(feature = "plugins")]
+    Plugin(usize),
 }
 
 #[derive(Debug)]
@@ -24,116 +165,1816 @@ pub struct App {
     // Multi-screen logic goes here
     screen: Screen,
     clock: Clock,
-    time_ctrl_selecter: TimeCtrl,
+    picked: PickerEntry,
+    presets: Vec<Preset>,
+    round: RoundTimer,
+    focus: FocusTimer,
+    games: GamesView,
+    keys: KeyMap,
+    meta: GameMeta,
+    config: Config,
+    // The font of the config, read once
+    font: Font,
+
+    // Clock state published to remote frontends
+    publisher: Publisher,
+    servers: Servers,
+
+    // Network play
+    role: Role,
+    net_status: Option<String>,
+    mdns: Option<Daemon>,
+    hosts: Vec<Host>,
+    host_selected: ListState,
+    dashboard: Dashboard,
+
+    // The keys of the screen, at the bottom
+    hints: HintBar,
+    // Every key of the screen listed over it, until any key is pressed
+    help: bool,
+
+    // Frames are only drawn every eco::FRAME_INTERVAL in eco mode, or at once after input
+    eco: EcoMode,
+    sound: Sound,
+    // Players whose clock already sounded the low time alert this game
+    low_time_alerted: [bool; 2],
+    drawn_at: Instant,
+    redraw: bool,
+
+    // Times announced for players who can't see the clocks
+    announcer: Announcer,
+
+    // Side panel with the latest events
+    feed: EventFeed,
+    show_feed: bool,
+    /// Whether the time of the latest moves is shown beside the clocks.
+    show_move_times: bool,
+
+    // The moves with their clocks, and their SAN typed by a scorer
+    score: Scoresheet,
+
+    // The standing draw offer, and the events of the game recorded with it
+    draw_offer: Option<Player>,
+    log: Vec<LogEntry>,
+
+    // The time control typed on the custom screen
+    custom: CustomInput,
+    // The players' names, shown above their clocks
+    names: NamesInput,
+    // Player 1's clock is drawn on the right
+    swapped: bool,
+    // Where the clocks were last drawn, to tell which one a tap hits
+    clocks_area: Rect,
+
+    // The best-of-N match being played, if any
+    series: Option<Series>,
+
+    // The player whose flag fell, until the claim of their opponent is confirmed, and whether
+    // it was rejected, which leaves the clocks stopped without a result
+    claim: Option<Player>,
+    claim_rejected: bool,
+    // A reset of the game waiting for confirmation
+    confirm_reset: bool,
+    // The game left unfinished when the app was last closed, offered over the picker
+    resumable: Option<Snapshot>,
+    // When the state of the game was last saved for resuming it
+    saved_at: Instant,
+
+    // Engine opponent and the move being typed against it
+    engine: Option<Engine>,
+    move_input: Option<String>,
+
+    // Sleep of the computer noticed during the game, and the correction offered for it
+    wall_clock: WallClock,
+    correction: Option<(Player, Discrepancy)>,
+
+    #[cfg(feature = "lua")]
+    script: Option<Script>,
+
+    // Time controls from plugins, and the one of the current game
+    #[cfg(feature = "plugins")]
+    plugins: Vec<Plugin>,
+    #[cfg(feature = "plugins")]
+    plugin: Option<usize>,
 }
 
 impl Default for App {
     fn default() -> Self {
+        Self::new(Config::default(), GameMeta::default())
+    }
+}
+
+impl App {
+    pub fn new(config: Config, meta: GameMeta) -> Self {
+        let mut sound = Sound::new();
+        sound.muted = config.sound.muted;
+        let (presets, net_status) = match presets::load() {
+            Ok(presets) => (presets, None),
+            Err(e) => (Vec::new(), Some(format!("{e:#}"))),
+        };
+        let (font, font_error) = match config.font() {
+            Ok(font) => (font, None),
+            Err(e) => (FontName::default().font().clone(), Some(format!("{e:#}"))),
+        };
+        let custom = config.custom_time_control();
         Self {
             clock: Clock::default(),
             running: true,
-            events: EventHandler::new(),
+            events: EventHandler::new(config.tick_rate()),
             screen: Screen::SelectTimeCtrl,
-            time_ctrl_selecter: TimeCtrl::default(),
+            picked: match custom {
+                Some(_) => PickerEntry::Custom,
+                None => PickerEntry::BuiltIn(config.time_control()),
+            },
+            presets,
+            round: RoundTimer::default(),
+            focus: FocusTimer::default(),
+            games: GamesView::default(),
+            keys: KeyMap::new(&config.keys),
+            meta,
+            publisher: Publisher::new(Clock::default().snapshot()),
+            servers: Servers::default(),
+            role: Role::Local,
+            net_status: net_status.or(font_error),
+            mdns: None,
+            hosts: Vec::new(),
+            host_selected: ListState::default(),
+            dashboard: Dashboard::default(),
+            hints: HintBar::default(),
+            help: false,
+            eco: EcoMode::default(),
+            sound,
+            low_time_alerted: [false; 2],
+            drawn_at: Instant::now(),
+            redraw: true,
+            announcer: Announcer::default(),
+            feed: EventFeed::default(),
+            show_feed: false,
+            show_move_times: false,
+            score: Scoresheet::default(),
+            draw_offer: None,
+            log: Vec::new(),
+            custom: custom
+                .map(|ctrl| CustomInput::new(ctrl.into()))
+                .unwrap_or_default(),
+            names: NamesInput::default(),
+            swapped: false,
+            series: None,
+            clocks_area: Rect::default(),
+            claim: None,
+            claim_rejected: false,
+            confirm_reset: false,
+            resumable: resume::load(),
+            saved_at: Instant::now(),
+            engine: None,
+            move_input: None,
+            wall_clock: WallClock::default(),
+            correction: None,
+            config,
+            font,
+            #[cfg(feature = "lua")]
+            script: None,
+            #[cfg(feature = "plugins")]
+            plugins: Vec::new(),
+            #[cfg(feature = "plugins")]
+            plugin: None,
         }
     }
-}
 
-impl App {
-    pub fn new() -> Self {
-        Self::default()
+    /// Starts the remote control servers requested in `args`.
+    pub async fn serve(&mut self, args: &RemoteArgs) -> anyhow::Result<()> {
+        let remote = self.publisher.remote(self.events.sender());
+        self.servers = args.start(&remote).await?;
+        #[cfg(feature = "callbacks")]
+        callback::spawn(&self.config.callbacks, &self.meta, &remote);
+        Ok(())
+    }
+
+    /// Hosts a network game on `port`, playing the left clock, and announces it on the local
+    /// network as `name`.
+    pub async fn host(&mut self, port: u16, name: &str) -> anyhow::Result<()> {
+        net::host(port, self.publisher.remote(self.events.sender())).await?;
+        self.role = Role::Host;
+        // Players can still join by address when mDNS is not available
+        self.mdns = discovery::advertise(name, port).ok();
+        Ok(())
+    }
+
+    /// Shows `status` on top of the screen until another one replaces it.
+    pub fn show_status(&mut self, status: String) {
+        self.net_status = Some(status);
+    }
+
+    /// Skips the picker for a game of `ctrl`, kept as the custom time control for the next
+    /// games.
+    pub fn start_with(&mut self, ctrl: CtrlOption) {
+        self.custom = CustomInput::new(ctrl.into());
+        self.picked = PickerEntry::Custom;
+        self.start_or_ask_names();
+    }
+
+    /// Plays a match of `games` games, see [`Series`].
+    pub fn best_of(&mut self, games: u32) -> anyhow::Result<()> {
+        self.series = Some(Series::new(games, &self.config.series)?);
+        Ok(())
+    }
+
+    /// Looks for games on the local network and lets the player pick one to join.
+    pub fn discover(&mut self) -> anyhow::Result<()> {
+        self.mdns = Some(discovery::browse(self.events.sender())?);
+        self.net_status = Some(tr("looking for games on the local network").to_string());
+        self.screen = Screen::Discover;
+        Ok(())
+    }
+
+    /// Joins the network game hosted at `addr`, playing the right clock.
+    pub async fn join(&mut self, addr: &str) -> anyhow::Result<()> {
+        self.role = Role::Client(net::join(addr, false, self.events.sender()).await?);
+        self.mdns = None;
+        self.screen = Screen::Clocks;
+        Ok(())
+    }
+
+    /// Follows the game hosted at `addr`, or the clock behind a control socket, read-only.
+    pub async fn spectate(&mut self, addr: &str) -> anyhow::Result<()> {
+        match addr {
+            path if control::is_socket(path) => {
+                control::follow(Path::new(path), self.events.sender()).await?
+            }
+            // Spectators have nothing to send to the host
+            _ => drop(net::join(addr, true, self.events.sender()).await?),
+        }
+        self.role = Role::Spectator;
+        self.screen = Screen::Clocks;
+        Ok(())
+    }
+
+    /// Shows the clocks at `addrs`, hosts or control sockets, on a dashboard.
+    pub async fn arbiter(&mut self, addrs: &[String]) -> anyhow::Result<()> {
+        self.dashboard = Dashboard::follow(addrs, &self.events.sender()).await;
+        self.role = Role::Spectator;
+        self.screen = Screen::Arbiter;
+        Ok(())
+    }
+
+    /// Lets the UCI engine `command` play `player`. The opponent types their moves.
+    pub async fn engine(&mut self, command: &str, player: Player) -> anyhow::Result<()> {
+        let remote = self.publisher.remote(self.events.sender());
+        self.engine = Some(Engine::start(command, player, remote).await?);
+        Ok(())
+    }
+
+    /// Mirrors the clock of a Lichess game.
+    #[cfg(feature = "lichess")]
+    pub fn mirror_lichess(&mut self, source: lichess::Source) {
+        lichess::follow(source, self.events.sender());
+        self.role = Role::Spectator;
+        self.screen = Screen::Clocks;
+    }
+
+    /// Runs the hooks of the Lua script at `path` on clock events.
+    #[cfg(feature = "lua")]
+    pub fn script(&mut self, path: &Path) -> anyhow::Result<()> {
+        self.script = Some(Script::load(path)?);
+        Ok(())
+    }
+
+    /// Adds the time controls of the plugins in the plugins directory to the picker.
+    #[cfg(feature = "plugins")]
+    pub fn load_plugins(&mut self) {
+        for plugin in plugin::discover() {
+            match plugin {
+                Ok(plugin) => self.plugins.push(plugin),
+                Err(e) => self.net_status = Some(format!("{e:#}")),
+            }
+        }
     }
 
     /// Run the application's main loop.
-    pub async fn run(mut self, mut terminal: DefaultTerminal) -> anyhow::Result<()> {
+    pub async fn run(mut self, mut terminal: Terminal) -> anyhow::Result<()> {
         while self.running {
-            terminal.draw(|frame| self.ui(frame))?;
-            match self.events.next().await? {
-                Event::Tick => self.tick(),
+            if self.redraw || !self.eco.is_enabled() || self.drawn_at.elapsed() >= eco::FRAME_INTERVAL
+            {
+                terminal.draw(|frame| self.ui(frame))?;
+                self.drawn_at = Instant::now();
+                self.redraw = false;
+            }
+            let prev = self.clock.snapshot();
+            let event = self.events.next().await?;
+            self.redraw = !matches!(event, Event::Tick | Event::TimerTick);
+            match event {
+                Event::Tick => {
+                    term::poll_keys(&mut terminal, &self.events.sender())?;
+                    self.tick()
+                }
                 Event::TimerTick => {
-                    if self.clock.is_time_out() && self.screen == Screen::Clocks {
+                    let claimed = self.claim.is_some() || self.claim_rejected;
+                    if self.clock.is_over() && self.screen == Screen::Clocks && !claimed {
                         self.events.send(AppEvent::Timeout);
                     }
                     self.clock.tick_timer();
-                }
-                Event::Crossterm(event) => match event {
-                    ratatui::crossterm::event::Event::Key(key_event) => {
-                        self.handle_key_events(key_event)?
+                    self.alert_low_time(&prev);
+                    if self.saved_at.elapsed() >= SAVE_INTERVAL {
+                        self.save_game();
                     }
-                    _ => {}
-                },
+                    self.dashboard.tick_timer();
+                    self.round.tick_timer();
+                    self.focus.tick_timer();
+                    #[cfg(feature = "lua")]
+                    if let Some(script) = &self.script
+                        && let Err(e) = script.tick(&mut self.clock)
+                    {
+                        self.net_status = Some(format!("{e:#}"));
+                    }
+                }
+                Event::Key(key_event) => self.handle_key_events(key_event)?,
+                Event::Mouse(mouse_event) => self.handle_mouse_events(mouse_event),
+                // Redrawn right away, even in eco mode
+                Event::Resize => {}
                 Event::App(app_event) => match app_event {
-                    AppEvent::Timeout => self.screen = Screen::TimeOut,
+                    AppEvent::Timeout => self.time_out(),
                     AppEvent::HitClock => self.hit_clock(),
+                    AppEvent::Join(addr) => {
+                        if let Err(e) = self.join(&addr).await {
+                            self.net_status = Some(format!("{e:#}"));
+                        }
+                    }
                     AppEvent::Quit => self.quit(),
                 },
+                Event::Command(command) => self.handle_command(command),
+                Event::Net(NetEvent::Status(status)) => self.net_status = Some(status),
+                Event::Net(NetEvent::Sync(snapshot)) => {
+                    self.clock = Clock::from_snapshot(&snapshot);
+                    if snapshot.flagged.is_none() {
+                        self.screen = Screen::Clocks;
+                    }
+                }
+                Event::Net(NetEvent::Found(host)) => {
+                    self.hosts.retain(|h| h.fullname != host.fullname);
+                    self.hosts.push(host);
+                    if self.host_selected.selected().is_none() {
+                        self.host_selected.select_first();
+                    }
+                }
+                Event::Net(NetEvent::Lost(fullname)) => {
+                    self.hosts.retain(|h| h.fullname != fullname);
+                }
+                #[cfg(feature = "board")]
+                Event::Net(NetEvent::Moved(uci)) => {
+                    if let Err(e) = self.score.played(&uci) {
+                        self.net_status = Some(e);
+                    }
+                }
+                #[cfg(not(feature = "board"))]
+                Event::Net(NetEvent::Moved(_)) => {}
+                Event::Board(index, event) => self.dashboard.update(index, event),
+            }
+            self.lapse_draw_offer(&prev);
+            #[cfg(feature = "lua")]
+            if let Some(script) = &self.script
+                && let Err(e) = script.clock_changed(&prev, &mut self.clock)
+            {
+                self.net_status = Some(format!("{e:#}"));
+            }
+            #[cfg(feature = "plugins")]
+            if let Some(plugin) = self.plugin.and_then(|i| self.plugins.get(i)) {
+                plugin.clock_changed(&prev, &mut self.clock);
+            }
+            #[cfg(feature = "board")]
+            self.end_on_board();
+            let snapshot = self.clock.snapshot();
+            self.feed.clock_changed(&prev, &snapshot);
+            if let Err(e) = self
+                .announcer
+                .clock_changed(&self.config.accessibility, &prev, &snapshot)
+            {
+                self.net_status = Some(format!("{e:#}"));
             }
+            self.score.clock_changed(&prev, &snapshot);
+            self.publisher.publish(Snapshot {
+                names: self.names.names().map(|name| name.map(str::to_string)),
+                ..snapshot
+            });
         }
         Ok(())
     }
 
+    pub fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Hit(_) | Command::Move(_) if self.screen != Screen::Clocks => {}
+            Command::Hit(None) => self.hit_clock(),
+            Command::Hit(Some(player)) => self.clock.hit_by(player),
+            Command::Move(uci_move) => match &self.engine {
+                Some(engine) => {
+                    let human = engine.player().opponent();
+                    // Moving first starts the clock
+                    let first =
+                        self.clock.curr_player().is_none() && self.clock.snapshot().white == human;
+                    if !first && self.clock.curr_player() != Some(human) {
+                        return;
+                    }
+                    // An illegal move neither presses the clock nor reaches the engine
+                    #[cfg(feature = "board")]
+                    if let Err(e) = self.score.played(&uci_move) {
+                        self.net_status = Some(e);
+                        return;
+                    }
+                    if first {
+                        self.clock.hit();
+                    }
+                    self.clock.hit_by(human);
+                    engine.opponent_moved(uci_move);
+                }
+                None => self.hit_clock(),
+            },
+            Command::Pause => self
+                .clock
+                .pause(self.clock.curr_player().unwrap_or_default()),
+            Command::First(player) => self.clock.set_first_to_move(player),
+            Command::Adjust(player, delta_ms) => self.adjust(player, delta_ms),
+            Command::Set(ctrl) => {
+                #[cfg(feature = "plugins")]
+                {
+                    self.plugin = None;
+                }
+                self.picked = PickerEntry::BuiltIn(ctrl);
+                self.clock.set_preset(ctrl);
+                self.cap_time();
+                self.new_game();
+                self.screen = Screen::Clocks;
+            }
+            Command::SetCustom(ctrl) => {
+                #[cfg(feature = "plugins")]
+                {
+                    self.plugin = None;
+                }
+                self.custom = CustomInput::new(ctrl.into());
+                self.picked = PickerEntry::Custom;
+                self.clock.set(ctrl);
+                self.cap_time();
+                self.new_game();
+                self.screen = Screen::Clocks;
+            }
+            Command::Quit => self.quit(),
+        }
+    }
+
     pub fn handle_key_events(&mut self, key_event: KeyEvent) -> anyhow::Result<()> {
         match key_event.code {
+            // Not typed into the screen as a `c`
             KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
-                self.events.send(AppEvent::Quit)
+                self.events.send(AppEvent::Quit);
+                return Ok(());
+            }
+            _ => {}
+        }
+        if self.help {
+            self.help = false;
+            return Ok(());
+        }
+        // Typed into the field otherwise
+        let global = if self.typing() {
+            &[Action::Hints][..]
+        } else {
+            &[Action::Hints, Action::Help, Action::Eco, Action::Mute]
+        };
+        match self.keys.find(global, &key_event) {
+            Some(Action::Hints) => self.hints.show(),
+            Some(Action::Help) => {
+                self.help = true;
+                return Ok(());
             }
+            Some(Action::Eco) => self.eco.toggle(),
+            Some(Action::Mute) => self.sound.toggle_mute(),
             _ => {}
         }
 
+        // The game goes on while the correction waits for an answer
+        if let Some((player, discrepancy)) = self.correction {
+            match self.keys.find(QUESTION_ACTIONS, &key_event) {
+                Some(Action::Yes) => {
+                    self.adjust(player, -discrepancy.0);
+                    self.correction = None;
+                    return Ok(());
+                }
+                Some(Action::No) => {
+                    self.correction = None;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        if self.claim.is_some() {
+            let answers = [Action::Yes, Action::Draw, Action::No];
+            match self.keys.find(&answers, &key_event) {
+                Some(Action::Yes) => {}
+                Some(Action::Draw) => self.clock.draw_flag(),
+                Some(Action::No) => {
+                    self.claim = None;
+                    self.claim_rejected = true;
+                    return Ok(());
+                }
+                _ => return Ok(()),
+            }
+            self.claim = None;
+            self.record_game();
+            self.screen = Screen::TimeOut;
+            return Ok(());
+        }
+
+        if self.resumable.is_some() && self.screen == Screen::SelectTimeCtrl {
+            match self.keys.find(QUESTION_ACTIONS, &key_event) {
+                Some(Action::Yes) => self.resume(),
+                Some(Action::No) => {
+                    self.resumable = None;
+                    resume::clear();
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if self.confirm_reset {
+            match self.keys.find(QUESTION_ACTIONS, &key_event) {
+                Some(Action::Yes) => self.start_game(),
+                Some(Action::No) => {}
+                _ => return Ok(()),
+            }
+            self.confirm_reset = false;
+            return Ok(());
+        }
+
+        if self.screen == Screen::Clocks && self.score.handle_key(key_event.code) {
+            return Ok(());
+        }
+
+        if self.screen == Screen::Clocks && self.type_move(&key_event) {
+            return Ok(());
+        }
+
+        let started = self.clock.snapshot().turn.is_some();
+        let action = |actions: &[Action]| self.keys.find(actions, &key_event);
         match self.screen {
-            Screen::Clocks => match key_event.code {
-                KeyCode::Char(' ') => {
+            Screen::Clocks => match (action(CLOCKS_ACTIONS), &self.role) {
+                (Some(Action::Hit), Role::Local) => {
                     self.events.send(AppEvent::HitClock);
                 }
-                KeyCode::Char('p') => self
+                (Some(Action::Hit), Role::Host) => self.hit_host_clock(),
+                (Some(Action::Hit), Role::Client(host)) => {
+                    let _ = host.send("hit".to_string());
+                }
+                (Some(Action::Pause), Role::Client(host)) => {
+                    let _ = host.send("pause".to_string());
+                }
+                (Some(Action::Pause), Role::Local | Role::Host) => self
                     .clock
                     .pause(self.clock.curr_player().unwrap_or_default()),
-                KeyCode::Char('r' | 'R') => {
+                (Some(Action::FirstToMove), Role::Local | Role::Host) => {
                     self.clock.flip_first_to_move();
                 }
+                // The player on the side of the arrow moves first
+                (Some(side @ (Action::Previous | Action::Next)), Role::Local | Role::Host)
+                    if !started =>
+                {
+                    let left = if self.swapped {
+                        Player::Player2
+                    } else {
+                        Player::Player1
+                    };
+                    let player = if side == Action::Previous {
+                        left
+                    } else {
+                        left.opponent()
+                    };
+                    self.clock.set_first_to_move(player);
+                }
+                (Some(Action::SwapSides), Role::Local | Role::Host) if !started => {
+                    self.clock.swap_sides();
+                }
+                // Once the game started the clocks only change places on the screen
+                (Some(Action::SwapSides), Role::Local | Role::Host) => {
+                    self.swapped = !self.swapped;
+                }
+                (Some(Action::Draw), Role::Local) => {
+                    if let Some(player) = self.clock.snapshot().turn {
+                        self.offer_draw(player);
+                    }
+                }
+                (Some(Action::Draw), Role::Host) => self.offer_draw(net::HOST_PLAYER),
+                (Some(Action::MoreTime), Role::Local) => {
+                    if let Some(player) = self.clock.snapshot().turn {
+                        self.give_time(player);
+                    }
+                }
+                (Some(Action::MoreTime), Role::Host) => self.give_time(net::HOST_PLAYER),
+                (Some(Action::Reset), Role::Local | Role::Host) if started => {
+                    self.confirm_reset = true;
+                }
+                (Some(Action::ResignPlayer1), Role::Local | Role::Host) if started => {
+                    self.resign(Player::Player1);
+                }
+                (Some(Action::ResignPlayer2), Role::Local | Role::Host) if started => {
+                    self.resign(Player::Player2);
+                }
+                // The ratings are typed with the names
+                (Some(Action::Handicap), Role::Local | Role::Host) if !started => {
+                    self.screen = Screen::PlayerNames;
+                }
+                (Some(Action::Quit), Role::Spectator) => self.events.send(AppEvent::Quit),
+                (Some(Action::Events), _) => self.show_feed = !self.show_feed,
+                (Some(Action::MoveTimes), Role::Local | Role::Host) => {
+                    self.show_move_times = !self.show_move_times;
+                }
+                (Some(Action::Times), _) => self.announce_times(),
+                (Some(Action::Score), Role::Local | Role::Host) => self.score.open(),
+                (Some(Action::EngineMove), Role::Local | Role::Host) if self.engine.is_some() => {
+                    self.move_input = Some(String::new());
+                }
                 _ => {}
             },
-            Screen::SelectTimeCtrl => match key_event.code {
-                KeyCode::Char('q') => self.events.send(AppEvent::Quit),
-                KeyCode::Char(' ') | KeyCode::Enter => {
-                    self.clock.set(self.time_ctrl_selecter);
-                    self.screen = Screen::Clocks;
+            Screen::SelectTimeCtrl => match action(PICKER_ACTIONS) {
+                Some(Action::Quit) => self.events.send(AppEvent::Quit),
+                Some(Action::Start) if self.picked == PickerEntry::Custom => {
+                    self.screen = Screen::CustomTimeCtrl;
+                }
+                Some(Action::Start) => self.start_or_ask_names(),
+                Some(Action::Delete) => {
+                    if let PickerEntry::Preset(i) = self.picked {
+                        self.select_previous_time_ctrl();
+                        self.presets.remove(i);
+                        self.save_presets();
+                    }
+                }
+                Some(Action::Increment) => {
+                    self.clock
+                        .set_increment_kind(self.clock.increment_kind().next());
+                }
+                Some(Action::History) => match history::load() {
+                    Ok(games) => {
+                        self.games = GamesView::new(games);
+                        self.screen = Screen::History;
+                    }
+                    Err(e) => self.net_status = Some(format!("{e:#}")),
+                },
+                Some(Action::Keys) => self.screen = Screen::Keys,
+                Some(Action::Next) => self.select_next_time_ctrl(),
+                Some(Action::Previous) => self.select_previous_time_ctrl(),
+                _ => {}
+            },
+            Screen::CustomTimeCtrl => match action(FIELD_ACTIONS) {
+                Some(Action::Confirm) if self.custom.ctrl().is_ok() => {
+                    if let Some(preset) = self.custom.preset() {
+                        self.save_preset(preset);
+                    }
+                    self.start_or_ask_names();
                 }
-                _ => self.time_ctrl_selecter.handle_key_events(key_event),
+                Some(Action::Confirm) => {}
+                Some(_) => self.screen = Screen::SelectTimeCtrl,
+                None => self.custom.handle_key(key_event.code),
+            },
+            Screen::PlayerNames => match action(FIELD_ACTIONS) {
+                Some(Action::Confirm) => self.start_game(),
+                Some(_) => self.screen = Screen::SelectTimeCtrl,
+                None => self.names.handle_key(key_event.code),
+            },
+            Screen::TimeOut | Screen::GameOver => match action(GAME_OVER_ACTIONS) {
+                // The host starts the next game of a client
+                Some(Action::NewGame)
+                    if !matches!(self.role, Role::Client(_) | Role::Spectator) =>
+                {
+                    match &self.series {
+                        Some(series) if series.game().is_some() => self.start_game(),
+                        _ => {
+                            self.series = None;
+                            self.screen = Screen::SelectTimeCtrl;
+                        }
+                    }
+                }
+                // The same time control again, or the next game of the match
+                Some(Action::Rematch)
+                    if !matches!(self.role, Role::Client(_) | Role::Spectator) =>
+                {
+                    if self.series.as_ref().is_some_and(|s| s.game().is_none()) {
+                        self.series = None;
+                    }
+                    self.start_game();
+                }
+                Some(Action::ExportCsv) => self.export_move_times(Format::Csv),
+                Some(Action::ExportJson) => self.export_move_times(Format::Json),
+                Some(Action::Quit) => self.events.send(AppEvent::Quit),
+                _ => {}
             },
-            Screen::TimeOut => match key_event.code {
-                KeyCode::Char('R' | 'r') | KeyCode::Enter => {
+            Screen::Round => match action(TIMER_ACTIONS) {
+                Some(Action::Hit | Action::Pause) => self.round.toggle(),
+                // A round under way is only left by quitting
+                Some(Action::Confirm | Action::Back) if !self.round.is_started() => {
                     self.screen = Screen::SelectTimeCtrl;
                 }
-                KeyCode::Char('q') => self.events.send(AppEvent::Quit),
+                Some(Action::Quit) if !self.round.is_started() => {
+                    self.events.send(AppEvent::Quit);
+                }
+                _ => {}
+            },
+            Screen::Focus => match action(TIMER_ACTIONS) {
+                Some(Action::Hit | Action::Pause) => self.focus.toggle(),
+                Some(Action::Skip) => self.focus.skip(),
+                Some(Action::Confirm | Action::Back) if !self.focus.is_running() => {
+                    self.screen = Screen::SelectTimeCtrl;
+                }
+                Some(Action::Quit) if !self.focus.is_running() => {
+                    self.events.send(AppEvent::Quit);
+                }
                 _ => {}
             },
+            Screen::Arbiter => match action(&[Action::Quit]) {
+                Some(Action::Quit) if self.dashboard.is_grid() => self.events.send(AppEvent::Quit),
+                _ => self.dashboard.handle_key(key_event.code),
+            },
+            // Quitting these screens goes back to the picker
+            Screen::History => match action(&[Action::Back, Action::Quit]) {
+                Some(_) => self.screen = Screen::SelectTimeCtrl,
+                None => self.games.handle_key(key_event.code),
+            },
+            Screen::Keys => {
+                if action(&[Action::Back, Action::Quit]).is_some() {
+                    self.screen = Screen::SelectTimeCtrl;
+                }
+            }
+            Screen::Discover => match action(DISCOVER_ACTIONS) {
+                Some(Action::Up) => self.host_selected.select_previous(),
+                Some(Action::Down) => self.host_selected.select_next(),
+                Some(Action::Start) => {
+                    let selected = self.host_selected.selected();
+                    if let Some(host) = selected.and_then(|i| self.hosts.get(i)) {
+                        self.events.send(AppEvent::Join(host.addr.to_string()));
+                    }
+                }
+                Some(_) => self.events.send(AppEvent::Quit),
+                None => {}
+            },
         }
         Ok(())
     }
 
+    /// A tap on a clock stops it like the button of a physical clock: the half of the screen
+    /// of a player hits their clock, and a tap on either side starts the game. Over the network,
+    /// where each side has their own screen, a tap anywhere is a hit.
+    pub fn handle_mouse_events(&mut self, mouse_event: MouseEvent) {
+        let modal = self.claim.is_some() || self.confirm_reset;
+        let at = Position::new(mouse_event.column, mouse_event.row);
+        if self.screen != Screen::Clocks || modal || !self.clocks_area.contains(at) {
+            return;
+        }
+        // Player 1 is on the left or at the bottom unless the sides are swapped
+        let first = if self.swapped {
+            Player::Player2
+        } else {
+            Player::Player1
+        };
+        let area = self.clocks_area;
+        let first_tapped = if self.config.layout.is_vertical(area) {
+            at.y >= area.y + area.height / 2
+        } else {
+            at.x < area.x + area.width / 2
+        };
+        let player = if first_tapped {
+            first
+        } else {
+            first.opponent()
+        };
+        match &self.role {
+            Role::Local => {
+                if self.clock.curr_player().is_none_or(|p| p == player) {
+                    self.hit_clock();
+                }
+            }
+            Role::Host => self.hit_host_clock(),
+            Role::Client(host) => {
+                let _ = host.send("hit".to_string());
+            }
+            Role::Spectator => {}
+        }
+    }
+
+    /// Whether the keys are typed into a text field: names, a preset's name or moves.
+    fn typing(&self) -> bool {
+        matches!(self.screen, Screen::PlayerNames | Screen::CustomTimeCtrl)
+            || (self.screen == Screen::Clocks && self.score.is_open())
+            || self.move_input.is_some()
+    }
+
+    /// Starts the game picked, after asking for the players' names when the config says so.
+    fn start_or_ask_names(&mut self) {
+        // The round and focus timers have no players
+        let timer = matches!(self.picked, PickerEntry::Round | PickerEntry::Focus);
+        if self.config.players.ask_names && !timer {
+            self.screen = Screen::PlayerNames;
+        } else {
+            self.start_game();
+        }
+    }
+
+    fn start_game(&mut self) {
+        self.new_game();
+        #[cfg(feature = "plugins")]
+        {
+            self.plugin = None;
+        }
+        match self.picked {
+            PickerEntry::BuiltIn(ctrl) => {
+                self.clock.set_preset(ctrl);
+                self.set_up_series_game();
+                self.screen = Screen::Clocks;
+            }
+            PickerEntry::Custom => {
+                if let Ok(ctrl) = self.custom.ctrl() {
+                    self.clock.set(ctrl);
+                    self.set_up_series_game();
+                    self.screen = Screen::Clocks;
+                }
+            }
+            entry @ (PickerEntry::Byoyomi | PickerEntry::Canadian) => {
+                let (minutes, overtime) = if entry == PickerEntry::Byoyomi {
+                    (self.config.byoyomi.minutes, self.config.byoyomi.overtime())
+                } else {
+                    (
+                        self.config.canadian.minutes,
+                        self.config.canadian.overtime(),
+                    )
+                };
+                self.clock
+                    .set((Duration::from_secs(minutes * 60), Duration::ZERO));
+                self.clock.set_overtime(overtime);
+                self.set_up_series_game();
+                self.screen = Screen::Clocks;
+            }
+            PickerEntry::Classical => {
+                self.clock.set(self.config.classical.ctrl());
+                self.clock.set_stages(self.config.classical.stages.clone());
+                self.set_up_series_game();
+                self.screen = Screen::Clocks;
+            }
+            PickerEntry::Hourglass => {
+                let minutes = self.config.hourglass.minutes;
+                self.clock
+                    .set((Duration::from_secs(minutes * 60), Duration::ZERO));
+                self.clock.set_hourglass(true);
+                self.set_up_series_game();
+                self.screen = Screen::Clocks;
+            }
+            PickerEntry::Round => {
+                self.round = RoundTimer::new(&self.config.round);
+                self.screen = Screen::Round;
+            }
+            PickerEntry::Focus => {
+                self.focus = FocusTimer::new(&self.config.focus);
+                self.screen = Screen::Focus;
+            }
+            PickerEntry::Preset(index) => {
+                if let Some(preset) = self.presets.get(index) {
+                    self.clock.set(preset.ctrls());
+                    self.set_up_series_game();
+                    self.screen = Screen::Clocks;
+                }
+            }
+            #[cfg(feature = "plugins")]
+            PickerEntry::Plugin(index) => {
+                if let Some(plugin) = self.plugins.get(index) {
+                    // The plugin's start time replaces the one of the longest built-in control
+                    self.clock.set(TimeCtrl::Tab4);
+                    self.clock.set_time(plugin.start);
+                    self.net_status = Some(plugin.name.clone());
+                    self.plugin = Some(index);
+                    self.screen = Screen::Clocks;
+                }
+            }
+        }
+        self.cap_time();
+        self.apply_handicap();
+    }
+
+    /// Gives the stronger player time odds for the ratings typed with the names, every game
+    /// until they're cleared.
+    fn apply_handicap(&mut self) {
+        let Some(ratings) = self.names.ratings() else {
+            return;
+        };
+        if self.screen != Screen::Clocks {
+            return;
+        }
+        let snapshot = self.clock.snapshot();
+        let suggestion = handicap::suggest(&self.config.handicap, &snapshot, ratings);
+        let delta_ms = suggestion.delta_ms(&snapshot);
+        if delta_ms != 0 {
+            self.adjust(suggestion.stronger, delta_ms);
+        }
+    }
+
+    /// Caps the clocks at the max time set for the time control of the game, if any.
+    fn cap_time(&mut self) {
+        let ctrl = tabs::parse_pgn(&self.clock.snapshot().time_control);
+        let max_time = ctrl.and_then(|ctrl| self.config.max_time(ctrl));
+        self.clock.set_max_time(max_time);
+    }
+
+    /// Sets the clock up for the next game of the match: its time control, the colors and the
+    /// odds of an Armageddon game.
+    fn set_up_series_game(&mut self) {
+        let Some(game) = self.series.as_ref().and_then(Series::game) else {
+            return;
+        };
+        if let Some(control) = game.control {
+            self.clock.set_preset(control);
+        }
+        if self.clock.snapshot().white != game.white {
+            self.clock.flip_first_to_move();
+        }
+        if game.armageddon {
+            self.clock.set_armageddon();
+        }
+    }
+
+    fn new_game(&mut self) {
+        resume::clear();
+        self.draw_offer = None;
+        self.claim = None;
+        self.claim_rejected = false;
+        self.announcer.last = None;
+        self.log.clear();
+        self.low_time_alerted = [false; 2];
+    }
+
+    /// Goes back to the game left unfinished, paused until the players are ready.
+    fn resume(&mut self) {
+        let Some(mut snapshot) = self.resumable.take() else {
+            return;
+        };
+        self.new_game();
+        snapshot.paused = true;
+        self.clock = Clock::from_snapshot(&snapshot);
+        // A reset or a rematch sets the resumed time control up again
+        self.picked = self.entry_of(&snapshot);
+        self.screen = Screen::Clocks;
+    }
+
+    /// The picker entry setting up the time control of `snapshot`, the custom one filled with
+    /// it when no other entry does.
+    fn entry_of(&mut self, snapshot: &Snapshot) -> PickerEntry {
+        match snapshot.overtime {
+            _ if snapshot.hourglass => return PickerEntry::Hourglass,
+            Overtime::Byoyomi { .. } => return PickerEntry::Byoyomi,
+            Overtime::Canadian { .. } => return PickerEntry::Canadian,
+            Overtime::None if !snapshot.stages.is_empty() => return PickerEntry::Classical,
+            Overtime::None if snapshot.armageddon => {
+                return PickerEntry::BuiltIn(TimeCtrl::Armageddon);
+            }
+            Overtime::None => {}
+        }
+        let ctrls = self.clock.time_ctrl();
+        if snapshot.player2_time_control.is_none()
+            && let Some(ctrl) = TimeCtrl::from_pgn(&snapshot.time_control)
+        {
+            return PickerEntry::BuiltIn(ctrl);
+        }
+        if let Some(index) = self.presets.iter().position(|p| p.ctrls() == ctrls) {
+            return PickerEntry::Preset(index);
+        }
+        self.custom = CustomInput::new(ctrls);
+        PickerEntry::Custom
+    }
+
+    /// Saves the game being played, to resume it if the app is closed before it's over.
+    fn save_game(&mut self) {
+        self.saved_at = Instant::now();
+        let snapshot = self.clock.snapshot();
+        let playing = matches!(self.role, Role::Local | Role::Host);
+        if !playing || snapshot.turn.is_none() || self.clock.is_over() {
+            return;
+        }
+        if let Err(e) = resume::save(&snapshot) {
+            self.net_status = Some(format!("{e:#}"));
+        }
+    }
+
+    /// Sounds the alert the first time a player's clock falls into the last seconds.
+    fn alert_low_time(&mut self, prev: &Snapshot) {
+        let next = self.clock.snapshot();
+        let times = |snapshot: &Snapshot| {
+            [snapshot.player1_ms, snapshot.player2_ms].map(Duration::from_millis)
+        };
+        for (i, (before, after)) in times(prev).into_iter().zip(times(&next)).enumerate() {
+            if !Clock::burning(before) && Clock::burning(after) && !self.low_time_alerted[i] {
+                self.low_time_alerted[i] = true;
+                self.sound.play(&self.config.sound, SoundKind::LowTime);
+            }
+        }
+    }
+
+    /// Announces both times and the player to move, for a player who can't see the clocks.
+    fn announce_times(&mut self) {
+        let text = announce::summary(&self.clock.snapshot());
+        if let Err(e) = self.announcer.announce(&self.config.accessibility, text) {
+            self.net_status = Some(format!("{e:#}"));
+        }
+    }
+
+    /// Registers a draw offer of `player`, or retracts the one they made.
+    fn offer_draw(&mut self, player: Player) {
+        let snapshot = self.clock.snapshot();
+        if snapshot.turn.is_none() || snapshot.flagged.is_some() {
+            return;
+        }
+        // The opponent of the offering player accepts with 'd' or declines with their move
+        if self.draw_offer == Some(player.opponent()) {
+            self.draw_offer = None;
+            self.log_event(player, GameEvent::DrawAccepted);
+            self.end_game(Outcome::DrawAgreed);
+            return;
+        }
+        let event = if self.draw_offer == Some(player) {
+            self.draw_offer = None;
+            GameEvent::DrawRetracted
+        } else {
+            self.draw_offer = Some(player);
+            GameEvent::DrawOffered
+        };
+        self.log_event(player, event);
+    }
+
+    /// Ends the game with the resignation of `player`.
+    fn resign(&mut self, player: Player) {
+        if self.clock.is_over() {
+            return;
+        }
+        self.log_event(player, GameEvent::Resigned);
+        self.end_game(Outcome::Resignation {
+            winner: player.opponent(),
+        });
+    }
+
+    /// Stops both clocks on a result agreed at the board and shows it.
+    fn end_game(&mut self, outcome: Outcome) {
+        self.draw_offer = None;
+        self.clock.end(outcome);
+        self.record_game();
+        self.screen = Screen::GameOver;
+    }
+
+    /// Drops the draw offer the opponent answered with a move.
+    fn lapse_draw_offer(&mut self, prev: &Snapshot) {
+        let Some(offering) = self.draw_offer else {
+            return;
+        };
+        for event in ClockEvent::between(prev, &self.clock.snapshot()) {
+            if let ClockEvent::Hit { player, .. } = event
+                && player == offering.opponent()
+            {
+                self.draw_offer = None;
+                self.log_event(player, GameEvent::DrawDeclined);
+            }
+        }
+    }
+
+    /// Stops the clock once the moves followed on the board mate or stalemate.
+    #[cfg(feature = "board")]
+    fn end_on_board(&mut self) {
+        let white = self.clock.snapshot().white;
+        if let Some(outcome) = self.score.board().and_then(|board| board.outcome(white)) {
+            self.clock.end(outcome);
+        }
+    }
+
+    /// Gives the opponent of `player` the time set for moretime, during the game.
+    fn give_time(&mut self, player: Player) {
+        if self.clock.snapshot().running {
+            let ms = self.config.moretime.seconds * 1000;
+            self.adjust(player.opponent(), ms as i64);
+        }
+    }
+
+    fn adjust(&mut self, player: Player, delta_ms: i64) {
+        self.clock.adjust(player, delta_ms);
+        self.feed.adjusted(player, delta_ms);
+    }
+
+    fn log_event(&mut self, player: Player, event: GameEvent) {
+        self.feed.push(Line::from(
+            // The event reads after the player, e.g. "{} offered a draw"
+            trf(&format!("{{}} {event}"), &[&i18n::player(player)]).fg(Color::LightYellow),
+        ));
+        self.log.push(LogEntry {
+            at: date::now(),
+            move_number: self.clock.move_number(),
+            player,
+            event,
+        });
+    }
+
+    /// The entries of the picker in order: the built-in time controls, the ones of the config,
+    /// the round and focus timers, the saved presets, then plugins.
+    fn picker_entries(&self) -> Vec<PickerEntry> {
+        let mut entries: Vec<_> = TimeCtrl::iter().map(PickerEntry::BuiltIn).collect();
+        entries.extend([
+            PickerEntry::Custom,
+            PickerEntry::Byoyomi,
+            PickerEntry::Canadian,
+            PickerEntry::Classical,
+            PickerEntry::Hourglass,
+            PickerEntry::Round,
+            PickerEntry::Focus,
+        ]);
+        entries.extend((0..self.presets.len()).map(PickerEntry::Preset));
+        #[cfg(feature = "plugins")]
+        entries.extend((0..self.plugins.len()).map(PickerEntry::Plugin));
+        entries
+    }
+
+    fn picker_label(&self, entry: PickerEntry) -> String {
+        match entry {
+            PickerEntry::BuiltIn(ctrl) => tr(&ctrl.to_string()).to_string(),
+            PickerEntry::Custom => tr("Custom").to_string(),
+            PickerEntry::Byoyomi => self.config.byoyomi.label(),
+            PickerEntry::Canadian => self.config.canadian.label(),
+            PickerEntry::Classical => self.config.classical.label(),
+            PickerEntry::Hourglass => self.config.hourglass.label(),
+            PickerEntry::Round => RoundTimer::label(&self.config.round),
+            PickerEntry::Focus => FocusTimer::label(&self.config.focus),
+            PickerEntry::Preset(index) => self.presets[index].name.clone(),
+            #[cfg(feature = "plugins")]
+            PickerEntry::Plugin(index) => self.plugins[index].name.clone(),
+        }
+    }
+
+    /// Position of the entry picked among [`App::picker_entries`].
+    fn picked_index(&self) -> usize {
+        let entries = self.picker_entries();
+        entries.iter().position(|&e| e == self.picked).unwrap_or(0)
+    }
+
+    /// Saves `preset`, replacing the one of the same name.
+    fn save_preset(&mut self, preset: Preset) {
+        let same_name = self
+            .presets
+            .iter_mut()
+            .find(|saved| saved.name == preset.name);
+        match same_name {
+            Some(saved) => *saved = preset,
+            None => self.presets.push(preset),
+        }
+        self.save_presets();
+    }
+
+    fn save_presets(&mut self) {
+        if let Err(e) = presets::save(&self.presets) {
+            self.net_status = Some(format!("{e:#}"));
+        }
+    }
+
+    fn select_next_time_ctrl(&mut self) {
+        let entries = self.picker_entries();
+        let next = (self.picked_index() + 1).min(entries.len() - 1);
+        self.picked = entries[next];
+    }
+
+    fn select_previous_time_ctrl(&mut self) {
+        let entries = self.picker_entries();
+        self.picked = entries[self.picked_index().saturating_sub(1)];
+    }
+
+    /// Edits the move typed against the engine once its field is open, returning whether the
+    /// key was used for it.
+    fn type_move(&mut self, key_event: &KeyEvent) -> bool {
+        let Some(input) = &mut self.move_input else {
+            return false;
+        };
+        match (self.keys.find(FIELD_ACTIONS, key_event), key_event.code) {
+            (Some(Action::Confirm), _) if !input.is_empty() => {
+                match format!("move {input}").parse() {
+                    Ok(command) => self.handle_command(command),
+                    Err(e) => self.net_status = Some(e),
+                }
+                self.move_input = None;
+            }
+            (Some(Action::Back), _) => self.move_input = None,
+            // Space stays the clock's, no move contains it
+            (_, KeyCode::Char(' ')) => return false,
+            (_, KeyCode::Char(c)) => input.push(c),
+            (_, KeyCode::Backspace) => {
+                input.pop();
+            }
+            _ => return false,
+        }
+        true
+    }
+
     // Handles the tick event of the terminal.
-    pub fn tick(&self) {}
+    pub fn tick(&mut self) {
+        self.eco.check();
+        self.hints.fade = self.animated();
+        if let Err(e) = self.announcer.tick(&self.config.accessibility, &self.clock) {
+            self.net_status = Some(format!("{e:#}"));
+        }
+        // Mirrored clocks follow the time of their host
+        if !matches!(self.role, Role::Local | Role::Host) {
+            return;
+        }
+        let Some(discrepancy) = self.wall_clock.check() else {
+            return;
+        };
+        let snapshot = self.clock.snapshot();
+        self.net_status = Some(match wallclock::log(discrepancy, &snapshot) {
+            Ok(()) => discrepancy.to_string(),
+            Err(e) => format!("{discrepancy}, could not log it: {e:#}"),
+        });
+        // Time lost while sleeping can be taken off the clock that was running
+        if discrepancy.0 > 0 && snapshot.running {
+            self.correction = snapshot.turn.map(|player| (player, discrepancy));
+        }
+    }
 
     pub fn ui(&mut self, frame: &mut Frame) {
+        self.hints
+            .update(self.screen == Screen::Clocks && self.clock.snapshot().running);
+        let [area, hints] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(u16::from(self.hints.is_visible())),
+        ])
+        .areas(frame.area());
         match self.screen {
-            Screen::Clocks => self.render_clocks(frame),
+            Screen::Clocks => self.render_clocks(frame, area),
             Screen::SelectTimeCtrl => self.render_select_time_ctrl(frame),
-            Screen::TimeOut => self.render_time_out(frame),
+            Screen::CustomTimeCtrl => self.render_custom_time_ctrl(frame),
+            Screen::PlayerNames => self.render_player_names(frame),
+            Screen::TimeOut => self.render_time_out(frame, area),
+            Screen::GameOver => self.render_game_over(frame, area),
+            Screen::Discover => self.render_discover(frame),
+            Screen::Arbiter => self.dashboard.render(area, frame.buffer_mut()),
+            Screen::History => frame.render_widget(&self.games, area),
+            Screen::Round => frame.render_widget(&self.round, area),
+            Screen::Focus => frame.render_widget(&self.focus, area),
+            Screen::Keys => frame.render_widget(&self.keys, area),
+        }
+        self.hints
+            .render(&self.key_hints(), hints, frame.buffer_mut());
+        if let Some(input) = &self.move_input {
+            let [bottom] = Layout::vertical([Constraint::Length(1)])
+                .flex(Flex::End)
+                .areas(area);
+            Line::from(format!(" move: {input}_ ").fg(Color::LightYellow))
+                .centered()
+                .render(bottom, frame.buffer_mut());
+        }
+        if let Some((player, discrepancy)) = self.correction {
+            self.render_correction(frame, player, discrepancy);
+        }
+        if let Some(flagged) = self.claim {
+            self.render_claim(frame, flagged);
+        }
+        if self.confirm_reset {
+            self.render_confirm_reset(frame);
+        }
+        if let Some(snapshot) = &self.resumable
+            && self.screen == Screen::SelectTimeCtrl
+        {
+            self.render_resume(frame, snapshot);
+        }
+        if self.help {
+            self.render_help(frame);
+        }
+        if let Some(status) = &self.net_status {
+            let [top] = Layout::vertical([Constraint::Length(1)]).areas(frame.area());
+            Line::from(format!(" {status} ").fg(Color::LightGreen))
+                .centered()
+                .render(top, frame.buffer_mut());
         }
     }
 
-    pub fn render_clocks(&mut self, frame: &mut Frame) {
-        self.clock.render(frame.area(), frame.buffer_mut());
+    /// Whether the hint bar fades and tenths of seconds tick, not in eco mode or with
+    /// reduced motion.
+    fn animated(&self) -> bool {
+        !self.eco.is_enabled() && !self.config.accessibility.reduced_motion
+    }
+
+    /// Keys of the current screen shown on the hint bar, with what they do.
+    fn key_hints(&self) -> Vec<(String, &'static str)> {
+        let started = self.clock.snapshot().turn.is_some();
+        let preset_picked = matches!(self.picked, PickerEntry::Preset(_));
+        let key = |actions: &[Action]| self.keys.label(actions);
+        let hints = |hints: &[(&[Action], &'static str)]| {
+            hints
+                .iter()
+                .map(|&(actions, hint)| (key(actions), hint))
+                .collect()
+        };
+        match (&self.screen, &self.role) {
+            (Screen::Clocks, Role::Spectator) => hints(&[
+                (&[Action::Events], "events"),
+                (&[Action::Times], "times"),
+                (&[Action::Quit], "quit"),
+            ]),
+            (Screen::Clocks, Role::Client(_)) => hints(&[
+                (&[Action::Hit], "hit"),
+                (&[Action::Pause], "pause"),
+                (&[Action::Events], "events"),
+                (&[Action::Times], "times"),
+            ]),
+            (Screen::Clocks, _) if self.engine.is_some() => hints(&[
+                (&[Action::Hit], "hit"),
+                (&[Action::EngineMove], "engine move"),
+                (&[Action::Pause], "pause"),
+                (&[Action::Draw], "draw"),
+                (&[Action::ResignPlayer1, Action::ResignPlayer2], "resign"),
+                (&[Action::Reset], "reset"),
+                (&[Action::Score], "score"),
+                (&[Action::Events], "events"),
+            ]),
+            (Screen::Clocks, _) if !started => hints(&[
+                (&[Action::Hit], "start"),
+                (&[Action::Previous, Action::Next], "first to move"),
+                (&[Action::SwapSides], "swap sides"),
+                (&[Action::Handicap], "handicap"),
+                (&[Action::Mute], "mute"),
+                (&[Action::Score], "score"),
+                (&[Action::Events], "events"),
+                (&[Action::Eco], "eco"),
+            ]),
+            (Screen::Clocks, _) => hints(&[
+                (&[Action::Hit], "hit"),
+                (&[Action::Pause], "pause"),
+                (&[Action::Draw], "draw"),
+                (&[Action::ResignPlayer1, Action::ResignPlayer2], "resign"),
+                (&[Action::MoreTime], "moretime"),
+                (&[Action::Reset], "reset"),
+                (&[Action::Score], "score"),
+                (&[Action::Events], "events"),
+                (&[Action::Times], "times"),
+            ]),
+            (Screen::SelectTimeCtrl, _) if self.resumable.is_some() => {
+                hints(&[(&[Action::Yes], "resume"), (&[Action::No], "discard")])
+            }
+            (Screen::SelectTimeCtrl, _) if preset_picked => hints(&[
+                (&[Action::Previous, Action::Next], "choose"),
+                (&[Action::Increment], "increment"),
+                (&[Action::Start], "start"),
+                (&[Action::Delete], "delete"),
+                (&[Action::History], "history"),
+                (&[Action::Quit], "quit"),
+            ]),
+            (Screen::SelectTimeCtrl, _) => hints(&[
+                (&[Action::Previous, Action::Next], "choose"),
+                (&[Action::Increment], "increment"),
+                (&[Action::Start], "start"),
+                (&[Action::History], "history"),
+                (&[Action::Keys], "keys"),
+                (&[Action::Help], "help"),
+                (&[Action::Quit], "quit"),
+            ]),
+            (Screen::History, _) => vec![("↑↓".into(), "choose"), (key(&[Action::Back]), "back")],
+            (Screen::Keys, _) => vec![(key(&[Action::Back]), "back")],
+            (Screen::PlayerNames | Screen::CustomTimeCtrl, _) => vec![
+                ("Tab".into(), "next field"),
+                (key(&[Action::Confirm]), "start"),
+                (key(&[Action::Back]), "back"),
+            ],
+            (Screen::TimeOut | Screen::GameOver, _)
+                if self.series.as_ref().is_some_and(|s| s.game().is_some()) =>
+            {
+                hints(&[(&[Action::NewGame], "next game"), (&[Action::Quit], "quit")])
+            }
+            (Screen::TimeOut | Screen::GameOver, _) => hints(&[
+                (&[Action::NewGame], "new game"),
+                (&[Action::Rematch], "rematch"),
+                (&[Action::ExportCsv, Action::ExportJson], "export CSV/JSON"),
+                (&[Action::Quit], "quit"),
+            ]),
+            (Screen::Discover, _) => hints(&[
+                (&[Action::Up, Action::Down], "choose"),
+                (&[Action::Start], "join"),
+                (&[Action::Quit], "quit"),
+            ]),
+            (Screen::Arbiter, _) => vec![
+                ("arrows".into(), "choose"),
+                ("Enter".into(), "open"),
+                (key(&[Action::Quit]), "quit"),
+            ],
+            (Screen::Round, _) => hints(&[
+                (&[Action::Hit], "start/pause"),
+                (&[Action::Confirm], "back"),
+            ]),
+            (Screen::Focus, _) => hints(&[
+                (&[Action::Hit], "start/pause"),
+                (&[Action::Skip], "skip"),
+                (&[Action::Confirm], "back"),
+            ]),
+        }
+    }
+
+    /// The first key bound to `action`, for the texts telling what to press.
+    fn key(&self, action: Action) -> String {
+        self.keys.label(&[action])
+    }
+
+    /// Every key of the current screen with what it does, for the help. Screens without
+    /// bindable actions list their hints.
+    fn help_keys(&self) -> Vec<(String, String)> {
+        let actions = match (&self.screen, &self.role) {
+            (Screen::Clocks, Role::Local | Role::Host) => CLOCKS_ACTIONS,
+            (Screen::SelectTimeCtrl, _) if self.resumable.is_none() => PICKER_ACTIONS,
+            (Screen::TimeOut | Screen::GameOver, _) => GAME_OVER_ACTIONS,
+            (Screen::Round | Screen::Focus, _) => TIMER_ACTIONS,
+            _ => {
+                let hints = self.key_hints().into_iter();
+                return hints
+                    .map(|(keys, hint)| (keys, tr(hint).to_string()))
+                    .collect();
+            }
+        };
+        let global = [Action::Hints, Action::Help, Action::Eco, Action::Mute];
+        let actions = actions.iter().chain(&global).map(|&action| {
+            let what = action.to_string().replace('_', " ");
+            (self.keys.bound(action), tr(&what).to_string())
+        });
+        actions.collect()
+    }
+
+    pub fn render_clocks(&mut self, frame: &mut Frame, area: Rect) {
+        let move_times_width = if self.show_move_times {
+            moves::WIDTH
+        } else {
+            0
+        };
+        let [area, move_times, feed] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(move_times_width),
+            Constraint::Length(if self.show_feed { 36 } else { 0 }),
+        ])
+        .areas(area);
+        #[cfg(feature = "board")]
+        let area = match self.score.board() {
+            Some(board) => {
+                let [area, panel] =
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Length(board::WIDTH)])
+                        .areas(area);
+                let [panel] = Layout::vertical([Constraint::Length(11)])
+                    .flex(Flex::Center)
+                    .areas(panel);
+                frame.render_widget(board, panel);
+                area
+            }
+            None => area,
+        };
+        self.clocks_area = area;
+        ClockWidget::new(&self.clock)
+            .theme(self.config.theme())
+            .font(&self.font)
+            .layout(self.config.layout)
+            .mirrored(self.config.mirrored)
+            .names(self.names.names())
+            .swapped(self.swapped)
+            .tenths(self.animated())
+            .subseconds(self.config.subseconds)
+            .text(self.config.accessibility.screen_reader)
+            .render(area, frame.buffer_mut());
+        if let Some(series) = &self.series {
+            let [top] = Layout::vertical([Constraint::Length(1)]).areas(area);
+            Line::from(format!(" {} ", series.status()).fg(Color::LightGreen))
+                .centered()
+                .render(top, frame.buffer_mut());
+        }
+        if let Some(announcement) = &self.announcer.last {
+            // Under the status line at the top
+            let [_, line] = Layout::vertical([Constraint::Length(1); 2]).areas(area);
+            Line::from(format!(" {announcement} ").fg(Color::LightCyan))
+                .centered()
+                .render(line, frame.buffer_mut());
+        }
+        frame.render_widget(&self.feed, feed);
+        frame.render_widget(MoveTimesPanel::new(self.clock.move_times()), move_times);
+        // Above the time control or hint at the bottom
+        let [score, _] = Layout::vertical([Constraint::Length(1); 2])
+            .flex(Flex::End)
+            .areas(area);
+        frame.render_widget(&self.score, score);
+        if let Some(player) = self.draw_offer {
+            // Where the clock marks the first to move before the game
+            let [left, right] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(area);
+            let side = if (player == Player::Player1) != self.swapped {
+                left
+            } else {
+                right
+            };
+            let [_, mark, _] = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Percentage(30),
+            ])
+            .areas(side);
+            Line::from(format!(" {} ", tr("draw offered")).fg(Color::LightYellow).bold())
+                .centered()
+                .render(mark, frame.buffer_mut());
+        }
     }
 
     pub fn render_select_time_ctrl(&mut self, frame: &mut Frame) {
-        let center = self.popup_area(frame.area(), 40, 3);
-        self.time_ctrl_selecter.render(center, frame.buffer_mut());
+        let center = self.popup_area(frame.area(), 60, 3);
+        let theme = self.config.theme();
+        let extras: Vec<_> = self
+            .picker_entries()
+            .into_iter()
+            .filter(|entry| !matches!(entry, PickerEntry::BuiltIn(_)))
+            .map(|entry| self.picker_label(entry))
+            .collect();
+        let picked = self.picked_index();
+        tabs::render_picker(&extras, picked, theme, center, frame.buffer_mut());
+        let kind = trf("increment: {}", &[&self.clock.increment_kind()]);
+        Block::default()
+            .title_bottom(Line::from(format!(" {kind} ").fg(theme.accent)).centered())
+            .render(center, frame.buffer_mut());
     }
 
-    pub fn render_time_out(&mut self, frame: &mut Frame) {
-        self.render_clocks(frame);
+    pub fn render_time_out(&mut self, frame: &mut Frame, area: Rect) {
+        self.render_clocks(frame, area);
+    }
+
+    /// The clocks with the result of the game over them.
+    fn render_game_over(&mut self, frame: &mut Frame, area: Rect) {
+        self.render_clocks(frame, area);
+        let snapshot = self.clock.snapshot();
+        let (Some(outcome), Some(result)) = (snapshot.outcome, snapshot.result()) else {
+            return;
+        };
+        // Above the clocks, which keep the final times
+        let [top, _] =
+            Layout::vertical([Constraint::Percentage(40), Constraint::Fill(1)]).areas(area);
+        let area = self.popup_area(top, 40, 4);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Game over")))
+            .title_alignment(Alignment::Center)
+            .border_style(Color::LightGreen);
+        let text = vec![
+            Line::from(i18n::outcome(outcome)),
+            Line::from(result.bold()),
+        ];
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(text)
+            .centered()
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// Asks the arbiter whether to take the time the computer slept off `player`'s clock.
+    fn render_correction(&self, frame: &mut Frame, player: Player, discrepancy: Discrepancy) {
+        let area = self.popup_area(frame.area(), 50, 5);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Wall clock")))
+            .title_alignment(Alignment::Center)
+            .border_style(Color::LightRed);
+        let secs = format!("{:.1}", discrepancy.0 as f64 / 1000.0);
+        let player = i18n::player(player);
+        let [yes, no] = [Action::Yes, Action::No].map(|action| self.key(action));
+        let question = trf(
+            "Take {}s off the clock of {}? ({}/{})",
+            &[&secs, &player, &yes, &no],
+        );
+        let text = vec![
+            Line::from(discrepancy.to_string()),
+            Line::from(tr("the computer may have slept")),
+            Line::from(question.bold()),
+        ];
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(text)
+            .centered()
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// Asks the opponent of the `flagged` player or the arbiter to confirm the win on time.
+    fn render_claim(&self, frame: &mut Frame, flagged: Player) {
+        let area = self.popup_area(frame.area(), 50, 5);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Flag")))
+            .title_alignment(Alignment::Center)
+            .border_style(Color::LightRed);
+        let winner = i18n::player(flagged.opponent());
+        let [yes, no, draw] =
+            [Action::Yes, Action::No, Action::Draw].map(|action| self.key(action));
+        let text = vec![
+            Line::from(trf("The flag of {} fell", &[&i18n::player(flagged)])),
+            Line::from(trf("Confirm the win of {}? ({}/{})", &[&winner, &yes, &no]).bold()),
+            Line::from(trf("{} if they can't mate, the game is drawn", &[&draw]).dark_gray()),
+        ];
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(text)
+            .centered()
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// Asks whether to abort the game and start it again, e.g. after a false start.
+    fn render_confirm_reset(&self, frame: &mut Frame) {
+        let area = self.popup_area(frame.area(), 50, 4);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Reset")))
+            .title_alignment(Alignment::Center)
+            .border_style(Color::LightYellow);
+        let [yes, no] = [Action::Yes, Action::No].map(|action| self.key(action));
+        let text = vec![
+            Line::from(tr("Abort the game and start it again?")),
+            Line::from(trf("{} to reset, {} to go on", &[&yes, &no]).dark_gray()),
+        ];
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(text)
+            .centered()
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// The keys of the current screen over it, dimmed behind.
+    fn render_help(&self, frame: &mut Frame) {
+        let keys = self.help_keys();
+        let width = keys
+            .iter()
+            .map(|(k, _)| k.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut text: Vec<_> = keys
+            .into_iter()
+            .map(|(keys, what)| {
+                Line::from(vec![
+                    format!("{keys:>width$}  ").light_green().bold(),
+                    what.into(),
+                ])
+            })
+            .collect();
+        text.push(Line::from(tr("any key to close").dark_gray()).centered());
+        let area = self.popup_area(frame.area(), 60, text.len() as u16 + 2);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Help")))
+            .title_alignment(Alignment::Center)
+            .border_style(Color::LightGreen)
+            .padding(Padding::horizontal(1));
+        let buf = frame.buffer_mut();
+        buf.set_style(buf.area, Modifier::DIM);
+        Clear.render(area, buf);
+        Paragraph::new(text).block(block).render(area, buf);
+    }
+
+    /// Offers to go back to the game left unfinished last time.
+    fn render_resume(&self, frame: &mut Frame, snapshot: &Snapshot) {
+        let area = self.popup_area(frame.area(), 50, 5);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Resume previous game")))
+            .title_alignment(Alignment::Center)
+            .border_style(Color::LightGreen);
+        let ctrl = tabs::parse_pgn(&snapshot.time_control).map(tabs::label);
+        let times = [snapshot.player1_ms, snapshot.player2_ms]
+            .map(|ms| Time(Duration::from_millis(ms)).to_string());
+        let [yes, no] = [Action::Yes, Action::No].map(|action| self.key(action));
+        let text = vec![
+            Line::from(format!(
+                "{}, {}",
+                ctrl.unwrap_or_default(),
+                trf("Move {}", &[&snapshot.move_number])
+            )),
+            Line::from(format!("{}   {}", times[0], times[1]).bold()),
+            Line::from(trf("{} to resume, {} to discard", &[&yes, &no]).dark_gray()),
+        ];
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(text)
+            .centered()
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// Asks for the base time and increment of a time control that isn't among the presets.
+    fn render_custom_time_ctrl(&self, frame: &mut Frame) {
+        let area = self.popup_area(frame.area(), 50, 12);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Custom time control")))
+            .title_alignment(Alignment::Center)
+            .border_style(Color::LightGreen);
+        let labels = [tr("Base time: {} minutes"), tr("Increment: {} seconds")];
+        let field = |i: usize| {
+            let cursor = if i == self.custom.focused { "_" } else { "" };
+            let value = format!("{}{cursor}", self.custom.value(i));
+            let line = Line::from(trf(labels[i % 2], &[&value]));
+            if i == self.custom.focused {
+                line.bold()
+            } else {
+                line.dark_gray()
+            }
+        };
+        let naming = self.custom.focused == self.custom.fields.len();
+        let cursor = if naming { "_" } else { "" };
+        let name = format!("{}{cursor}", self.custom.name);
+        let name = Line::from(trf("Save as: {}", &[&name]));
+        let name = if naming {
+            name.bold()
+        } else {
+            name.dark_gray()
+        };
+        let confirm = self.key(Action::Confirm);
+        let status = match self.custom.ctrl() {
+            Ok(ctrl) if self.custom.preset().is_some() => Line::from(
+                trf("{}, {} to save and start", &[&ctrl.label(), &confirm]).fg(Color::LightGreen),
+            ),
+            Ok(ctrl) => {
+                Line::from(trf("{}, {} to start", &[&ctrl.label(), &confirm]).fg(Color::LightGreen))
+            }
+            Err(problem) => Line::from(problem.dark_gray()),
+        };
+        let player = |player| Line::from(i18n::player(player).underlined());
+        let text = vec![
+            player(Player::Player1),
+            field(0),
+            field(1),
+            player(Player::Player2),
+            field(2),
+            field(3),
+            Line::default(),
+            name,
+            Line::default(),
+            status,
+        ];
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(text)
+            .centered()
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    fn render_player_names(&self, frame: &mut Frame) {
+        let area = self.popup_area(frame.area(), 50, 9);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Players")))
+            .title_alignment(Alignment::Center)
+            .border_style(Color::LightGreen);
+        let field = |i: usize| {
+            let player = [Player::Player1, Player::Player2][i / 2];
+            let cursor = if i == self.names.focused { "_" } else { "" };
+            let line = if i.is_multiple_of(2) {
+                let value = format!("{}{cursor}", self.names.fields[player.index()]);
+                Line::from(format!("{}: {value}", i18n::player(player)))
+            } else {
+                let value = format!("{}{cursor}", self.names.ratings[player.index()]);
+                Line::from(trf("rating: {}", &[&value]))
+            };
+            if i == self.names.focused {
+                line.bold()
+            } else {
+                line.dark_gray()
+            }
+        };
+        let odds = match self.names.ratings() {
+            Some([rating1, rating2]) if rating1 == rating2 => {
+                tr("Equal ratings, no time odds").to_string()
+            }
+            Some(ratings) => {
+                let (stronger, percent) = handicap::share(&self.config.handicap, ratings);
+                let (stronger, percent) = (i18n::player(stronger), format!("{percent:.0}"));
+                trf("{} gets {}% of the time", &[&stronger, &percent])
+            }
+            None => tr("Type both ratings for time odds").to_string(),
+        };
+        let start = trf(
+            "{} to start, empty names are left out",
+            &[&self.key(Action::Confirm)],
+        );
+        let text = vec![
+            field(0),
+            field(1),
+            field(2),
+            field(3),
+            Line::default(),
+            Line::from(odds.dark_gray()),
+            Line::from(start.fg(Color::LightGreen)),
+        ];
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(text)
+            .centered()
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    pub fn render_discover(&mut self, frame: &mut Frame) {
+        let height = self.hosts.len().clamp(1, 10) as u16 + 2;
+        let area = self.popup_area(frame.area(), 50, height);
+        let block = Block::bordered()
+            .title(format!(" {} ", tr("Join a game")))
+            .title_alignment(Alignment::Center);
+        if self.hosts.is_empty() {
+            Line::from(tr("searching...").dark_gray())
+                .centered()
+                .render(block.inner(area), frame.buffer_mut());
+            block.render(area, frame.buffer_mut());
+            return;
+        }
+        let list = List::new(self.hosts.iter().map(|host| host.to_string()))
+            .block(block)
+            .highlight_symbol("> ")
+            .highlight_style(Color::LightGreen);
+        StatefulWidget::render(list, area, frame.buffer_mut(), &mut self.host_selected);
     }
 
     // helper function to create a centered rect using up certain percentage of the available rect `r`
@@ -146,10 +1987,101 @@ impl App {
     }
 
     pub fn quit(&mut self) {
+        self.save_game();
         self.running = false;
     }
 
+    pub fn time_out(&mut self) {
+        // Several timer ticks may report the same time out. Games of others aren't recorded.
+        if self.screen == Screen::Clocks && self.clock.is_time_out() {
+            self.sound.play(&self.config.sound, SoundKind::Flag);
+        }
+        if self.screen == Screen::Clocks && !matches!(self.role, Role::Spectator) {
+            // The result waits for the claim of a flag to be confirmed
+            if self.config.claims.confirm && self.clock.snapshot().outcome.is_none() {
+                self.claim = self.clock.flagged();
+                if self.claim.is_some() {
+                    return;
+                }
+            }
+            self.record_game();
+        }
+        self.screen = Screen::TimeOut;
+    }
+
+    fn record_game(&mut self) {
+        let snapshot = self.clock.snapshot();
+        let Some(result) = snapshot.result() else {
+            return;
+        };
+        if let Some(series) = &mut self.series {
+            series.record(&snapshot);
+        }
+        // The snapshot only has the time control of the first stage
+        let time_control = match tabs::parse_pgn(&snapshot.time_control) {
+            Some(ctrl) if !self.clock.stages().is_empty() => {
+                tabs::pgn_stages(ctrl, self.clock.stages())
+            }
+            _ => snapshot.time_control,
+        };
+        let record = GameRecord {
+            finished_at: date::now(),
+            time_control,
+            white: self.player_name(snapshot.white),
+            black: self.player_name(snapshot.white.opponent()),
+            result: result.to_string(),
+            meta: self.meta.clone(),
+            log: self.log.clone(),
+            moves: self.score.moves().to_vec(),
+            // White's last move counts even when Black hasn't answered it
+            move_count: snapshot.move_number - u32::from(snapshot.turn == Some(snapshot.white)),
+            move_times_ms: self
+                .clock
+                .move_times()
+                .iter()
+                .map(|time| time.time.as_millis() as u64)
+                .collect(),
+        };
+        resume::clear();
+        // A failing history write must not take the running app down, only be reported
+        let error = |e: anyhow::Error| format!("{e:#}");
+        if let Err(e) = history::append(&record) {
+            self.net_status = Some(trf("game not saved to the history: {}", &[&error(e)]));
+        } else if let Err(e) = history::prune(Retention::from(&self.config.history)) {
+            self.net_status = Some(trf("history not pruned: {}", &[&error(e)]));
+        }
+    }
+
+    /// Writes the time each move of the game took to a file, for spreadsheets.
+    fn export_move_times(&mut self, format: Format) {
+        let names = [Player::Player1, Player::Player2].map(|player| self.player_name(player));
+        let status = match export::write(self.clock.move_times(), &names, format) {
+            Ok(path) => trf("Move times written to {}", &[&path.display()]),
+            Err(e) => format!("{e:#}"),
+        };
+        self.net_status = Some(status);
+    }
+
+    /// The name typed for `player`, or which player they are.
+    fn player_name(&self, player: Player) -> String {
+        self.names
+            .name(player)
+            .map_or_else(|| player.to_string(), str::to_string)
+    }
+
     pub fn hit_clock(&mut self) {
+        if !self.clock.is_over() {
+            self.sound.play(&self.config.sound, SoundKind::Hit);
+        }
         self.clock.hit();
     }
+
+    /// Hits the host's clock, which only stops it while it runs, like [`App::hit_clock`].
+    fn hit_host_clock(&mut self) {
+        let player = net::HOST_PLAYER;
+        if !self.clock.is_over() && self.clock.curr_player().is_none_or(|p| p == player) {
+            self.sound.play(&self.config.sound, SoundKind::Hit);
+        }
+        self.clock.hit_by(player);
+    }
 }
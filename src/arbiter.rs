@@ -0,0 +1,215 @@
+use std::{path::Path, time::Duration};
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Paragraph, Widget},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    clock::{Clock, ClockWidget, Player, Time},
+    control,
+    event::Event,
+    i18n::{tr, trf},
+    net::{self, NetEvent},
+};
+
+const CELL_WIDTH: u16 = 30;
+const CELL_HEIGHT: u16 = 4;
+
+/// A clock followed on the dashboard.
+#[derive(Debug)]
+struct Board {
+    label: String,
+    clock: Clock,
+    /// Why the clock isn't followed, until its next update.
+    problem: Option<String>,
+}
+
+/// The clocks of several boards at a glance, for arbiters.
+#[derive(Debug, Default)]
+pub struct Dashboard {
+    boards: Vec<Board>,
+    selected: usize,
+    /// Board shown in full instead of the grid.
+    open: Option<usize>,
+    /// Cells per row of the grid as last drawn, to move the selection up and down.
+    columns: usize,
+}
+
+impl Dashboard {
+    /// Follows the clocks at `addrs`, network hosts or control sockets, read-only. Their
+    /// updates are sent to `events` as [`Event::Board`], boards that can't be reached are
+    /// shown with the error.
+    pub async fn follow(addrs: &[String], events: &mpsc::UnboundedSender<Event>) -> Dashboard {
+        let mut boards = vec![];
+        for (index, addr) in addrs.iter().enumerate() {
+            let (sender, mut received) = mpsc::unbounded_channel();
+            let followed = if control::is_socket(addr) {
+                control::follow(Path::new(addr), sender).await
+            } else {
+                net::join(addr, true, sender).await.map(drop)
+            };
+            let events = events.clone();
+            tokio::spawn(async move {
+                while let Some(event) = received.recv().await {
+                    if let Event::Net(event) = event
+                        && events.send(Event::Board(index, event)).is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+            boards.push(Board {
+                label: addr.clone(),
+                clock: Clock::default(),
+                problem: followed.err().map(|e| e.to_string()),
+            });
+        }
+        Dashboard {
+            boards,
+            ..Dashboard::default()
+        }
+    }
+
+    pub fn update(&mut self, index: usize, event: NetEvent) {
+        let Some(board) = self.boards.get_mut(index) else {
+            return;
+        };
+        match event {
+            NetEvent::Sync(snapshot) => {
+                board.clock = Clock::from_snapshot(&snapshot);
+                board.problem = None;
+            }
+            NetEvent::Status(status) if status.starts_with("disconnected") => {
+                board.problem = Some(status)
+            }
+            _ => {}
+        }
+    }
+
+    /// Runs the clocks between two updates of their hosts.
+    pub fn tick_timer(&mut self) {
+        for board in &mut self.boards {
+            if !board.clock.is_over() {
+                board.clock.tick_timer();
+            }
+        }
+    }
+
+    /// Whether the grid, rather than a single board, is shown.
+    pub fn is_grid(&self) -> bool {
+        self.open.is_none()
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) {
+        if self.open.is_some() {
+            if let KeyCode::Esc | KeyCode::Enter | KeyCode::Backspace = code {
+                self.open = None;
+            }
+            return;
+        }
+        let last = self.boards.len().saturating_sub(1);
+        let columns = self.columns.max(1);
+        self.selected = match code {
+            KeyCode::Left | KeyCode::Char('h') => self.selected.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => (self.selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => self.selected.saturating_sub(columns),
+            KeyCode::Down | KeyCode::Char('j') if self.selected + columns <= last => {
+                self.selected + columns
+            }
+            // Boards are numbered from 1 on the grid
+            KeyCode::Char(c @ '1'..='9') => {
+                let index = c as usize - '1' as usize;
+                if index <= last {
+                    self.open = Some(index);
+                }
+                index.min(last)
+            }
+            KeyCode::Enter | KeyCode::Char(' ') if !self.boards.is_empty() => {
+                self.open = Some(self.selected);
+                self.selected
+            }
+            _ => self.selected,
+        };
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        if let Some(board) = self.open.and_then(|i| self.boards.get(i)) {
+            ClockWidget::new(&board.clock).render(area, buf);
+            let [top] = Layout::vertical([Constraint::Length(1)]).areas(area);
+            Line::from(format!(" {} ", board.label).fg(Color::LightGreen))
+                .centered()
+                .render(top, buf);
+            return;
+        }
+
+        self.columns = (area.width / CELL_WIDTH).max(1) as usize;
+        let rows = area.height / CELL_HEIGHT;
+        let title =
+            Line::from(format!(" {} ", tr("Boards, Enter to open one")).fg(Color::LightGreen))
+                .centered();
+        let [title_area, grid] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        title.render(title_area, buf);
+        for (index, board) in self.boards.iter().enumerate() {
+            let (row, column) = (index / self.columns, index % self.columns);
+            if row as u16 >= rows {
+                break;
+            }
+            let cell = Rect {
+                x: grid.x + column as u16 * CELL_WIDTH,
+                y: grid.y + row as u16 * CELL_HEIGHT,
+                width: CELL_WIDTH,
+                height: CELL_HEIGHT,
+            }
+            .intersection(grid);
+            render_board(index, board, index == self.selected, cell, buf);
+        }
+    }
+}
+
+fn render_board(index: usize, board: &Board, selected: bool, area: Rect, buf: &mut Buffer) {
+    let border = if selected {
+        Style::default().fg(Color::LightGreen)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(border)
+        .title(format!(" {} {} ", index + 1, board.label));
+
+    let snapshot = board.clock.snapshot();
+    let time = |player: Player, ms: u64| {
+        let time = Time(Duration::from_millis(ms)).to_string();
+        let style = if snapshot.flagged == Some(player) {
+            Style::default().fg(Color::White).bg(Color::Red).bold()
+        } else if snapshot.running && snapshot.turn == Some(player) {
+            Style::default().fg(Color::LightGreen).bold()
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        Span::styled(format!(" {time} "), style)
+    };
+    let times = Line::from(vec![
+        time(Player::Player1, snapshot.player1_ms),
+        "│".dark_gray(),
+        time(Player::Player2, snapshot.player2_ms),
+    ]);
+    let info = match (&board.problem, snapshot.result()) {
+        (Some(problem), _) => Line::from(problem.clone().yellow()),
+        (None, Some(result)) => {
+            Line::from(trf("{}, move {}", &[&result, &snapshot.move_number]).red())
+        }
+        (None, None) => Line::from(trf("move {}", &[&snapshot.move_number]).dark_gray()),
+    };
+    Paragraph::new(vec![times, info])
+        .centered()
+        .block(block)
+        .render(area, buf);
+}
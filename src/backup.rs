@@ -0,0 +1,44 @@
+use std::{fs, path::Path};
+
+use crate::{config::Config, history, presets};
+
+/// Files bundled into a backup archive, by their name inside the archive.
+fn files() -> Vec<(&'static str, std::path::PathBuf)> {
+    vec![
+        ("history.jsonl", history::path()),
+        ("config.toml", Config::path()),
+        ("presets.toml", presets::path()),
+    ]
+}
+
+/// Writes the history, config and presets into a single tar archive.
+pub fn create(archive: &Path) -> anyhow::Result<()> {
+    let mut builder = tar::Builder::new(fs::File::create(archive)?);
+    for (name, path) in files() {
+        if path.exists() {
+            builder.append_path_with_name(&path, name)?;
+            println!("added {}", path.display());
+        }
+    }
+    builder.finish()?;
+    Ok(())
+}
+
+/// Restores files from an archive made by [`create`], overwriting the current ones.
+pub fn restore(archive: &Path) -> anyhow::Result<()> {
+    let files = files();
+    let mut archive = tar::Archive::new(fs::File::open(archive)?);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let Some((_, path)) = files.iter().find(|(file, _)| *file == name) else {
+            anyhow::bail!("unexpected file in backup: {name}");
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        entry.unpack(path)?;
+        println!("restored {}", path.display());
+    }
+    Ok(())
+}
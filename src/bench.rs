@@ -0,0 +1,57 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+use crate::{
+    clock::{Clock, ClockWidget},
+    tabs::TimeCtrl,
+};
+
+/// Terminal sizes rendered, from a split laptop screen to a wall display.
+const SIZES: [(u16, u16); 4] = [(80, 24), (120, 40), (200, 60), (400, 120)];
+
+/// The digits drawn by the clock, each with its own glyphs.
+const STYLES: [(&str, Duration, bool); 4] = [
+    ("minutes", Duration::from_secs(154), true),
+    ("tenths", Duration::from_millis(9_870), true),
+    ("seconds", Duration::from_millis(9_870), false),
+    ("hours", Duration::from_secs(5_400), true),
+];
+
+/// Renders the clocks `frames` times into an in-memory buffer for every terminal size and
+/// style of digits, and prints the time a frame took.
+pub fn run(frames: u32) {
+    println!(
+        "{:<9} {:<8} {:>10} {:>8}",
+        "size", "style", "per frame", "fps"
+    );
+    for (width, height) in SIZES {
+        let area = Rect::new(0, 0, width, height);
+        for (style, time, tenths) in STYLES {
+            let mut clock = Clock::default();
+            clock.set(TimeCtrl::default());
+            clock.set_time(time);
+            clock.hit();
+
+            let mut buf = Buffer::empty(area);
+            let start = Instant::now();
+            for _ in 0..frames {
+                buf.reset();
+                ClockWidget::new(&clock)
+                    .tenths(tenths)
+                    .render(area, &mut buf);
+                black_box(&buf);
+            }
+            let per_frame = start.elapsed() / frames;
+            println!(
+                "{:<9} {style:<8} {:>10} {:>8.0}",
+                format!("{width}x{height}"),
+                format!("{:.1?}", per_frame),
+                1.0 / per_frame.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Paragraph, Widget},
+};
+use shakmaty::{
+    Chess, File, Move, Position, Rank, Role, Square,
+    san::{San, SanPlus},
+    uci::UciMove,
+};
+
+use crate::{
+    clock::{Outcome, Player},
+    i18n::trf,
+};
+
+/// Width of the panel, two columns per square plus the rank numbers and borders.
+pub const WIDTH: u16 = 20;
+
+/// The position of the game, from the moves typed by a scorer or played on a connected board.
+#[derive(Debug, Clone, Default)]
+pub struct BoardPanel {
+    position: Chess,
+    moves: Vec<Move>,
+}
+
+impl BoardPanel {
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = BoardPanel::default();
+    }
+
+    /// Plays the move in SAN, returning it as written by the board, e.g. with its check mark.
+    pub fn play_san(&mut self, san: &str) -> Result<String, String> {
+        let m = San::from_ascii(san.as_bytes())
+            .ok()
+            .and_then(|san| san.to_move(&self.position).ok())
+            .ok_or_else(|| trf("{} isn't legal here", &[&san]))?;
+        Ok(self.play(m))
+    }
+
+    /// Plays the move in UCI notation, returning it in SAN.
+    pub fn play_uci(&mut self, uci: &str) -> Result<String, String> {
+        let m = uci
+            .parse::<UciMove>()
+            .ok()
+            .and_then(|uci| uci.to_move(&self.position).ok())
+            .ok_or_else(|| trf("{} isn't legal here", &[&uci]))?;
+        Ok(self.play(m))
+    }
+
+    fn play(&mut self, m: Move) -> String {
+        self.moves.push(m);
+        SanPlus::from_move_and_play_unchecked(&mut self.position, m).to_string()
+    }
+
+    /// How the game ended on the board, `white` being the player of the white pieces.
+    pub fn outcome(&self, white: Player) -> Option<Outcome> {
+        if self.position.is_checkmate() {
+            let winner = match self.position.turn() {
+                shakmaty::Color::White => white.opponent(),
+                shakmaty::Color::Black => white,
+            };
+            Some(Outcome::Checkmate { winner })
+        } else if self.position.is_stalemate() {
+            Some(Outcome::Stalemate)
+        } else {
+            None
+        }
+    }
+
+    /// Takes the last move back.
+    pub fn undo(&mut self) {
+        self.moves.pop();
+        self.position = Chess::default();
+        for m in &self.moves {
+            self.position.play_unchecked(*m);
+        }
+    }
+}
+
+impl Widget for &BoardPanel {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let last = self.moves.last().map(|m| (m.from(), m.to()));
+        let board = self.position.board();
+        let mut lines = vec![];
+        for rank in Rank::ALL.into_iter().rev() {
+            let mut spans = vec![Span::styled(format!("{rank}"), Color::DarkGray)];
+            for file in File::ALL {
+                let square = Square::from_coords(file, rank);
+                let bg = match last {
+                    Some((from, to)) if Some(square) == from || square == to => {
+                        Color::from_u32(0x00a9a238)
+                    }
+                    _ if square.is_light() => Color::from_u32(0x00b58863),
+                    _ => Color::from_u32(0x00805030),
+                };
+                let (glyph, fg) = match board.piece_at(square) {
+                    Some(piece) => (
+                        figurine(piece.role),
+                        match piece.color {
+                            shakmaty::Color::White => Color::White,
+                            shakmaty::Color::Black => Color::Black,
+                        },
+                    ),
+                    None => (' ', Color::Reset),
+                };
+                spans.push(Span::styled(
+                    format!("{glyph} "),
+                    Style::default().fg(fg).bg(bg),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::styled(" a b c d e f g h", Color::DarkGray));
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::DarkGray);
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+/// The solid Unicode figurine of a piece, coloured when drawn.
+fn figurine(role: Role) -> char {
+    match role {
+        Role::King => '♚',
+        Role::Queen => '♛',
+        Role::Rook => '♜',
+        Role::Bishop => '♝',
+        Role::Knight => '♞',
+        Role::Pawn => '♟',
+    }
+}
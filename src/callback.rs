@@ -0,0 +1,239 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    clock::{ClockEvent, Snapshot},
+    history::GameMeta,
+    paths,
+    remote::Remote,
+};
+
+/// Wait before retrying a failed delivery, doubled after every failure up to
+/// [`MAX_RETRY_INTERVAL`].
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(300);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An endpoint receiving clock events, configured in `config.toml`:
+///
+/// ```toml
+/// [[callbacks]]
+/// url = "https://tournament.example/api/clock"
+/// events = ["game_start", "result"]
+/// token = "secret"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallbackConfig {
+    pub url: String,
+    /// Events posted to the endpoint, `game_start` and `result` by default.
+    #[serde(default = "default_events")]
+    pub events: Vec<String>,
+    /// Sent as a bearer token.
+    pub token: Option<String>,
+}
+
+fn default_events() -> Vec<String> {
+    vec!["game_start".to_string(), "result".to_string()]
+}
+
+/// A request waiting to be delivered. The token of its endpoint is only looked up in the config
+/// when it's sent, to keep it out of the queue file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Delivery {
+    url: String,
+    body: serde_json::Value,
+}
+
+/// How an attempt to deliver a request went.
+enum Sent {
+    Delivered,
+    /// The endpoint refused the request, sending it again wouldn't change that.
+    Rejected(u16),
+    Failed,
+}
+
+/// Undelivered requests, kept across restarts.
+fn queue_path() -> PathBuf {
+    paths::data_dir().join("callbacks.jsonl")
+}
+
+/// Requests the endpoint rejected, with the status of their answer.
+fn rejected_path() -> PathBuf {
+    paths::data_dir().join("callbacks-rejected.jsonl")
+}
+
+/// Posts the configured events to their endpoints as JSON, e.g.
+/// `{"event":"result","result":"1-0","state":{..},"meta":{"board":"3"}}`.
+///
+/// Deliveries are queued and sent in order. One that failed on the way or on a server error is
+/// retried until the endpoint accepts it, one the endpoint rejected with a client error is set
+/// aside in [`rejected_path`]. The queue is saved in the data directory, so requests left when
+/// the clock is closed are sent the next time it runs.
+pub fn spawn(callbacks: &[CallbackConfig], meta: &GameMeta, remote: &Remote) {
+    let (sender, deliveries) = mpsc::channel();
+    let pending = load_queue();
+    if callbacks.is_empty() && pending.is_empty() {
+        return;
+    }
+    let endpoints = callbacks.to_vec();
+    thread::spawn(move || deliver(pending, &deliveries, &endpoints));
+
+    let callbacks = callbacks.to_vec();
+    let meta = meta.clone();
+    let state = remote.state.clone();
+    let mut events = remote.clock_events.subscribe();
+    tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            };
+            let name = name(&event);
+            let body = body(name, &state.borrow(), &meta);
+            for callback in callbacks.iter().filter(|c| c.events.iter().any(|e| e == name)) {
+                let delivery = Delivery {
+                    url: callback.url.clone(),
+                    body: body.clone(),
+                };
+                if sender.send(delivery).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Name of the callback event for a clock event.
+fn name(event: &ClockEvent) -> &'static str {
+    match event {
+        ClockEvent::NewGame { .. } => "new_game",
+        ClockEvent::Start { .. } => "game_start",
+        ClockEvent::Hit { .. } => "hit",
+        ClockEvent::Pause => "pause",
+        ClockEvent::Resume => "resume",
+        ClockEvent::Flag { .. } | ClockEvent::End { .. } => "result",
+    }
+}
+
+fn body(name: &str, snapshot: &Snapshot, meta: &GameMeta) -> serde_json::Value {
+    let mut body = json!({ "event": name, "state": snapshot, "meta": meta });
+    if let Some(result) = snapshot.result() {
+        body["result"] = result.into();
+    }
+    body
+}
+
+/// Sends the queued deliveries one after the other, forever, with the tokens of `endpoints`.
+fn deliver(
+    mut queue: VecDeque<Delivery>,
+    deliveries: &mpsc::Receiver<Delivery>,
+    endpoints: &[CallbackConfig],
+) {
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .new_agent();
+    let mut retry = RETRY_INTERVAL;
+    loop {
+        let received = match queue.front() {
+            None => deliveries.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            Some(delivery) => match post(&agent, delivery, endpoints) {
+                Sent::Failed => {
+                    let received = deliveries.recv_timeout(retry);
+                    if let Err(RecvTimeoutError::Disconnected) = received {
+                        thread::sleep(retry);
+                    }
+                    retry = (retry * 2).min(MAX_RETRY_INTERVAL);
+                    received
+                }
+                sent => {
+                    if let Sent::Rejected(status) = sent {
+                        set_aside(delivery, status);
+                    }
+                    queue.pop_front();
+                    save_queue(&queue);
+                    retry = RETRY_INTERVAL;
+                    continue;
+                }
+            },
+        };
+        match received {
+            Ok(delivery) => {
+                queue.push_back(delivery);
+                save_queue(&queue);
+            }
+            // Keep retrying what's left, the clock may be closing
+            Err(RecvTimeoutError::Disconnected) if queue.is_empty() => return,
+            Err(_) => {}
+        }
+    }
+}
+
+fn post(agent: &ureq::Agent, delivery: &Delivery, endpoints: &[CallbackConfig]) -> Sent {
+    let mut request = agent.post(&delivery.url);
+    let endpoint = endpoints.iter().find(|e| e.url == delivery.url);
+    if let Some(token) = endpoint.and_then(|e| e.token.as_ref()) {
+        request = request.header("Authorization", format!("Bearer {token}"));
+    }
+    match request.send_json(&delivery.body) {
+        Ok(_) => Sent::Delivered,
+        // Timeouts and rate limits pass, like server errors
+        Err(ureq::Error::StatusCode(status @ 400..=499)) if !matches!(status, 408 | 429) => {
+            Sent::Rejected(status)
+        }
+        Err(_) => Sent::Failed,
+    }
+}
+
+/// Keeps a request the endpoint rejected for a look by hand, it's dropped if that fails.
+fn set_aside(delivery: &Delivery, status: u16) {
+    let Ok(mut line) = serde_json::to_value(delivery) else {
+        return;
+    };
+    line["status"] = status.into();
+    let _ = fs::create_dir_all(paths::data_dir());
+    let _ = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(rejected_path())
+        .and_then(|mut file| writeln!(file, "{line}"));
+}
+
+fn load_queue() -> VecDeque<Delivery> {
+    let content = fs::read_to_string(queue_path()).unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Saves the queue, on failure the deliveries are only kept in memory.
+fn save_queue(queue: &VecDeque<Delivery>) {
+    let path = queue_path();
+    if queue.is_empty() {
+        let _ = fs::remove_file(path);
+        return;
+    }
+    let mut content = String::new();
+    for delivery in queue {
+        if let Ok(line) = serde_json::to_string(delivery) {
+            content += &line;
+            content.push('\n');
+        }
+    }
+    let _ = fs::create_dir_all(paths::data_dir());
+    let tmp = path.with_extension("tmp");
+    let _ = fs::write(&tmp, content).and_then(|_| fs::rename(tmp, path));
+}
@@ -0,0 +1,236 @@
+use std::{io, path::PathBuf, time::Duration};
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::{
+    backup, bench,
+    clock::Player,
+    command,
+    config::Config,
+    control,
+    custom,
+    date,
+    discovery,
+    history::{self, GameMeta, Retention},
+    net,
+    remote::RemoteArgs,
+    statusbar,
+    tabs::{CtrlOption, TimeCtrl},
+};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Simple terminal chess clock")]
+pub struct Cli {
+    /// Start a game right away, e.g. "5+3" for 5 minutes and 3 seconds of increment
+    #[arg(value_name = "MINUTES+INCREMENT", value_parser = custom::parse_ctrl)]
+    pub time: Option<CtrlOption>,
+    /// Start a game of this many minutes right away
+    #[arg(long, value_name = "N", conflicts_with = "time")]
+    pub minutes: Option<u64>,
+    /// Seconds added after each move of the game started with --minutes
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value = "0",
+        requires = "minutes"
+    )]
+    pub increment: u64,
+    #[command(flatten)]
+    pub meta: GameMeta,
+
+    /// Run without a TUI, reading commands (hit, pause, quit) from stdin
+    #[arg(long, requires = "output")]
+    pub headless: bool,
+    /// Emit state updates as newline-delimited JSON (headless mode)
+    #[arg(long, requires = "headless", group = "output")]
+    pub json: bool,
+    /// Emit state updates as UCI `go wtime .. btime .. winc .. binc ..` lines (headless mode)
+    #[arg(long, requires = "headless", group = "output")]
+    pub uci: bool,
+    /// Time control for headless mode, e.g. "3+2"
+    #[arg(long, default_value = "3+2", value_parser = TimeCtrl::parse)]
+    pub control: TimeCtrl,
+    /// Minimum interval between state updates in milliseconds
+    #[arg(long, value_name = "MS", default_value = "100", value_parser = parse_millis)]
+    pub interval: Duration,
+    #[command(flatten)]
+    pub remote: RemoteArgs,
+    /// Play against this UCI engine, e.g. stockfish, entering your moves like e2e4
+    #[arg(long, value_name = "COMMAND")]
+    pub engine: Option<String>,
+    /// Clock played by the engine
+    #[arg(
+        long,
+        value_name = "1|2",
+        default_value = "2",
+        value_parser = command::parse_player,
+        requires = "engine"
+    )]
+    pub engine_plays: Player,
+    /// Play a match of N games, changing colors every game
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub best_of: Option<u32>,
+    /// Run the on_hit, on_tick and on_flag hooks of this Lua script
+    #[cfg(feature = "lua")]
+    #[arg(long, value_name = "PATH")]
+    pub script: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List finished games
+    History {
+        /// Only show games matching these fields
+        #[command(flatten)]
+        filter: GameMeta,
+        /// Print games as PGN instead of a list
+        #[arg(long)]
+        pgn: bool,
+        /// List the events of each game, e.g. draw offers
+        #[arg(long, conflicts_with = "pgn")]
+        log: bool,
+    },
+    /// Delete old games from the history
+    ///
+    /// Without options the retention policy from the config file is applied.
+    Prune {
+        /// Keep only the last N games
+        #[arg(long, value_name = "N")]
+        keep: Option<usize>,
+        /// Delete games finished before this date
+        #[arg(long, value_name = "YYYY-MM-DD", value_parser = parse_date)]
+        before: Option<u64>,
+    },
+    /// Bundle the history, config and presets into one archive
+    Backup { archive: PathBuf },
+    /// Restore the history, config and presets from a backup archive
+    Restore { archive: PathBuf },
+    /// Host a game over the network, the opponent joins with `chessclock join`
+    Host {
+        #[arg(long, default_value_t = net::DEFAULT_PORT)]
+        port: u16,
+        /// Name shown to players looking for games on the local network
+        #[arg(long, default_value_t = discovery::default_name())]
+        name: String,
+    },
+    /// Join a game hosted on another machine
+    Join {
+        /// Address of the host, e.g. 192.168.1.20 or 192.168.1.20:7878. Without it, pick one
+        /// of the games found on the local network
+        addr: Option<String>,
+    },
+    /// Follow a game without being able to change the clock, e.g. on a wall display
+    Spectate {
+        /// Address of the host, e.g. 192.168.1.20, or the path of a control socket
+        addr: String,
+    },
+    /// Watch the clocks of several boards at once
+    Arbiter {
+        /// Addresses of the hosts or paths of the control sockets of the boards
+        #[arg(required = true)]
+        addrs: Vec<String>,
+    },
+    /// Print the times of a clock running with `--socket`
+    Status {
+        /// Control socket of the clock
+        #[arg(long, value_name = "PATH", default_value_os_t = control::default_path())]
+        socket: PathBuf,
+        /// Print a colored snippet for tmux's `status-right`
+        #[arg(long)]
+        tmux: bool,
+    },
+    /// Print the completion script for a shell, e.g. `chessclock completions bash`
+    Completions { shell: Shell },
+    /// Print the man page
+    Man,
+    /// Time the rendering of the clocks across terminal sizes and styles of digits
+    Bench {
+        /// Frames rendered for each size and style
+        #[arg(long, default_value = "5000", value_parser = clap::value_parser!(u32).range(1..))]
+        frames: u32,
+    },
+    /// Mirror the clock of a live Lichess game
+    #[cfg(feature = "lichess")]
+    Lichess {
+        /// ID of the game, the part of the URL after lichess.org/
+        #[arg(required_unless_present = "user")]
+        game: Option<String>,
+        /// Follow the games of this user instead
+        #[arg(long, conflicts_with = "game")]
+        user: Option<String>,
+    },
+}
+
+impl Cli {
+    /// The time control to start a game with right away, given as `5+3` or with `--minutes`.
+    pub fn start(&self) -> anyhow::Result<Option<CtrlOption>> {
+        match (self.time, self.minutes) {
+            (Some(ctrl), _) => Ok(Some(ctrl)),
+            (None, Some(minutes)) => custom::ctrl(minutes, self.increment)
+                .map(Some)
+                .map_err(anyhow::Error::msg),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+fn parse_millis(s: &str) -> Result<Duration, String> {
+    s.parse().map(Duration::from_millis).map_err(|e| format!("{e}"))
+}
+
+fn parse_date(s: &str) -> Result<u64, String> {
+    date::parse(s).ok_or_else(|| format!("invalid date '{s}', expected YYYY-MM-DD"))
+}
+
+impl Command {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self {
+            Command::History { filter, pgn, log } => history::print(&filter, pgn, log),
+            Command::Prune { keep, before } => {
+                let retention = if keep.is_none() && before.is_none() {
+                    Retention::from(&Config::load()?.history)
+                } else {
+                    Retention {
+                        keep_last: keep,
+                        before,
+                    }
+                };
+                let removed = history::prune(retention)?;
+                println!("removed {removed} game(s)");
+                Ok(())
+            }
+            Command::Backup { archive } => backup::create(&archive),
+            Command::Restore { archive } => backup::restore(&archive),
+            Command::Status { socket, tmux } => {
+                let format = if tmux {
+                    statusbar::Format::Tmux
+                } else {
+                    statusbar::Format::Plain
+                };
+                println!("{}", statusbar::render(&control::query(&socket)?, format));
+                Ok(())
+            }
+            Command::Completions { shell } => {
+                clap_complete::generate(shell, &mut Cli::command(), "chessclock", &mut io::stdout());
+                Ok(())
+            }
+            Command::Man => Ok(clap_mangen::Man::new(Cli::command()).render(&mut io::stdout())?),
+            Command::Bench { frames } => {
+                bench::run(frames);
+                Ok(())
+            }
+            Command::Host { .. }
+            | Command::Join { .. }
+            | Command::Spectate { .. }
+            | Command::Arbiter { .. } => {
+                unreachable!("network games run in the TUI")
+            }
+            #[cfg(feature = "lichess")]
+            Command::Lichess { .. } => unreachable!("mirrored games run in the TUI"),
+        }
+    }
+}
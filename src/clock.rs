@@ -11,259 +11,402 @@ use ratatui::{
     widgets::{Block, Paragraph, Widget},
 };
 
-use crate::{event::TIMER_TICK, tabs::TimeCtrl};
-
-macro_rules! font {
-    ($d:expr) => {
-        match $d {
-            '0' => include_str!("font/0.txt"),
-            '1' => include_str!("font/1.txt"),
-            '2' => include_str!("font/2.txt"),
-            '3' => include_str!("font/3.txt"),
-            '4' => include_str!("font/4.txt"),
-            '5' => include_str!("font/5.txt"),
-            '6' => include_str!("font/6.txt"),
-            '7' => include_str!("font/7.txt"),
-            '8' => include_str!("font/8.txt"),
-            '9' => include_str!("font/9.txt"),
-            '.' => include_str!("font/dot.txt"),
-            ':' => include_str!("font/colon.txt"),
-            _ => unreachable!(),
-        }
-    };
-}
+use serde::Deserialize;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Time(pub Duration);
+pub use chessclock_core::clock::*;
 
-impl Time {
-    fn with_font(&self) -> String {
-        let mut split_vec: Vec<Vec<&str>> = vec![];
-        for d in self.to_string().chars() {
-            split_vec.push(font!(d).split('\n').collect());
-        }
+use crate::{
+    font::{Font, FontName},
+    i18n::{self, tr, trf},
+    tabs,
+};
+
+/// Big digits turned by 180°, for the player sitting across a clock lying flat.
+fn upside_down(digits: &str) -> String {
+    let lines: Vec<String> = digits
+        .lines()
+        .rev()
+        .map(|line| {
+            let turned = line.chars().rev().map(|c| match c {
+                '▀' => '▄',
+                '▄' => '▀',
+                '▌' => '▐',
+                '▐' => '▌',
+                '▘' => '▗',
+                '▗' => '▘',
+                '▝' => '▖',
+                '▖' => '▝',
+                '▙' => '▜',
+                '▜' => '▙',
+                '▛' => '▟',
+                '▟' => '▛',
+                '╭' => '╯',
+                '╯' => '╭',
+                '╮' => '╰',
+                '╰' => '╮',
+                '├' => '┤',
+                '┤' => '├',
+                '┬' => '┴',
+                '┴' => '┬',
+                '┐' => '└',
+                '└' => '┐',
+                '┌' => '┘',
+                '┘' => '┌',
+                '╷' => '╵',
+                '╵' => '╷',
+                '╶' => '╴',
+                '╴' => '╶',
+                '_' => '‾',
+                '‾' => '_',
+                c => c,
+            });
+            turned.collect()
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Colours of the clocks and of the time control picker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// The clock of the player to move.
+    pub active: Color,
+    /// The clock waiting, and both clocks before the game.
+    pub inactive: Color,
+    /// The clock of the player to move in the last seconds.
+    pub burning: Color,
+    /// Texts and borders, such as the time control or the time out below the clocks.
+    pub accent: Color,
+    /// The time controls of the picker that aren't selected.
+    pub muted: Color,
+    /// Banners over the clocks, such as the pause.
+    pub highlight: Color,
+}
 
-        let letter_height = split_vec[0].len();
-        let lines: Vec<_> = (0..letter_height)
-            .map(|i| {
-                split_vec
-                    .iter()
-                    .map(move |s| s[i])
-                    .collect::<Vec<_>>()
-                    .join(" ")
-            })
-            .collect();
-        lines.join("\n")
+impl Default for Theme {
+    fn default() -> Self {
+        ThemeName::default().theme()
     }
 }
 
-impl Display for Time {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let total_s = self.0.as_secs();
-        let hh = total_s / 3_600;
-        let mm = (total_s % 3_600) / 60;
-        let ss = total_s % 60;
-        let ms = (self.0.as_millis() % 1000) / 100;
-
-        if hh > 0 {
-            write!(f, "{:02}:{:02}:{:02}", hh, mm, ss)
-        } else if mm == 0 && ss <= 20 {
-            write!(f, "{:02}:{:02}.{}", mm, ss, ms)
-        } else {
-            write!(f, "{:02}:{:02}", mm, ss)
+/// The built-in themes, picked by name in the config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    Solarized,
+    Dracula,
+    /// Shades of gray, for e-ink and monochrome displays.
+    Monochrome,
+}
+
+impl ThemeName {
+    pub fn theme(self) -> Theme {
+        match self {
+            ThemeName::Default => Theme {
+                active: Color::LightGreen,
+                inactive: Color::from_u32(0x003f3f3f),
+                burning: Color::LightRed,
+                accent: Color::LightGreen,
+                muted: Color::from_u32(0x007f00),
+                highlight: Color::LightYellow,
+            },
+            ThemeName::Solarized => Theme {
+                active: Color::from_u32(0x859900),
+                inactive: Color::from_u32(0x586e75),
+                burning: Color::from_u32(0xdc322f),
+                accent: Color::from_u32(0x2aa198),
+                muted: Color::from_u32(0x586e75),
+                highlight: Color::from_u32(0xb58900),
+            },
+            ThemeName::Dracula => Theme {
+                active: Color::from_u32(0x50fa7b),
+                inactive: Color::from_u32(0x6272a4),
+                burning: Color::from_u32(0xff5555),
+                accent: Color::from_u32(0xbd93f9),
+                muted: Color::from_u32(0x6272a4),
+                highlight: Color::from_u32(0xf1fa8c),
+            },
+            ThemeName::Monochrome => Theme {
+                active: Color::Gray,
+                inactive: Color::DarkGray,
+                burning: Color::White,
+                accent: Color::White,
+                muted: Color::DarkGray,
+                highlight: Color::White,
+            },
         }
     }
 }
 
-#[derive(Default, Debug, Clone, Copy)]
-pub enum Player {
+/// How the two clocks are laid out, picked in the config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClockLayout {
+    /// Stacked in terminals taller than wide, side by side otherwise.
     #[default]
-    Player1,
-    Player2,
+    Auto,
+    Horizontal,
+    /// Player 2 at the top and Player 1 at the bottom, like the clock apps of phones.
+    Vertical,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum ClockState {
-    NotStarted,
-    Pause,
-    Player(Player),
+impl ClockLayout {
+    /// Whether the clocks are stacked in `area`. Cells are about twice as high as wide.
+    pub fn is_vertical(self, area: Rect) -> bool {
+        match self {
+            ClockLayout::Auto => area.height * 2 > area.width,
+            ClockLayout::Horizontal => false,
+            ClockLayout::Vertical => true,
+        }
+    }
 }
 
+/// The two clocks in big digits side by side, or stacked, with the time control or a hint below them.
+///
+/// ```no_run
+/// # use chessclock::clock::{Clock, ClockWidget};
+/// # fn draw(frame: &mut ratatui::Frame, clock: &Clock) {
+/// frame.render_widget(ClockWidget::new(clock), frame.area());
+/// # }
+/// ```
 #[derive(Debug, Clone, Copy)]
-pub struct Clock {
-    player1: Time,
-    player2: Time,
-    state: ClockState,
-    resume_player: Player, // player turn before pause
-    first_to_move: Player,
-    increment: Duration,
-    time_ctrl: TimeCtrl,
+pub struct ClockWidget<'a> {
+    clock: &'a Clock,
+    theme: Theme,
+    font: &'a Font,
+    layout: ClockLayout,
+    names: [Option<&'a str>; 2],
+    swapped: bool,
+    mirrored: bool,
+    tenths: bool,
+    subseconds: SubSeconds,
+    text: bool,
 }
 
-impl Clock {
-    pub fn burning(time: Duration) -> bool {
-        time < Duration::from_secs(21)
+impl<'a> ClockWidget<'a> {
+    pub fn new(clock: &'a Clock) -> Self {
+        Self {
+            clock,
+            theme: Theme::default(),
+            font: FontName::default().font(),
+            layout: ClockLayout::default(),
+            names: [None; 2],
+            swapped: false,
+            mirrored: false,
+            tenths: true,
+            subseconds: SubSeconds::default(),
+            text: false,
+        }
     }
 
-    pub fn set(&mut self, ctrl: TimeCtrl) {
-        self.time_ctrl = ctrl;
-        self.player1.0 = ctrl.to_duration().0;
-        self.player2.0 = ctrl.to_duration().0;
-        self.increment = ctrl.to_duration().1;
-        self.state = ClockState::NotStarted;
+    /// Whether the times are written as plain lines instead of big digits, for screen
+    /// readers.
+    pub fn text(mut self, text: bool) -> Self {
+        self.text = text;
+        self
     }
 
-    pub fn curr_player(&self) -> Option<Player> {
-        match self.state {
-            ClockState::Player(p) => Some(p),
-            _ => None,
-        }
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
     }
 
-    pub fn hit(&mut self) {
-        match self.state {
-            ClockState::NotStarted => self.state = ClockState::Player(self.first_to_move),
-            ClockState::Pause => (),
-            ClockState::Player(p) => match p {
-                Player::Player1 => {
-                    self.state = ClockState::Player(Player::Player2);
-                    self.player1.0 += self.increment;
-                }
-                Player::Player2 => {
-                    self.state = ClockState::Player(Player::Player1);
-                    self.player2.0 += self.increment;
-                }
-            },
-        }
+    pub fn font(mut self, font: &'a Font) -> Self {
+        self.font = font;
+        self
     }
 
-    pub fn tick_timer(&mut self) {
-        let millisec = Duration::from_millis(TIMER_TICK);
-        match self.state {
-            ClockState::NotStarted | ClockState::Pause => (),
-            ClockState::Player(p) => match p {
-                Player::Player1 => {
-                    self.player1.0 = self.player1.0.saturating_sub(millisec);
-                }
-                Player::Player2 => {
-                    self.player2.0 = self.player2.0.saturating_sub(millisec);
-                }
-            },
-        }
+    pub fn layout(mut self, layout: ClockLayout) -> Self {
+        self.layout = layout;
+        self
     }
 
-    pub fn is_time_out(&self) -> bool {
-        if self.player1.0 == Duration::ZERO || self.player2.0 == Duration::ZERO {
-            true
-        } else {
-            false
-        }
+    /// The players' names, shown above their clocks and instead of "Player 1" and "Player 2".
+    pub fn names(mut self, names: [Option<&'a str>; 2]) -> Self {
+        self.names = names;
+        self
     }
 
-    pub fn pause(&mut self, resume_player: Player) {
-        match self.state {
-            ClockState::Pause => self.state = ClockState::Player(self.resume_player),
-            ClockState::Player(_) => {
-                self.resume_player = resume_player;
-                self.state = ClockState::Pause;
-            }
-            ClockState::NotStarted => (),
-        }
+    /// Whether Player 1 is drawn on the right and Player 2 on the left, or at the top when
+    /// stacked, for a clock that sits the other way round.
+    pub fn swapped(mut self, swapped: bool) -> Self {
+        self.swapped = swapped;
+        self
     }
 
-    fn state_to_style_pure(
-        state: ClockState,
-        resume: Player,
-        time1: Duration,
-        time2: Duration,
-    ) -> [Style; 2] {
-        let active_style = Style::default().fg(Color::LightGreen);
-        let inactive_style = Style::default().fg(Color::from_u32(0x003f3f3f));
-        let burning_clock_style = Style::default().fg(Color::LightRed);
-        match state {
-            ClockState::Player(p) => match p {
-                Player::Player1 => [
-                    if Clock::burning(time1) {
-                        burning_clock_style
-                    } else {
-                        active_style
-                    },
-                    inactive_style,
-                ],
-                Player::Player2 => [
-                    inactive_style,
-                    if Clock::burning(time2) {
-                        burning_clock_style
-                    } else {
-                        active_style
-                    },
-                ],
-            },
-            ClockState::NotStarted => [inactive_style, inactive_style],
-            ClockState::Pause => {
-                Clock::state_to_style_pure(ClockState::Player(resume), resume, time1, time2)
-            }
-        }
+    /// Whether the digits of the player on the right, or at the top when stacked, are upside
+    /// down so both players can read their own clock across a laptop lying flat.
+    pub fn mirrored(mut self, mirrored: bool) -> Self {
+        self.mirrored = mirrored;
+        self
     }
 
-    pub fn flip_first_to_move(&mut self) {
-        self.first_to_move = match self.first_to_move {
-            Player::Player1 => Player::Player2,
-            Player::Player2 => Player::Player1,
-        }
+    /// The name of `player`, or which player they are when they have none.
+    fn name(&self, player: Player) -> String {
+        self.names[player.index()].map_or_else(|| i18n::player(player), str::to_string)
     }
-}
 
-impl Default for Clock {
-    fn default() -> Self {
-        Self {
-            increment: Duration::from_secs(1),
-            player1: Time(Duration::from_secs(1)),
-            player2: Time(Duration::from_secs(1)),
-            state: ClockState::NotStarted,
-            resume_player: Player::Player1,
-            time_ctrl: TimeCtrl::Tab1,
-            first_to_move: Player::default(),
-        }
+    /// Whether the fractions of seconds are shown, they are by default.
+    pub fn tenths(mut self, tenths: bool) -> Self {
+        self.tenths = tenths;
+        self
+    }
+
+    /// When the fractions of seconds are shown and how precisely, tenths in the last 20
+    /// seconds by default.
+    pub fn subseconds(mut self, subseconds: SubSeconds) -> Self {
+        self.subseconds = subseconds;
+        self
     }
 }
 
-impl Widget for Clock {
+impl Widget for ClockWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(vec![Percentage(50), Percentage(50)])
-            .split(area);
+        let clock = self.clock;
+        let [time1, time2] = clock.times();
+        // The row between stacked clocks
+        let (layout, between) = if self.layout.is_vertical(area) {
+            let [top, between, bottom] =
+                Layout::vertical([Fill(1), Length(1), Fill(1)]).areas(area);
+            ([bottom, top], Some(between))
+        } else {
+            (Layout::horizontal([Percentage(50); 2]).areas(area), None)
+        };
+        // Player 1 is on the left or at the bottom unless the sides are swapped
+        let (side1, side2) = if self.swapped {
+            (layout[1], layout[0])
+        } else {
+            (layout[0], layout[1])
+        };
 
         let l2 = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![Fill(3), Min(10), Fill(1)])
-            .split(layout[0]);
+            .split(side1);
         let l3 = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![Fill(3), Min(10), Fill(1)])
-            .split(layout[1]);
-
-        let bottom_text = if matches!(self.state, ClockState::NotStarted) {
-            " Hit <space> to start ".to_string()
-        } else if self.is_time_out() {
-            " Time out. Hit <enter> to continue ".to_string()
-        } else if matches!(self.state, ClockState::Pause) {
-            " Pause. Hit 'p' to resume ".to_string()
+            .split(side2);
+
+        let bottom_text = if matches!(clock.state(), ClockState::NotStarted) {
+            format!(" {} ", tr("Hit <space> to start"))
+        } else if let Some(outcome) = clock.outcome() {
+            let outcome = if clock.is_armageddon() && outcome.winner().is_none() {
+                let black = self.name(clock.black());
+                trf(
+                    "{}, {} wins on draw odds",
+                    &[&i18n::outcome(outcome), &black],
+                )
+            } else {
+                i18n::outcome(outcome)
+            };
+            format!(" {} ", trf("{}. Hit <enter> to continue", &[&outcome]))
+        } else if let Some(flagged) = clock.flagged().filter(|p| self.names[p.index()].is_some()) {
+            let lost = trf(
+                "{} lost on time. Hit <enter> to continue",
+                &[&self.name(flagged)],
+            );
+            format!(" {lost} ")
+        } else if clock.is_time_out() {
+            format!(" {} ", tr("Time out. Hit <enter> to continue"))
+        } else if matches!(clock.state(), ClockState::Pause) {
+            format!(" {} ", tr("Pause. Hit 'p' to resume"))
         } else {
-            self.time_ctrl.to_string()
+            let mut label = if clock.stages().is_empty() {
+                clock.time_ctrl().label()
+            } else {
+                tabs::stages_label(clock.time_ctrl().0[0], clock.stages())
+            };
+            if clock.increment_kind() != IncrementKind::Fischer {
+                label = format!("{label} {}", clock.increment_kind());
+            }
+            if clock.overtime() != Overtime::None {
+                label = format!("{label} {}", clock.overtime());
+            }
+            if clock.is_hourglass() {
+                label = format!("{label} {}", tr("hourglass"));
+            }
+            label
         };
-        let instructions = Line::from(bottom_text.fg(Color::LightGreen).bold());
+        let instructions = Line::from(bottom_text.fg(self.theme.accent).bold());
         let block = Block::default().title_bottom(instructions.centered());
 
-        if matches!(self.state, ClockState::NotStarted) {
-            let [left, right] =
-                Layout::horizontal([Percentage(50), Percentage(50)]).areas(*buf.area());
-            let [_, left, _] = Layout::vertical([Fill(1), Length(1), Percentage(30)]).areas(left);
-            let [_, right, _] = Layout::vertical([Fill(1), Length(1), Percentage(30)]).areas(right);
-            let mark = Line::from(" first to move ".fg(Color::Reset).bold()).centered();
+        let styles = state_to_style_pure(
+            clock.state(),
+            clock.turn().unwrap_or_default(),
+            time1.0,
+            time2.0,
+            self.theme,
+        );
+        let digits = |time: Time, player: Player| {
+            let digits = if self.tenths {
+                self.font.text(&time.with_subseconds(self.subseconds))
+            } else {
+                self.font.text(&time.in_seconds())
+            };
+            // Player 2 sits across unless the sides are swapped
+            if self.mirrored && (player == Player::Player2) != self.swapped {
+                upside_down(&digits)
+            } else {
+                digits
+            }
+        };
+        let height = self.font.height() as u16;
+        let digits = [
+            digits(time1, Player::Player1),
+            digits(time2, Player::Player2),
+        ];
+        // Digits cut by a small terminal would read as other numbers. They leave room for the
+        // text at the bottom and for the move number between clocks side by side.
+        let margin = if between.is_some() { 0 } else { 10 };
+        let fits = digits.iter().zip([l2[1], l3[1]]).all(|(digits, area)| {
+            let width = digits.lines().map(|line| line.chars().count()).max();
+            digits.lines().count() < area.height as usize
+                && width.unwrap_or(0) + margin <= area.width as usize
+        });
+
+        if self.text || !fits {
+            let to_move = clock.curr_player().unwrap_or(clock.first_to_move());
+            let lines: Vec<_> = [(Player::Player1, time1), (Player::Player2, time2)]
+                .into_iter()
+                .map(|(player, time)| {
+                    let mut time = match overtime_status(clock, player) {
+                        Some(status) => format!("{} ({status})", time.in_seconds()),
+                        None => time.in_seconds(),
+                    };
+                    if clock.is_armageddon() && player == clock.black() {
+                        time = format!("{time}, {}", tr("draw odds"));
+                    }
+                    let args: [&dyn Display; 2] = [&self.name(player), &time];
+                    Line::from(if player == to_move && !clock.is_over() {
+                        trf("{}: {}, to move", &args)
+                    } else {
+                        trf("{}: {}", &args)
+                    })
+                })
+                .collect();
+            // Above the text at the bottom
+            let hint = Length(u16::from(!self.text));
+            let [_, text, hint, _, _] =
+                Layout::vertical([Fill(1), Length(2), hint, Fill(1), hint]).areas(area);
+            Paragraph::new(lines).centered().render(text, buf);
+            Line::from(tr("terminal too small").dark_gray())
+                .centered()
+                .render(hint, buf);
+            block.render(area, buf);
+            return;
+        }
+
+        if matches!(clock.state(), ClockState::NotStarted) {
+            // Under the digits and the overtime left
+            let [_, left] = Layout::vertical([Length(height + 1), Length(1)]).areas(l2[1]);
+            let [_, right] = Layout::vertical([Length(height + 1), Length(1)]).areas(l3[1]);
+            let mark = Line::from(format!(" {} ", tr("first to move")).fg(Color::Reset).bold())
+                .centered();
             mark.render(
-                match self.first_to_move {
+                match clock.first_to_move() {
                     Player::Player1 => left,
                     Player::Player2 => right,
                 },
@@ -271,16 +414,164 @@ impl Widget for Clock {
             );
         }
 
-        let styles = Clock::state_to_style_pure(
-            self.state,
-            self.resume_player,
-            self.player1.0,
-            self.player2.0,
-        );
-        let p1 = Text::styled(self.player1.with_font(), styles[0]);
-        let p2 = Text::styled(self.player2.with_font(), styles[1]);
-        Paragraph::new(p1).centered().render(l2[1], buf);
-        Paragraph::new(p2).centered().render(l3[1], buf);
+        for (player, area) in [(Player::Player1, l2[0]), (Player::Player2, l3[0])] {
+            if let Some(name) = self.names[player.index()] {
+                // Above the draw odds banner
+                let [_, row, _] = Layout::vertical([Fill(1), Length(1), Length(1)]).areas(area);
+                Line::from(name.bold()).centered().render(row, buf);
+            }
+        }
+        if clock.is_armageddon() {
+            // Right above Black's digits, for the whole game
+            let area = match clock.black() {
+                Player::Player1 => l2[0],
+                Player::Player2 => l3[0],
+            };
+            let [_, banner] = Layout::vertical([Fill(1), Length(1)]).areas(area);
+            let banner_text = format!(" {} ", tr("draw odds"));
+            let line = Line::from(banner_text.fg(self.theme.highlight).bold());
+            line.centered().render(banner, buf);
+        }
+
+        let [p1, p2] = digits;
+        Paragraph::new(Text::styled(p1, styles[0]))
+            .centered()
+            .render(l2[1], buf);
+        Paragraph::new(Text::styled(p2, styles[1]))
+            .centered()
+            .render(l3[1], buf);
+        // The delay counts down under the big digits, before the time does
+        if let (Some(left), Some(player)) = (clock.delay_left(), clock.turn()) {
+            let area = match player {
+                Player::Player1 => l2[1],
+                Player::Player2 => l3[1],
+            };
+            let [_, area] = Layout::vertical([Length(height + 1), Length(1)]).areas(area);
+            let secs = if self.tenths {
+                format!("{:.1}", left.as_secs_f64())
+            } else {
+                left.as_secs_f64().ceil().to_string()
+            };
+            let line = Line::from(trf("delay {}s", &[&secs]).fg(self.theme.accent));
+            line.centered().render(area, buf);
+        }
+        // The rows of the digits across both clocks side by side
+        let across = |row: u16| {
+            let [_, row] = Layout::vertical([Length(row), Length(1)]).areas(l2[1]);
+            Rect {
+                x: area.x,
+                width: area.width,
+                ..row
+            }
+        };
+        let paused = matches!(clock.state(), ClockState::Pause);
+        if let (true, Some(player)) = (clock.is_hourglass(), clock.curr_player()) {
+            // Sand flows from the running clock into the other one, moving like the tenths
+            let flow = if self.tenths {
+                let step = (clock.move_elapsed().as_millis() / 250 % 3) as usize;
+                ["•  ", " • ", "  •"][step]
+            } else {
+                "•••"
+            };
+            let flow = match between {
+                // Down from the clock at the top, beside the move number
+                Some(_) if (player == Player::Player2) != self.swapped => format!("{flow}↓"),
+                Some(_) => format!("{flow}↑"),
+                None if (player == Player::Player1) != self.swapped => format!("{flow}›"),
+                None => format!("‹{}", flow.chars().rev().collect::<String>()),
+            };
+            let line = Line::from(flow.fg(self.theme.accent));
+            match between {
+                // Right of the move number
+                Some(row) => {
+                    let [_, _, right] =
+                        Layout::horizontal([Fill(1), Length(10), Fill(1)]).areas(row);
+                    line.render(right, buf);
+                }
+                // The middle row of the digits
+                None => {
+                    let [_, middle, _] =
+                        Layout::horizontal([Fill(1), Length(8), Fill(1)]).areas(across(height / 2));
+                    line.centered().render(middle, buf);
+                }
+            }
+        }
+        let started = !matches!(clock.state(), ClockState::NotStarted);
+        // Under the pause when stacked
+        if started && (between.is_none() || !paused) {
+            // Between the clocks, on the top row of the digits
+            let row = between.unwrap_or_else(|| across(0));
+            let [_, middle, _] = Layout::horizontal([Fill(1), Length(10), Fill(1)]).areas(row);
+            let line = Line::from(trf("Move {}", &[&clock.move_number()]).fg(Color::Gray));
+            line.centered().render(middle, buf);
+        }
+        if paused {
+            // Across both clocks, on the middle row of the digits
+            let row = between.unwrap_or_else(|| across(height / 2));
+            let paused = format!(" {} ", tr("PAUSED"));
+            let line = Line::from(paused.fg(self.theme.highlight).bold());
+            line.centered().render(row, buf);
+        }
+        for (player, area) in [(Player::Player1, l2[1]), (Player::Player2, l3[1])] {
+            if let Some(status) = overtime_status(clock, player) {
+                let [_, area] = Layout::vertical([Length(height), Length(1)]).areas(area);
+                let line = Line::styled(status, styles[player.index()]);
+                line.centered().render(area, buf);
+            }
+        }
         block.render(area, buf);
     }
 }
+
+/// The overtime left to `player`, e.g. `3×30s` for three byoyomi periods of 30 seconds.
+fn overtime_status(clock: &Clock, player: Player) -> Option<String> {
+    let left = clock.overtime_left(player);
+    match clock.overtime() {
+        Overtime::None => None,
+        Overtime::Byoyomi { periods, seconds } => {
+            Some(format!("{}×{seconds}s", left.unwrap_or(periods)))
+        }
+        Overtime::Canadian { .. } => Some(match left {
+            Some(left) => trf("{} moves left", &[&left]),
+            None => clock.overtime().to_string(),
+        }),
+    }
+}
+
+fn state_to_style_pure(
+    state: ClockState,
+    resume: Player,
+    time1: Duration,
+    time2: Duration,
+    theme: Theme,
+) -> [Style; 2] {
+    let active_style = Style::default().fg(theme.active);
+    let inactive_style = Style::default().fg(theme.inactive);
+    let burning_clock_style = Style::default().fg(theme.burning);
+    match state {
+        ClockState::Player(p) => match p {
+            Player::Player1 => [
+                if Clock::burning(time1) {
+                    burning_clock_style
+                } else {
+                    active_style
+                },
+                inactive_style,
+            ],
+            Player::Player2 => [
+                inactive_style,
+                if Clock::burning(time2) {
+                    burning_clock_style
+                } else {
+                    active_style
+                },
+            ],
+        },
+        ClockState::NotStarted => [inactive_style, inactive_style],
+        // Dimmed under the overlay
+        ClockState::Pause => {
+            let state = ClockState::Player(resume);
+            state_to_style_pure(state, resume, time1, time2, theme).map(|style| style.dim())
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::str::FromStr;
+
+use crate::{
+    clock::Player,
+    custom,
+    tabs::{CtrlOption, TimeCtrl},
+};
+
+/// Commands accepted from frontends other than the TUI, one per line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Start the clock or pass the turn to the opponent. With a player, only that player's
+    /// clock can be stopped, like pressing their button on a physical clock.
+    Hit(Option<Player>),
+    /// A move in UCI notation, e.g. `e2e4`. Passes the turn like [`Command::Hit`] and is
+    /// forwarded to the engine, if one plays.
+    Move(String),
+    /// Pause or resume the running clock.
+    Pause,
+    /// Give the first move to a player, before the game starts.
+    First(Player),
+    /// Add (or subtract, when negative) milliseconds to a player's time.
+    Adjust(Player, i64),
+    /// Start a new game with the given time control.
+    Set(TimeCtrl),
+    /// Start a new game with a base time and increment that isn't a built-in time control.
+    SetCustom(CtrlOption),
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let args: Vec<_> = words.collect();
+        match (command, args.as_slice()) {
+            ("hit", []) => Ok(Command::Hit(None)),
+            ("hit", [player]) => parse_player(player).map(|p| Command::Hit(Some(p))),
+            ("move", [uci_move]) => parse_move(uci_move).map(Command::Move),
+            ("pause", []) => Ok(Command::Pause),
+            ("first", [player]) => parse_player(player).map(Command::First),
+            ("adjust", [player, seconds]) => {
+                let player = parse_player(player)?;
+                let seconds: f64 = seconds
+                    .parse()
+                    .map_err(|_| format!("invalid number of seconds '{seconds}'"))?;
+                Ok(Command::Adjust(player, (seconds * 1000.0) as i64))
+            }
+            ("set", [_, ..]) => parse_set(&args.join(" ")),
+            ("quit", []) => Ok(Command::Quit),
+            _ => Err(format!("invalid command '{}'", s.trim())),
+        }
+    }
+}
+
+/// A new game with the built-in time control labelled `s`, or with the minutes and increment
+/// it's written in like `15+10`.
+pub fn parse_set(s: &str) -> Result<Command, String> {
+    match TimeCtrl::parse(s) {
+        Ok(ctrl) => Ok(Command::Set(ctrl)),
+        Err(_) => custom::parse_ctrl(s).map(Command::SetCustom),
+    }
+}
+
+/// Checks the shape of a UCI move, its legality is up to the engine.
+fn parse_move(s: &str) -> Result<String, String> {
+    let bytes = s.as_bytes();
+    let square = |file: u8, rank: u8| (b'a'..=b'h').contains(&file) && (b'1'..=b'8').contains(&rank);
+    let valid = match bytes {
+        [f1, r1, f2, r2] => square(*f1, *r1) && square(*f2, *r2),
+        [f1, r1, f2, r2, promotion] => {
+            square(*f1, *r1) && square(*f2, *r2) && b"qrbn".contains(promotion)
+        }
+        _ => false,
+    };
+    if valid {
+        Ok(s.to_string())
+    } else {
+        Err(format!("invalid move '{s}', expected e.g. e2e4"))
+    }
+}
+
+pub fn parse_player(s: &str) -> Result<Player, String> {
+    match s {
+        "1" => Ok(Player::Player1),
+        "2" => Ok(Player::Player2),
+        _ => Err(format!("unknown player '{s}', expected 1 or 2")),
+    }
+}
@@ -0,0 +1,474 @@
+use std::{collections::BTreeMap, fs, path::PathBuf, time::Duration};
+
+use anyhow::Context;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+#[cfg(feature = "callbacks")]
+use crate::callback::CallbackConfig;
+use crate::{
+    clock::{ClockLayout, Overtime, Stage, SubSeconds, Theme, ThemeName},
+    custom,
+    event::{TICK_FPS, TICK_FPS_RANGE},
+    font::{self, Font, FontName},
+    i18n::{Lang, Numerals, trf},
+    keymap::{Action, Key},
+    paths,
+    tabs::{self, CtrlOption, TimeCtrl},
+};
+
+/// User configuration read from `config.toml`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Language of the interface, e.g. `de`, taken from the locale if not set.
+    pub language: Option<Lang>,
+    /// Digits of the clocks, e.g. `devanagari`, taken from the locale if not set.
+    pub numerals: Option<Numerals>,
+    /// Time control picked when the app starts, e.g. `"3+2"`.
+    pub time_control: Option<String>,
+    /// Tick events per second, at which the screen is redrawn, from 1 to 240 or 60 if not set.
+    pub tick_rate: Option<f64>,
+    /// Built-in theme, e.g. `dracula`.
+    pub theme: ThemeName,
+    /// Colours overriding the ones of the theme.
+    pub colors: ColorsConfig,
+    /// The clocks side by side or stacked, by the shape of the terminal if not set.
+    pub layout: ClockLayout,
+    /// Glyphs of the digits, e.g. `7-segment` or a font of the config directory.
+    pub font: FontConfig,
+    /// Player 2's digits upside down, for playing face to face over a laptop lying flat.
+    pub mirrored: bool,
+    /// When tenths or hundredths of seconds are shown, in the last 20 seconds if not set.
+    pub subseconds: SubSeconds,
+    pub history: HistoryConfig,
+    pub round: RoundConfig,
+    pub focus: FocusConfig,
+    pub byoyomi: ByoyomiConfig,
+    pub canadian: CanadianConfig,
+    pub classical: ClassicalConfig,
+    pub hourglass: HourglassConfig,
+    pub handicap: HandicapConfig,
+    pub claims: ClaimsConfig,
+    pub moretime: MoreTimeConfig,
+    pub players: PlayersConfig,
+    pub sound: SoundConfig,
+    /// Most minutes a clock can rise to by time control, e.g. `"3+2" = 4`.
+    pub max_time: BTreeMap<String, u64>,
+    pub accessibility: AccessibilityConfig,
+    #[serde(rename = "match")]
+    pub series: SeriesConfig,
+    /// Keys bound to actions instead of the default ones, e.g. `hit = ["space", "enter"]`.
+    pub keys: BTreeMap<Action, Vec<Key>>,
+    #[cfg(feature = "callbacks")]
+    pub callbacks: Vec<CallbackConfig>,
+}
+
+/// A bundled font, or the name of one in `fonts/<name>/` of the config directory.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum FontConfig {
+    Bundled(FontName),
+    Custom(String),
+}
+
+impl Default for FontConfig {
+    fn default() -> Self {
+        FontConfig::Bundled(FontName::default())
+    }
+}
+
+/// Colours set one by one, see [`Theme`].
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct ColorsConfig {
+    pub active: Option<Color>,
+    pub inactive: Option<Color>,
+    pub burning: Option<Color>,
+    pub accent: Option<Color>,
+    pub muted: Option<Color>,
+    pub highlight: Option<Color>,
+}
+
+/// Retention policy applied to the game history after every finished game.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Keep only this many most recent games.
+    pub keep_last: Option<usize>,
+    /// Delete games older than this many days.
+    pub max_age_days: Option<u64>,
+}
+
+/// The tournament round timer.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RoundConfig {
+    /// Duration of a round.
+    pub minutes: u64,
+    /// Minutes left at which the timer announces the time remaining.
+    pub announce: Vec<u64>,
+}
+
+impl Default for RoundConfig {
+    fn default() -> Self {
+        Self {
+            minutes: 90,
+            announce: vec![30, 10, 5],
+        }
+    }
+}
+
+/// The work and break periods of the focus timer.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FocusConfig {
+    pub work_minutes: u64,
+    pub break_minutes: u64,
+}
+
+impl Default for FocusConfig {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            break_minutes: 5,
+        }
+    }
+}
+
+/// The byoyomi time control of the picker, for Go.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ByoyomiConfig {
+    /// Main time.
+    pub minutes: u64,
+    pub periods: u32,
+    /// Length of a period.
+    pub seconds: u64,
+}
+
+impl ByoyomiConfig {
+    /// The label of the picker, e.g. `10 5×30s`.
+    pub fn label(&self) -> String {
+        format!("{} {}", self.minutes, self.overtime())
+    }
+
+    pub fn overtime(&self) -> Overtime {
+        Overtime::Byoyomi {
+            periods: self.periods,
+            seconds: self.seconds,
+        }
+    }
+}
+
+impl Default for ByoyomiConfig {
+    fn default() -> Self {
+        Self {
+            minutes: 10,
+            periods: 5,
+            seconds: 30,
+        }
+    }
+}
+
+/// The Canadian overtime time control of the picker, for Go.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CanadianConfig {
+    /// Main time.
+    pub minutes: u64,
+    /// Moves to make in each period.
+    pub moves: u32,
+    pub period_minutes: u64,
+}
+
+impl CanadianConfig {
+    /// The label of the picker, e.g. `10 10/5m`.
+    pub fn label(&self) -> String {
+        format!("{} {}", self.minutes, self.overtime())
+    }
+
+    pub fn overtime(&self) -> Overtime {
+        Overtime::Canadian {
+            moves: self.moves,
+            seconds: self.period_minutes * 60,
+        }
+    }
+}
+
+impl Default for CanadianConfig {
+    fn default() -> Self {
+        Self {
+            minutes: 10,
+            moves: 10,
+            period_minutes: 5,
+        }
+    }
+}
+
+/// The time control in stages of the picker, as played in classical events.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ClassicalConfig {
+    /// Time of the first stage.
+    pub minutes: u64,
+    /// Increment of the first stage, in seconds.
+    pub increment: u64,
+    pub stages: Vec<Stage>,
+}
+
+impl ClassicalConfig {
+    pub fn ctrl(&self) -> CtrlOption {
+        (
+            Duration::from_secs(self.minutes * 60),
+            Duration::from_secs(self.increment),
+        )
+    }
+
+    /// The label of the picker, e.g. `90/40 30 +30`.
+    pub fn label(&self) -> String {
+        tabs::stages_label(self.ctrl(), &self.stages)
+    }
+}
+
+impl Default for ClassicalConfig {
+    fn default() -> Self {
+        Self {
+            minutes: 90,
+            increment: 0,
+            stages: vec![Stage {
+                moves: 40,
+                minutes: 30,
+                increment: 30,
+            }],
+        }
+    }
+}
+
+/// The hourglass of the picker, where the time one player uses flows to the other.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HourglassConfig {
+    pub minutes: u64,
+}
+
+impl HourglassConfig {
+    pub fn label(&self) -> String {
+        trf("Hourglass {}", &[&self.minutes])
+    }
+}
+
+impl Default for HourglassConfig {
+    fn default() -> Self {
+        Self { minutes: 1 }
+    }
+}
+
+/// Time odds suggested from the players' ratings, see [`crate::handicap::suggest`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HandicapConfig {
+    /// Percent of the time taken off the stronger player per 100 rating points.
+    pub percent_per_100: f64,
+    /// Least percent of the time left to the stronger player.
+    pub min_percent: f64,
+}
+
+impl Default for HandicapConfig {
+    fn default() -> Self {
+        Self {
+            percent_per_100: 10.0,
+            min_percent: 20.0,
+        }
+    }
+}
+
+/// Win-on-time claims, as over the board.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct ClaimsConfig {
+    /// A flag fall waits for the opponent or arbiter to confirm the claim before the result
+    /// is recorded.
+    pub confirm: bool,
+}
+
+/// Time a player gives their opponent with the moretime key.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MoreTimeConfig {
+    pub seconds: u64,
+}
+
+impl Default for MoreTimeConfig {
+    fn default() -> Self {
+        Self { seconds: 15 }
+    }
+}
+
+/// The players of the games.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct PlayersConfig {
+    /// The players' names are asked for after the time control is picked.
+    pub ask_names: bool,
+}
+
+/// Sounds played during the game, see [`crate::sound::Sound`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SoundConfig {
+    /// Sounds start muted, `M` turns them on.
+    pub muted: bool,
+    /// Loudness of the sounds from 0 to 1, silent at 0 even in the terminal.
+    pub volume: f32,
+    /// A click on every hit of the clock.
+    pub hit: bool,
+    /// An alert when a clock falls into the last seconds.
+    pub low_time: bool,
+    /// An alert when a player runs out of time.
+    pub flag: bool,
+    /// Directory of `hit.wav`, `low_time.wav` and `flag.wav` replacing the built-in sounds,
+    /// `sounds` next to the config file if not set.
+    pub dir: Option<PathBuf>,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            volume: 1.0,
+            hit: false,
+            low_time: true,
+            flag: true,
+            dir: None,
+        }
+    }
+}
+
+impl SoundConfig {
+    #[cfg(feature = "audio")]
+    pub fn dir(&self) -> PathBuf {
+        self.dir
+            .clone()
+            .unwrap_or_else(|| paths::config_dir().join("sounds"))
+    }
+}
+
+/// A best-of-N match started with `--best-of`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct SeriesConfig {
+    /// Time control of each game, e.g. `5+3`, the last one is kept for the games after it.
+    pub controls: Vec<String>,
+    /// A tie is broken by an Armageddon game.
+    pub armageddon: bool,
+}
+
+/// Help for players who can't see the clocks.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    /// The times are written as plain lines instead of big digits.
+    pub screen_reader: bool,
+    /// Command speaking the announcements, given the text as its last argument, e.g.
+    /// `espeak`.
+    pub tts: Option<String>,
+    /// Seconds between two announcements of the time of the player to move, none if 0.
+    pub interval: u64,
+    /// Nothing fades or flickers, e.g. the tenths of the last seconds aren't shown.
+    pub reduced_motion: bool,
+}
+
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self {
+            screen_reader: false,
+            tts: None,
+            interval: 60,
+            reduced_motion: false,
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        paths::config_dir().join("config.toml")
+    }
+
+    /// Loads the config file, falling back to defaults if it doesn't exist.
+    pub fn load() -> anyhow::Result<Config> {
+        match fs::read_to_string(Self::path()) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+    /// The time control picked at start, the default one if the config names none or an
+    /// unknown one.
+    pub fn time_control(&self) -> TimeCtrl {
+        self.time_control
+            .as_deref()
+            .and_then(|label| TimeCtrl::parse(label).ok())
+            .unwrap_or_default()
+    }
+
+    /// The time control picked at start when it isn't a built-in one but minutes and increment,
+    /// e.g. `"7+5"`.
+    pub fn custom_time_control(&self) -> Option<CtrlOption> {
+        let label = self.time_control.as_deref()?;
+        if TimeCtrl::parse(label).is_ok() {
+            return None;
+        }
+        custom::parse_ctrl(label).ok()
+    }
+
+    /// The font named in the config, read from `fonts/<name>/` of the config directory unless
+    /// it is bundled.
+    pub fn font(&self) -> anyhow::Result<Font> {
+        let name = match &self.font {
+            FontConfig::Bundled(name) => return Ok(name.font().clone()),
+            FontConfig::Custom(name) => name,
+        };
+        let dir = paths::config_dir().join("fonts").join(name);
+        let read = |file: &str| {
+            let path = dir.join(format!("{file}.txt"));
+            fs::read_to_string(&path).with_context(|| format!("could not read {}", path.display()))
+        };
+        let glyphs: Vec<_> = font::FILES
+            .into_iter()
+            .map(read)
+            .collect::<anyhow::Result<_>>()?;
+        let glyphs = glyphs.try_into().expect("a glyph per file");
+        Font::new(glyphs).map_err(|e| anyhow::anyhow!("font {name}: {e}"))
+    }
+
+    /// The theme named in the config with the colours set one by one over it.
+    pub fn theme(&self) -> Theme {
+        let theme = self.theme.theme();
+        let colors = &self.colors;
+        Theme {
+            active: colors.active.unwrap_or(theme.active),
+            inactive: colors.inactive.unwrap_or(theme.inactive),
+            burning: colors.burning.unwrap_or(theme.burning),
+            accent: colors.accent.unwrap_or(theme.accent),
+            muted: colors.muted.unwrap_or(theme.muted),
+            highlight: colors.highlight.unwrap_or(theme.highlight),
+        }
+    }
+
+    /// The tick rate set, [`TICK_FPS`] if it's out of [`TICK_FPS_RANGE`].
+    pub fn tick_rate(&self) -> f64 {
+        self.tick_rate
+            .filter(|rate| TICK_FPS_RANGE.contains(rate))
+            .unwrap_or(TICK_FPS)
+    }
+
+    /// The cap set for the time control `ctrl`, whose label is matched ignoring whitespace.
+    pub fn max_time(&self, ctrl: CtrlOption) -> Option<Duration> {
+        let wanted: String = tabs::label(ctrl).split_whitespace().collect();
+        self.max_time
+            .iter()
+            .find(|(label, _)| label.split_whitespace().collect::<String>() == wanted)
+            .map(|(_, &minutes)| Duration::from_secs(minutes * 60))
+    }
+}
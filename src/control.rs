@@ -0,0 +1,226 @@
+//! The control protocol, served on a Unix socket or, on Windows, on a named pipe.
+
+#[cfg(unix)]
+use std::{env, fs};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+#[cfg(unix)]
+use anyhow::bail;
+#[cfg(windows)]
+use tokio::net::windows::named_pipe::{ClientOptions, ServerOptions};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    sync::{broadcast, mpsc, watch},
+};
+
+use crate::{
+    clock::{ClockEvent, Snapshot},
+    command::Command,
+    event::Event,
+    net::NetEvent,
+    remote::Remote,
+};
+
+/// Minimum interval between two state updates sent to a `watch` client.
+const WATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Socket path used when `--socket` is given without a value.
+#[cfg(unix)]
+pub fn default_path() -> PathBuf {
+    match env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("chessclock.sock"),
+        None => {
+            let user = env::var("USER").unwrap_or_default();
+            env::temp_dir().join(format!("chessclock-{user}.sock"))
+        }
+    }
+}
+
+/// Pipe used when `--socket` is given without a value.
+#[cfg(windows)]
+pub fn default_path() -> PathBuf {
+    PathBuf::from(r"\\.\pipe\chessclock")
+}
+
+/// Whether `addr` names a control socket rather than a network host.
+pub fn is_socket(addr: &str) -> bool {
+    if cfg!(windows) {
+        addr.starts_with(r"\\.\pipe\")
+    } else {
+        Path::new(addr).exists()
+    }
+}
+
+/// A listening control socket, closed and on Unix removed when this is dropped.
+#[derive(Debug)]
+pub struct ControlSocket {
+    #[cfg(unix)]
+    path: PathBuf,
+    #[cfg(windows)]
+    server: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(unix)]
+impl Drop for ControlSocket {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(windows)]
+impl Drop for ControlSocket {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Listens for control connections on `path`.
+///
+/// Clients send one request per line: any [`Command`] (answered with `ok` or `error: ...`),
+/// `status` for a single JSON state line, `uci` for the times as a UCI `go` command, `watch` to
+/// receive a JSON line on every change, or `events` to receive a JSON line for every
+/// [`ClockEvent`].
+#[cfg(unix)]
+pub fn listen(path: &Path, remote: Remote) -> anyhow::Result<ControlSocket> {
+    if path.exists() {
+        // A socket left behind by a crashed instance can be replaced, a live one can't
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            bail!("another instance is listening on {}", path.display());
+        }
+        fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("could not listen on {}", path.display()))?;
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(handle(stream, remote.clone()));
+        }
+    });
+    Ok(ControlSocket {
+        path: path.to_owned(),
+    })
+}
+
+/// Listens for control connections on the named pipe `path`, see the Unix version.
+#[cfg(windows)]
+pub fn listen(path: &Path, remote: Remote) -> anyhow::Result<ControlSocket> {
+    let mut pipe = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(path)
+        .with_context(|| format!("could not listen on {}", path.display()))?;
+    let path = path.to_owned();
+    let server = tokio::spawn(async move {
+        // Every client is served by its own instance of the pipe
+        while pipe.connect().await.is_ok() {
+            let Ok(next) = ServerOptions::new().create(&path) else {
+                return;
+            };
+            tokio::spawn(handle(std::mem::replace(&mut pipe, next), remote.clone()));
+        }
+    });
+    Ok(ControlSocket { server })
+}
+
+async fn handle(stream: impl AsyncRead + AsyncWrite, remote: Remote) -> io::Result<()> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        let reply = match line.trim() {
+            "" => continue,
+            "status" => json_line(&*remote.state.borrow())?,
+            "uci" => remote.state.borrow().uci() + "\n",
+            "watch" => return watch(writer, remote.state).await,
+            "events" => return stream_events(writer, remote.clock_events.subscribe()).await,
+            request => match request.parse::<Command>() {
+                Ok(command) => {
+                    let _ = remote.events.send(Event::Command(command));
+                    "ok\n".to_string()
+                }
+                Err(e) => format!("error: {e}\n"),
+            },
+        };
+        writer.write_all(reply.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+/// Follows the clock behind the control socket at `path`, emitting its states as
+/// [`NetEvent::Sync`].
+pub async fn follow(path: &Path, events: mpsc::UnboundedSender<Event>) -> anyhow::Result<()> {
+    #[cfg(unix)]
+    let stream = UnixStream::connect(path).await;
+    #[cfg(windows)]
+    let stream = ClientOptions::new().open(path);
+    let mut stream = stream.with_context(|| format!("could not connect to {}", path.display()))?;
+    stream.write_all(b"watch\n").await?;
+    let path = path.display().to_string();
+    let send = move |event| {
+        let _ = events.send(Event::Net(event));
+    };
+    send(NetEvent::Status(format!("watching {path}")));
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stream).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Ok(snapshot) = serde_json::from_str(&line) {
+                send(NetEvent::Sync(snapshot));
+            }
+        }
+        send(NetEvent::Status(format!("disconnected from {path}")));
+    });
+    Ok(())
+}
+
+/// Asks the instance listening on `path` for its current state.
+pub fn query(path: &Path) -> anyhow::Result<Snapshot> {
+    #[cfg(unix)]
+    let stream = std::os::unix::net::UnixStream::connect(path);
+    // Named pipes open like files
+    #[cfg(windows)]
+    let stream = std::fs::File::options().read(true).write(true).open(path);
+    let mut stream =
+        stream.with_context(|| format!("no clock is listening on {}", path.display()))?;
+    io::Write::write_all(&mut stream, b"status\n")?;
+    let mut line = String::new();
+    io::BufRead::read_line(&mut io::BufReader::new(stream), &mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+async fn watch(
+    mut writer: impl AsyncWrite + Unpin,
+    mut state: watch::Receiver<Snapshot>,
+) -> io::Result<()> {
+    loop {
+        let line = json_line(&*state.borrow_and_update())?;
+        writer.write_all(line.as_bytes()).await?;
+        tokio::time::sleep(WATCH_INTERVAL).await;
+        if state.changed().await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+async fn stream_events(
+    mut writer: impl AsyncWrite + Unpin,
+    mut events: broadcast::Receiver<ClockEvent>,
+) -> io::Result<()> {
+    loop {
+        match events.recv().await {
+            Ok(event) => writer.write_all(json_line(&event)?.as_bytes()).await?,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+fn json_line(value: &impl serde::Serialize) -> io::Result<String> {
+    let json = serde_json::to_string(value).map_err(io::Error::other)?;
+    Ok(json + "\n")
+}
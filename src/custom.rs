@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    i18n::{tr, trf},
+    tabs::{CtrlOption, PlayerCtrls, Preset},
+};
+
+/// Longest base time that can be typed, in minutes.
+const MAX_BASE: u64 = 600;
+/// Largest increment that can be typed, in seconds.
+const MAX_INCREMENT: u64 = 180;
+/// Longest name a preset can be saved under.
+const MAX_NAME: usize = 20;
+
+/// A time control being typed: the base time in minutes and the increment in seconds of
+/// Player 1, then of Player 2, and the name to save it under.
+#[derive(Debug, Clone, Default)]
+pub struct CustomInput {
+    pub fields: [String; 4],
+    pub name: String,
+    /// Index of the field being typed, the name after the four times.
+    pub focused: usize,
+}
+
+impl CustomInput {
+    /// The fields filled with `ctrls`, Player 2's left empty when they're the same as Player 1's.
+    pub fn new(PlayerCtrls([player1, player2]): PlayerCtrls) -> Self {
+        let mut input = CustomInput::default();
+        let fields = |(base, increment): CtrlOption| {
+            [
+                (base.as_secs() / 60).to_string(),
+                increment.as_secs().to_string(),
+            ]
+        };
+        input.fields[..2].clone_from_slice(&fields(player1));
+        if player2 != player1 {
+            input.fields[2..].clone_from_slice(&fields(player2));
+        }
+        input
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) {
+        let count = self.fields.len() + 1;
+        let naming = self.focused == self.fields.len();
+        match code {
+            KeyCode::Tab | KeyCode::Down => self.focused = (self.focused + 1) % count,
+            KeyCode::Char('+') if !naming => self.focused = (self.focused + 1) % count,
+            KeyCode::BackTab | KeyCode::Up => self.focused = (self.focused + count - 1) % count,
+            KeyCode::Char(c) if naming && self.name.chars().count() < MAX_NAME => self.name.push(c),
+            KeyCode::Backspace if naming => {
+                self.name.pop();
+            }
+            KeyCode::Char(c @ '0'..='9') if !naming && self.fields[self.focused].len() < 3 => {
+                self.fields[self.focused].push(c)
+            }
+            KeyCode::Backspace if !naming => {
+                self.fields[self.focused].pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// The text of field `i`, the one of Player 1 for an empty field of Player 2.
+    pub fn value(&self, i: usize) -> &str {
+        match &self.fields[i] {
+            field if field.is_empty() && i >= 2 => &self.fields[i - 2],
+            field => field,
+        }
+    }
+
+    /// The time control typed, or what's wrong with it. Player 2 gets the time of Player 1
+    /// unless another one is typed for them.
+    pub fn ctrl(&self) -> Result<PlayerCtrls, String> {
+        let player1 = parse(self.value(0), self.value(1))?;
+        let player2 = parse(self.value(2), self.value(3))?;
+        Ok(PlayerCtrls([player1, player2]))
+    }
+
+    /// The time control typed as a preset to save, none without a name.
+    pub fn preset(&self) -> Option<Preset> {
+        let name = self.name.trim();
+        let ctrl = self.ctrl().ok()?;
+        (!name.is_empty()).then(|| Preset::new(name.to_string(), ctrl))
+    }
+}
+
+fn parse(base: &str, increment: &str) -> Result<CtrlOption, String> {
+    let Ok(base) = base.parse() else {
+        return Err(tr("Type the base time in minutes").to_string());
+    };
+    // No increment when it's left empty
+    ctrl(base, increment.parse().unwrap_or(0))
+}
+
+/// Reads a time control written as minutes and increment, e.g. `5+3` or `10` without one.
+pub fn parse_ctrl(s: &str) -> Result<CtrlOption, String> {
+    let (minutes, increment) = s.split_once('+').unwrap_or((s, "0"));
+    match (minutes.trim().parse(), increment.trim().parse()) {
+        (Ok(minutes), Ok(increment)) => ctrl(minutes, increment),
+        _ => Err(format!(
+            "invalid time control '{s}', expected MINUTES+INCREMENT like 5+3"
+        )),
+    }
+}
+
+/// A time control of `base` minutes and `increment` seconds, or what's wrong with it.
+pub fn ctrl(base: u64, increment: u64) -> Result<CtrlOption, String> {
+    if base == 0 {
+        return Err(tr("Type the base time in minutes").to_string());
+    }
+    if base > MAX_BASE {
+        return Err(trf("The base time is at most {} minutes", &[&MAX_BASE]));
+    }
+    if increment > MAX_INCREMENT {
+        return Err(trf(
+            "The increment is at most {} seconds",
+            &[&MAX_INCREMENT],
+        ));
+    }
+    Ok((
+        Duration::from_secs(base * 60),
+        Duration::from_secs(increment),
+    ))
+}
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use tokio::sync::{broadcast::error::RecvError, mpsc, watch};
+use zbus::{fdo, interface, object_server::SignalEmitter};
+
+use crate::{
+    clock::{ClockEvent, Player, Snapshot},
+    command::Command,
+    event::Event,
+    remote::Remote,
+};
+
+const NAME: &str = "io.github.stepann0.Chessclock";
+const PATH: &str = "/io/github/stepann0/Chessclock";
+/// Minimum interval between two `StateChanged` signals while the clock is running.
+const STATE_INTERVAL: Duration = Duration::from_secs(1);
+
+struct ClockInterface {
+    events: mpsc::UnboundedSender<Event>,
+    state: watch::Receiver<Snapshot>,
+}
+
+impl ClockInterface {
+    fn send(&self, command: Command) {
+        let _ = self.events.send(Event::Command(command));
+    }
+}
+
+#[interface(name = "io.github.stepann0.Chessclock1")]
+impl ClockInterface {
+    /// Start the clock or pass the turn to the opponent.
+    fn hit(&self) {
+        self.send(Command::Hit(None));
+    }
+
+    /// Pause or resume the clock.
+    fn pause(&self) {
+        self.send(Command::Pause);
+    }
+
+    /// Add seconds (subtract when negative) to player 1 or 2.
+    fn adjust(&self, player: u8, seconds: f64) -> fdo::Result<()> {
+        let player = match player {
+            1 => Player::Player1,
+            2 => Player::Player2,
+            _ => return Err(fdo::Error::InvalidArgs("player must be 1 or 2".into())),
+        };
+        self.send(Command::Adjust(player, (seconds * 1000.0) as i64));
+        Ok(())
+    }
+
+    /// The clock state as JSON.
+    #[zbus(property)]
+    fn status(&self) -> String {
+        serde_json::to_string(&*self.state.borrow()).unwrap_or_default()
+    }
+
+    #[zbus(signal)]
+    async fn state_changed(emitter: &SignalEmitter<'_>, state: &str) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn flag_fell(emitter: &SignalEmitter<'_>, player: u8) -> zbus::Result<()>;
+}
+
+/// Registers the service on the session bus.
+///
+/// `StateChanged` is emitted after every clock event and at most once per second otherwise,
+/// `FlagFell` when a player runs out of time.
+pub async fn serve(remote: &Remote) -> anyhow::Result<()> {
+    let interface = ClockInterface {
+        events: remote.events.clone(),
+        state: remote.state.clone(),
+    };
+    let connection = zbus::connection::Builder::session()?
+        .name(NAME)?
+        .serve_at(PATH, interface)?
+        .build()
+        .await?;
+
+    let mut state = remote.state.clone();
+    let mut events = remote.clock_events.subscribe();
+    tokio::spawn(async move {
+        let Ok(emitter) = SignalEmitter::new(&connection, PATH) else {
+            return;
+        };
+        let mut ticker = tokio::time::interval(STATE_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    if !state.has_changed().unwrap_or(false) {
+                        continue;
+                    }
+                }
+                event = events.recv() => match event {
+                    Ok(ClockEvent::Flag { player }) => {
+                        let player = match player {
+                            Player::Player1 => 1,
+                            Player::Player2 => 2,
+                        };
+                        let _ = ClockInterface::flag_fell(&emitter, player).await;
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return,
+                },
+            }
+            let json = serde_json::to_string(&*state.borrow_and_update()).unwrap_or_default();
+            let _ = ClockInterface::state_changed(&emitter, &json).await;
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serialport::SerialPort;
+use shakmaty::{Board, CastlingMode, Chess, Color, Move, Piece, Position, Role, Square, uci::UciMove};
+
+use crate::{
+    clock::{Player, Snapshot},
+    command::Command,
+    event::Event,
+    net::NetEvent,
+    remote::Remote,
+};
+
+// Requests and replies of the DGT board protocol
+const SEND_RESET: u8 = 0x40;
+const SEND_BOARD: u8 = 0x42;
+const SEND_UPDATES: u8 = 0x44;
+const BOARD_DUMP: u8 = 0x86;
+const FIELD_UPDATE: u8 = 0x8e;
+// Messages for a DGT 3000 clock attached to the board
+const CLOCK_MESSAGE: u8 = 0x2b;
+const CLOCK_START: u8 = 0x03;
+const CLOCK_SET_AND_RUN: u8 = 0x0a;
+const CLOCK_END: u8 = 0x00;
+
+/// Interval between two times sent to the DGT 3000, which counts down by itself in between.
+const CLOCK_INTERVAL: Duration = Duration::from_secs(1);
+/// Wait before opening the serial port again once it failed, e.g. after a loose cable.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watches the DGT board on the serial port `path` and hits the clock of the player who made a
+/// legal move on it. With `clock` the times are shown on a DGT 3000 attached to the board.
+///
+/// Errors of the port are shown on top of the screen while it's opened again.
+pub fn connect(path: &str, clock: bool, remote: Remote) -> anyhow::Result<()> {
+    let mut port = open(path).with_context(|| format!("could not open the DGT board on {path}"))?;
+    port.write_all(&[SEND_RESET, SEND_BOARD, SEND_UPDATES])?;
+
+    report(&remote, format!("DGT board on {path}"));
+    if clock {
+        let port = port.try_clone()?;
+        let (path, remote) = (path.to_string(), remote.clone());
+        thread::spawn(move || show_times(port, &path, &remote));
+    }
+    let path = path.to_string();
+    thread::spawn(move || watch(port, &path, &remote));
+    Ok(())
+}
+
+fn open(path: &str) -> serialport::Result<Box<dyn SerialPort>> {
+    serialport::new(path, 9600)
+        .timeout(Duration::from_secs(1))
+        .open()
+}
+
+fn report(remote: &Remote, status: String) {
+    let _ = remote.events.send(Event::Net(NetEvent::Status(status)));
+}
+
+/// Opens the port at `path` again after `error` of the `device` on it, until it opens.
+fn reconnect(
+    path: &str,
+    device: &str,
+    error: impl Display,
+    remote: &Remote,
+) -> Box<dyn SerialPort> {
+    report(remote, format!("{device}: {error}, reconnecting"));
+    loop {
+        thread::sleep(RECONNECT_INTERVAL);
+        if let Ok(port) = open(path) {
+            report(remote, format!("{device} on {path}"));
+            return port;
+        }
+    }
+}
+
+fn watch(mut port: Box<dyn SerialPort>, path: &str, remote: &Remote) {
+    let mut board = Board::empty();
+    let mut game = Chess::default();
+    loop {
+        let (kind, data) = match read_reply(&mut *port) {
+            Ok(Some(reply)) => reply,
+            // A reply cut by the timeout is dropped, the next one starts with its header byte
+            Ok(None) => continue,
+            Err(e) if e.kind() == io::ErrorKind::TimedOut => continue,
+            Err(e) => {
+                port = reconnect(path, "DGT board", e, remote);
+                // The board may have changed meanwhile, it's sent again in full
+                if let Err(e) = port.write_all(&[SEND_BOARD, SEND_UPDATES]) {
+                    report(remote, format!("DGT board: {e}"));
+                }
+                continue;
+            }
+        };
+
+        match (kind, data.as_slice()) {
+            (BOARD_DUMP, fields) if fields.len() == 64 => {
+                board = Board::empty();
+                for (field, &code) in fields.iter().enumerate() {
+                    place(&mut board, field as u8, code);
+                }
+            }
+            (FIELD_UPDATE, &[field, code]) => place(&mut board, field, code),
+            _ => continue,
+        }
+
+        if board == Board::new() {
+            game = Chess::default();
+        } else if let Some((m, next)) = find_move(&game, &board) {
+            hit(remote, game.turn());
+            let uci = UciMove::from_move(m, CastlingMode::Standard).to_string();
+            let _ = remote.events.send(Event::Net(NetEvent::Moved(uci)));
+            game = next;
+        }
+    }
+}
+
+/// The next reply of the board, with its kind, none if a byte out of a reply comes first.
+fn read_reply(port: &mut dyn SerialPort) -> io::Result<Option<(u8, Vec<u8>)>> {
+    let mut header = [0; 3];
+    port.read_exact(&mut header[..1])?;
+    // Replies start with a byte with the high bit set
+    if header[0] & 0x80 == 0 {
+        return Ok(None);
+    }
+    port.read_exact(&mut header[1..])?;
+    let size = (usize::from(header[1]) << 7 | usize::from(header[2])).saturating_sub(3);
+    let mut data = vec![0; size];
+    port.read_exact(&mut data)?;
+    Ok(Some((header[0], data)))
+}
+
+/// Puts the piece with the DGT `code` on `field`, numbered from a8 to h1.
+fn place(board: &mut Board, field: u8, code: u8) {
+    if field >= 64 {
+        return;
+    }
+    let field = u32::from(field);
+    let square = Square::new((7 - field / 8) * 8 + field % 8);
+    let (color, role) = match code {
+        1 => (Color::White, Role::Pawn),
+        2 => (Color::White, Role::Rook),
+        3 => (Color::White, Role::Knight),
+        4 => (Color::White, Role::Bishop),
+        5 => (Color::White, Role::King),
+        6 => (Color::White, Role::Queen),
+        7 => (Color::Black, Role::Pawn),
+        8 => (Color::Black, Role::Rook),
+        9 => (Color::Black, Role::Knight),
+        10 => (Color::Black, Role::Bishop),
+        11 => (Color::Black, Role::King),
+        12 => (Color::Black, Role::Queen),
+        _ => {
+            board.discard_piece_at(square);
+            return;
+        }
+    };
+    board.set_piece_at(square, Piece { color, role });
+}
+
+/// The legal move that leads to `board` and the position after it, if any.
+fn find_move(game: &Chess, board: &Board) -> Option<(Move, Chess)> {
+    game.legal_moves().into_iter().find_map(|m| {
+        let mut next = game.clone();
+        next.play_unchecked(m);
+        (next.board() == board).then_some((m, next))
+    })
+}
+
+fn hit(remote: &Remote, mover: Color) {
+    let snapshot = remote.state.borrow().clone();
+    let player = match mover {
+        Color::White => snapshot.white,
+        Color::Black => snapshot.white.opponent(),
+    };
+    let send = |command| {
+        let _ = remote.events.send(Event::Command(command));
+    };
+    // The first move starts the clock, which then runs for black
+    if snapshot.turn.is_none() {
+        send(Command::Hit(None));
+    }
+    send(Command::Hit(Some(player)));
+}
+
+/// Keeps the DGT 3000 in sync with the clock, Player 1 on the left.
+fn show_times(mut port: Box<dyn SerialPort>, path: &str, remote: &Remote) {
+    let mut last: Option<(Snapshot, Instant)> = None;
+    loop {
+        let snapshot = remote.state.borrow().clone();
+        let changed = last.as_ref().is_none_or(|(last, sent)| {
+            (last.turn, last.paused, last.running)
+                != (snapshot.turn, snapshot.paused, snapshot.running)
+                || last.time_control != snapshot.time_control
+                || sent.elapsed() >= CLOCK_INTERVAL
+        });
+        if changed {
+            if let Err(e) = port.write_all(&set_and_run(&snapshot)) {
+                port = reconnect(path, "DGT clock", e, remote);
+                // The times are sent again at once
+                last = None;
+                continue;
+            }
+            last = Some((snapshot, Instant::now()));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+fn set_and_run(snapshot: &Snapshot) -> [u8; 12] {
+    let hms = |ms: u64| {
+        let secs = ms.div_ceil(1000);
+        [
+            (secs / 3600) as u8,
+            (secs / 60 % 60) as u8,
+            (secs % 60) as u8,
+        ]
+    };
+    let [lh, lm, ls] = hms(snapshot.player1_ms);
+    let [rh, rm, rs] = hms(snapshot.player2_ms);
+    let running = match snapshot.turn {
+        Some(Player::Player1) if snapshot.running => 0x01,
+        Some(Player::Player2) if snapshot.running => 0x02,
+        _ => 0x04,
+    };
+    [
+        CLOCK_MESSAGE,
+        10,
+        CLOCK_START,
+        CLOCK_SET_AND_RUN,
+        lh,
+        lm,
+        ls,
+        rh,
+        rm,
+        rs,
+        running,
+        CLOCK_END,
+    ]
+}
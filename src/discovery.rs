@@ -0,0 +1,106 @@
+use std::{fmt, net::SocketAddr};
+
+use anyhow::Context;
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use tokio::sync::mpsc;
+
+use crate::{event::Event, net::NetEvent};
+
+/// mDNS service type of hosted games.
+const SERVICE: &str = "_chessclock._tcp.local.";
+
+/// A game hosted on the local network.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Host {
+    /// Unique mDNS name of the service, used to forget a host that went away.
+    pub fullname: String,
+    pub name: String,
+    pub addr: SocketAddr,
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.addr.ip())
+    }
+}
+
+/// The mDNS responder, advertising or browsing until dropped.
+pub struct Daemon(ServiceDaemon);
+
+impl fmt::Debug for Daemon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Daemon")
+    }
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        let _ = self.0.shutdown();
+    }
+}
+
+/// Default name of a hosted game, e.g. "anna's clock".
+pub fn default_name() -> String {
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "chessclock".to_string());
+    format!("{user}'s clock")
+}
+
+/// Announces a game hosted on `port` under `name`.
+pub fn advertise(name: &str, port: u16) -> anyhow::Result<Daemon> {
+    let daemon = ServiceDaemon::new().context("could not start mDNS")?;
+    let host: String = name.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    let service = ServiceInfo::new(
+        SERVICE,
+        name,
+        &format!("chessclock-{host}.local."),
+        "",
+        port,
+        &[("name", name)][..],
+    )?
+    .enable_addr_auto();
+    daemon
+        .register(service)
+        .context("could not advertise the game")?;
+    Ok(Daemon(daemon))
+}
+
+/// Looks for hosted games, reporting them as [`NetEvent::Found`] and [`NetEvent::Lost`].
+pub fn browse(events: mpsc::UnboundedSender<Event>) -> anyhow::Result<Daemon> {
+    let daemon = ServiceDaemon::new().context("could not start mDNS")?;
+    let found = daemon.browse(SERVICE)?;
+    tokio::spawn(async move {
+        while let Ok(event) = found.recv_async().await {
+            let event = match event {
+                ServiceEvent::ServiceResolved(service) => {
+                    // Prefer IPv4, the address people would type
+                    let Some(ip) = service
+                        .addresses
+                        .iter()
+                        .map(|ip| ip.to_ip_addr())
+                        .filter(|ip| !ip.is_loopback())
+                        .min_by_key(|ip| ip.is_ipv6())
+                    else {
+                        continue;
+                    };
+                    let name = service
+                        .get_property_val_str("name")
+                        .unwrap_or(&service.host)
+                        .to_string();
+                    NetEvent::Found(Host {
+                        fullname: service.fullname.clone(),
+                        name,
+                        addr: SocketAddr::new(ip, service.port),
+                    })
+                }
+                ServiceEvent::ServiceRemoved(_, fullname) => NetEvent::Lost(fullname),
+                _ => continue,
+            };
+            if events.send(Event::Net(event)).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(Daemon(daemon))
+}
@@ -0,0 +1,65 @@
+use std::time::{Duration, Instant};
+
+/// Interval between two frames in eco mode, when nothing but the time changes.
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(500);
+/// Interval between two checks of the power source.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fewer frames and no tenths of seconds, to save the battery of a laptop during long games.
+/// Follows the power source until it's toggled by hand.
+#[derive(Debug, Default)]
+pub struct EcoMode {
+    enabled: bool,
+    manual: bool,
+    checked_at: Option<Instant>,
+}
+
+impl EcoMode {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        self.manual = true;
+    }
+
+    /// Turns eco mode on while the computer runs on battery, at most once per
+    /// [`CHECK_INTERVAL`].
+    pub fn check(&mut self) {
+        if self.manual
+            || self
+                .checked_at
+                .is_some_and(|at| at.elapsed() < CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.checked_at = Some(Instant::now());
+        self.enabled = on_battery();
+    }
+}
+
+/// Whether a battery is discharging.
+#[cfg(target_os = "linux")]
+fn on_battery() -> bool {
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+    supplies.flatten().any(|supply| {
+        let read = |name| std::fs::read_to_string(supply.path().join(name)).unwrap_or_default();
+        read("type").trim() == "Battery" && read("status").trim() == "Discharging"
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn on_battery() -> bool {
+    std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("Battery Power"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn on_battery() -> bool {
+    false
+}
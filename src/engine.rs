@@ -0,0 +1,159 @@
+use std::{process::Stdio, time::Duration};
+
+use anyhow::{Context, bail};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    process::{ChildStdin, ChildStdout},
+    sync::{broadcast, mpsc},
+};
+
+use crate::{
+    clock::{ClockEvent, Player},
+    command::Command,
+    event::Event,
+    net::NetEvent,
+    remote::Remote,
+};
+
+/// How long the engine may take to answer `uci` and `isready`.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A UCI engine playing one side of the clock.
+#[derive(Debug)]
+pub struct Engine {
+    player: Player,
+    moves: mpsc::UnboundedSender<String>,
+}
+
+impl Engine {
+    /// Starts the engine `command`, which plays `player`. The engine moves whenever its clock
+    /// runs and presses it after answering with its best move.
+    pub async fn start(command: &str, player: Player, remote: Remote) -> anyhow::Result<Engine> {
+        let mut child = tokio::process::Command::new(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("could not start engine '{command}'"))?;
+        let mut uci = Uci {
+            stdin: child.stdin.take().context("engine has no stdin")?,
+            stdout: BufReader::new(child.stdout.take().context("engine has no stdout")?).lines(),
+        };
+        tokio::time::timeout(HANDSHAKE_TIMEOUT, uci.handshake())
+            .await
+            .context("engine did not answer")??;
+
+        let (moves, received) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            // The engine is killed when the task ends
+            let _child = child;
+            if let Err(e) = play(uci, player, received, &remote).await {
+                let status = NetEvent::Status(format!("engine: {e:#}"));
+                let _ = remote.events.send(Event::Net(status));
+            }
+        });
+        Ok(Engine { player, moves })
+    }
+
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    /// Passes the opponent's move in UCI notation, e.g. `e2e4`, to the engine.
+    pub fn opponent_moved(&self, uci_move: String) {
+        let _ = self.moves.send(uci_move);
+    }
+}
+
+struct Uci {
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+impl Uci {
+    async fn send(&mut self, line: &str) -> anyhow::Result<()> {
+        self.stdin.write_all(format!("{line}\n").as_bytes()).await?;
+        Ok(())
+    }
+
+    /// Reads lines until one starts with `prefix`, returning it.
+    async fn expect(&mut self, prefix: &str) -> anyhow::Result<String> {
+        while let Some(line) = self.stdout.next_line().await? {
+            if line.starts_with(prefix) {
+                return Ok(line);
+            }
+        }
+        bail!("engine quit")
+    }
+
+    async fn handshake(&mut self) -> anyhow::Result<()> {
+        self.send("uci").await?;
+        self.expect("uciok").await?;
+        self.send("isready").await?;
+        self.expect("readyok").await?;
+        Ok(())
+    }
+}
+
+async fn play(
+    mut uci: Uci,
+    player: Player,
+    mut opponent_moves: mpsc::UnboundedReceiver<String>,
+    remote: &Remote,
+) -> anyhow::Result<()> {
+    let mut state = remote.state.clone();
+    let mut clock_events = remote.clock_events.subscribe();
+    let mut moves: Vec<String> = vec![];
+    loop {
+        tokio::select! {
+            // A new game is published before any move of it is received
+            biased;
+            event = clock_events.recv() => match event {
+                Ok(ClockEvent::NewGame { .. }) if !moves.is_empty() => {
+                    moves.clear();
+                    uci.send("ucinewgame").await?;
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                _ => {}
+            },
+            opponent_move = opponent_moves.recv() => match opponent_move {
+                Some(opponent_move) => moves.push(opponent_move),
+                None => return Ok(()),
+            },
+            changed = state.changed() => changed?,
+        }
+        let snapshot = state.borrow_and_update().clone();
+        let engine_to_move = moves.len().is_multiple_of(2) == (snapshot.white == player);
+        if !snapshot.running || snapshot.turn != Some(player) || !engine_to_move {
+            continue;
+        }
+
+        let position = if moves.is_empty() {
+            "position startpos".to_string()
+        } else {
+            format!("position startpos moves {}", moves.join(" "))
+        };
+        uci.send(&position).await?;
+        uci.send(&snapshot.uci()).await?;
+        let best = uci.expect("bestmove").await?;
+        let status = match best.split_whitespace().nth(1) {
+            Some(best) if best != "(none)" => {
+                moves.push(best.to_string());
+                let _ = remote
+                    .events
+                    .send(Event::Command(Command::Hit(Some(player))));
+                let _ = remote
+                    .events
+                    .send(Event::Net(NetEvent::Moved(best.to_string())));
+                format!("engine plays {best}")
+            }
+            // Mate or stalemate, the game is over
+            _ => {
+                let _ = remote.events.send(Event::Command(Command::Pause));
+                "engine has no move left".to_string()
+            }
+        };
+        let _ = remote.events.send(Event::Net(NetEvent::Status(status)));
+    }
+}
@@ -1,34 +1,48 @@
-use futures::{FutureExt, StreamExt};
-use ratatui::crossterm::event::Event as CrosstermEvent;
+use ratatui::crossterm::event::{KeyEvent, MouseEvent};
 use std::{io, time::Duration};
-use tokio::sync::mpsc;
+use tokio::{sync::mpsc, time::MissedTickBehavior};
 
-/// The frequency at which tick events are emitted.
-const TICK_FPS: f64 = 60.0;
-/// Timer tick event
-pub(crate) const TIMER_TICK: u64 = 10;
+use crate::{clock::TIMER_TICK, command::Command, net::NetEvent, term};
+
+/// The frequency at which tick events are emitted, unless the config sets one.
+pub const TICK_FPS: f64 = 60.0;
+/// The frequencies a config may set, others fall back to [`TICK_FPS`].
+pub const TICK_FPS_RANGE: std::ops::RangeInclusive<f64> = 1.0..=240.0;
 
 /// Representation of all possible events.
 #[derive(Clone, Debug)]
 pub enum Event {
     Tick,
-    /// An event that represents timer tick. Each tick is TIMER_TICK milliseconds long
+    /// An event that represents timer tick, emitted every TIMER_TICK milliseconds
     ///
-    /// Use this event to decrement player's timer
+    /// Use this event to update player's timer, which counts the time actually elapsed
     TimerTick,
-    /// Crossterm events.
-    /// These events are emitted by the terminal.
-    Crossterm(CrosstermEvent),
+    /// Keys typed in the terminal, whatever its backend.
+    Key(KeyEvent),
+    /// Clicks and taps in the terminal, with the crossterm backend only.
+    #[cfg_attr(any(feature = "termion", feature = "termwiz"), allow(dead_code))]
+    Mouse(MouseEvent),
+    /// The terminal was resized, with the crossterm backend only.
+    #[cfg_attr(any(feature = "termion", feature = "termwiz"), allow(dead_code))]
+    Resize,
     /// Application events.
     ///
     /// Use this event to emit custom events that are specific to your application.
     App(AppEvent),
+    /// Commands from outside of the terminal, e.g. the control socket.
+    Command(Command),
+    /// Network play events.
+    Net(NetEvent),
+    /// Events of a board followed on the arbiter's dashboard, by index.
+    Board(usize, NetEvent),
 }
 
 #[derive(Clone, Debug)]
 pub enum AppEvent {
     Timeout,
     HitClock,
+    /// Join the network game hosted at this address.
+    Join(String),
     /// Quit the application.
     Quit,
 }
@@ -43,19 +57,26 @@ pub struct EventHandler {
 }
 
 impl EventHandler {
-    /// Constructs a new instance of [`EventHandler`] and spawns a new thread to handle events.
-    pub fn new() -> Self {
+    /// Constructs a new instance of [`EventHandler`] emitting `fps` tick events per second and
+    /// spawns a new thread to handle events.
+    pub fn new(fps: f64) -> Self {
         let (sender, receiver) = mpsc::unbounded_channel();
-        let actor = EventTask::new(sender.clone());
+        let actor = EventTask::new(sender.clone(), fps);
         tokio::spawn(async { actor.run().await });
+        term::read_keys(sender.clone());
         Self { sender, receiver }
     }
 
     pub async fn next(&mut self) -> io::Result<Event> {
-        self.receiver.recv().await.ok_or(io::Error::new(
-            io::ErrorKind::Other,
-            "could not recieve event",
-        ))
+        self.receiver
+            .recv()
+            .await
+            .ok_or(io::Error::other("could not recieve event"))
+    }
+
+    /// A sender for emitting events from other tasks.
+    pub fn sender(&self) -> mpsc::UnboundedSender<Event> {
+        self.sender.clone()
     }
 
     /// Queue an app event to be sent to the event receiver.
@@ -72,28 +93,28 @@ impl EventHandler {
 /// A thread that handles reading crossterm events and emitting tick events on a regular schedule.
 struct EventTask {
     sender: mpsc::UnboundedSender<Event>,
+    fps: f64,
 }
 
 impl EventTask {
-    fn new(sender: mpsc::UnboundedSender<Event>) -> Self {
-        Self { sender }
+    fn new(sender: mpsc::UnboundedSender<Event>, fps: f64) -> Self {
+        Self { sender, fps }
     }
 
     /// Runs the event thread.
     ///
-    /// This function emits tick events at a fixed rate and polls for crossterm events in between.
+    /// This function emits tick events at a fixed rate.
     async fn run(self) -> io::Result<()> {
-        let mut reader = crossterm::event::EventStream::new();
-
-        let tick_rate = Duration::from_secs_f64(1.0 / TICK_FPS);
+        let tick_rate = Duration::from_secs_f64(1.0 / self.fps);
         let mut tick = tokio::time::interval(tick_rate);
 
         let millisec = Duration::from_millis(TIMER_TICK);
         let mut clock_tick = tokio::time::interval(millisec);
+        // The clock catches up on its own, late ticks needn't
+        clock_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
         loop {
             let clock_tick_delay = clock_tick.tick();
             let tick_delay = tick.tick();
-            let crossterm_event = reader.next().fuse();
             tokio::select! { _ = self.sender.closed() => {
                 break;
               }
@@ -103,9 +124,6 @@ impl EventTask {
               _ = clock_tick_delay => {
                 self.send(Event::TimerTick);
               }
-              Some(Ok(evt)) = crossterm_event => {
-                  self.send(Event::Crossterm(evt)); // evt: crossterm::event::Event
-              }
             };
         }
         Ok(())
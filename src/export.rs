@@ -0,0 +1,79 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use serde::Serialize;
+
+use crate::{clock::MoveTime, date, paths};
+
+/// Format of the move times written by [`write`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// One line per move, the times in seconds, for spreadsheets.
+    Csv,
+    /// An array of moves, the times in milliseconds.
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+struct Row<'a> {
+    move_number: u32,
+    player: &'a str,
+    think_ms: u64,
+    remaining_ms: u64,
+}
+
+/// Directory the move times are written to.
+pub fn dir() -> PathBuf {
+    paths::data_dir().join("exports")
+}
+
+/// Writes the time each move of the game took and the time left after it to a new file,
+/// `names` being the names of Player 1 and Player 2. Returns the path of the file.
+pub fn write(times: &[MoveTime], names: &[String; 2], format: Format) -> anyhow::Result<PathBuf> {
+    let rows: Vec<_> = times
+        .iter()
+        .map(|time| Row {
+            move_number: time.move_number,
+            player: &names[time.player.index()],
+            think_ms: time.time.as_millis() as u64,
+            remaining_ms: time.left.as_millis() as u64,
+        })
+        .collect();
+    let (content, extension) = match format {
+        Format::Csv => (csv(&rows), "csv"),
+        Format::Json => (serde_json::to_string_pretty(&rows)? + "\n", "json"),
+    };
+    let now = date::now();
+    let name = format!(
+        "moves-{}-{}.{extension}",
+        date::format(now, '-'),
+        date::time_of_day(now).replace(':', "")
+    );
+    fs::create_dir_all(dir())?;
+    let path = dir().join(name);
+    fs::write(&path, content)?;
+    Ok(path)
+}
+
+fn csv(rows: &[Row]) -> String {
+    let secs = |ms: u64| format!("{:.3}", Duration::from_millis(ms).as_secs_f64());
+    let mut csv = String::from("move,player,think_seconds,remaining_seconds\n");
+    for row in rows {
+        csv += &format!(
+            "{},{},{},{}\n",
+            row.move_number,
+            field(row.player),
+            secs(row.think_ms),
+            secs(row.remaining_ms)
+        );
+    }
+    csv
+}
+
+/// `text` as a CSV field, quoted when it holds a comma, a quote or a line break.
+fn field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
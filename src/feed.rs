@@ -0,0 +1,110 @@
+use std::{collections::VecDeque, time::Duration};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph, Widget},
+};
+
+use crate::{
+    clock::{ClockEvent, Player, Snapshot, Time},
+    i18n::{self, tr, trf},
+};
+
+/// Entries kept on the panel, older ones are dropped.
+const MAX_ENTRIES: usize = 100;
+
+/// The latest events of the game, newest first, for arbiters and spectators.
+#[derive(Debug, Clone, Default)]
+pub struct EventFeed {
+    entries: VecDeque<Line<'static>>,
+    /// Time of each player when their turn began, to tell how long their move took.
+    turn_start_ms: [u64; 2],
+}
+
+impl EventFeed {
+    pub fn push(&mut self, entry: Line<'static>) {
+        self.entries.push_front(entry);
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    /// Adds the events that happened between the `prev` and `next` states of the clock.
+    pub fn clock_changed(&mut self, prev: &Snapshot, next: &Snapshot) {
+        for event in ClockEvent::between(prev, next) {
+            let entry = match event {
+                ClockEvent::NewGame { time_control } => {
+                    self.entries.clear();
+                    Line::from(trf("new game {}", &[&time_control]).dark_gray())
+                }
+                ClockEvent::Start { player } => {
+                    self.turn_start_ms[player.index()] = ms(next, player);
+                    Line::from(trf("{} started the clock", &[&i18n::player(player)]))
+                }
+                ClockEvent::Hit {
+                    player,
+                    move_number,
+                } => {
+                    let opponent = player.opponent();
+                    self.turn_start_ms[opponent.index()] = ms(next, opponent);
+                    let used = self.turn_start_ms[player.index()].saturating_sub(ms(prev, player));
+                    let used = Time(Duration::from_millis(used));
+                    Line::from(trf(
+                        "{}. {} moved in {}",
+                        &[&move_number, &i18n::player(player), &used],
+                    ))
+                }
+                ClockEvent::Pause => Line::from(tr("paused").fg(Color::LightYellow)),
+                ClockEvent::Resume => Line::from(tr("resumed").fg(Color::LightYellow)),
+                ClockEvent::Flag { player } => {
+                    Line::from(trf("{} flagged", &[&i18n::player(player)]).fg(Color::LightRed))
+                }
+                ClockEvent::End { outcome } => {
+                    Line::from(i18n::outcome(outcome).fg(Color::LightRed))
+                }
+            };
+            self.push(entry);
+        }
+    }
+
+    /// Adds a change of `player`'s time by the arbiter or a correction.
+    pub fn adjusted(&mut self, player: Player, delta_ms: i64) {
+        // The time of the move being played doesn't include the change
+        let start = &mut self.turn_start_ms[player.index()];
+        *start = start.saturating_add_signed(delta_ms);
+
+        let ms = delta_ms.unsigned_abs();
+        let delta = if ms.is_multiple_of(1000) {
+            format!("{}s", ms / 1000)
+        } else {
+            format!("{:.1}s", ms as f64 / 1000.0)
+        };
+        let player = i18n::player(player);
+        let entry = if delta_ms < 0 {
+            trf("{} penalised -{}", &[&player, &delta]).fg(Color::LightRed)
+        } else {
+            trf("{} given +{}", &[&player, &delta]).fg(Color::LightCyan)
+        };
+        self.push(Line::from(entry));
+    }
+}
+
+fn ms(snapshot: &Snapshot, player: Player) -> u64 {
+    match player {
+        Player::Player1 => snapshot.player1_ms,
+        Player::Player2 => snapshot.player2_ms,
+    }
+}
+
+impl Widget for &EventFeed {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::DarkGray)
+            .title(format!(" {} ", tr("Events")));
+        Paragraph::new(Vec::from(self.entries.clone()))
+            .block(block)
+            .render(area, buf);
+    }
+}
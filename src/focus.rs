@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::Widget,
+};
+
+use crate::{
+    config::FocusConfig,
+    i18n::{tr, trf},
+    round,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum Phase {
+    #[default]
+    Work,
+    Break,
+}
+
+/// A single timer alternating work and break periods, for study sessions between games.
+#[derive(Debug, Clone, Default)]
+pub struct FocusTimer {
+    work: Duration,
+    rest: Duration,
+    phase: Phase,
+    remaining: Duration,
+    running: bool,
+    /// Work periods completed.
+    sessions: u32,
+    /// Last update by [`FocusTimer::tick_timer`].
+    ticked_at: Option<Instant>,
+}
+
+impl FocusTimer {
+    pub fn new(config: &FocusConfig) -> Self {
+        let work = Duration::from_secs(config.work_minutes * 60);
+        Self {
+            work,
+            rest: Duration::from_secs(config.break_minutes * 60),
+            remaining: work,
+            ..Self::default()
+        }
+    }
+
+    /// Label of the timer in the time control picker, e.g. `Focus 25+5`.
+    pub fn label(config: &FocusConfig) -> String {
+        trf(
+            "Focus {}+{}",
+            &[&config.work_minutes, &config.break_minutes],
+        )
+    }
+
+    pub fn toggle(&mut self) {
+        self.running = !self.running;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Ends the current period early.
+    pub fn skip(&mut self) {
+        self.remaining = Duration::ZERO;
+        self.next_phase();
+    }
+
+    /// Counts down the time elapsed since the last tick, so the timer keeps to wall time
+    /// however late the ticks come.
+    pub fn tick_timer(&mut self) {
+        let now = Instant::now();
+        let elapsed = self.ticked_at.map_or(Duration::ZERO, |at| now - at);
+        self.ticked_at = Some(now);
+        if !self.running {
+            return;
+        }
+        self.remaining = self.remaining.saturating_sub(elapsed);
+        if self.remaining == Duration::ZERO {
+            self.next_phase();
+        }
+    }
+
+    /// A break starts right after work, the next work period waits to be started.
+    fn next_phase(&mut self) {
+        (self.phase, self.remaining) = match self.phase {
+            Phase::Work => {
+                self.sessions += 1;
+                (Phase::Break, self.rest)
+            }
+            Phase::Break => {
+                self.running = false;
+                (Phase::Work, self.work)
+            }
+        };
+    }
+}
+
+impl Widget for &FocusTimer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let hint = match (self.running, self.phase) {
+            (true, _) => tr("Hit <space> to pause, 's' to skip"),
+            (false, Phase::Work) => tr("Hit <space> to start working"),
+            (false, Phase::Break) => tr("Pause. Hit <space> to resume"),
+        };
+        let color = match self.phase {
+            Phase::Work => Color::LightGreen,
+            Phase::Break => Color::LightCyan,
+        };
+        let style = if self.running {
+            Style::default().fg(color)
+        } else {
+            Style::default().fg(Color::from_u32(0x003f3f3f))
+        };
+        let phase = match self.phase {
+            Phase::Work => trf("Work, session {}", &[&(self.sessions + 1)]),
+            Phase::Break if self.sessions == 1 => tr("Break, 1 session done").to_string(),
+            Phase::Break => trf("Break, {} sessions done", &[&self.sessions]),
+        };
+        let caption = Line::from(format!(" {phase} ").fg(color).bold());
+        round::render_countdown(self.remaining, style, Some(caption), hint, area, buf);
+    }
+}
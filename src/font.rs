@@ -0,0 +1,151 @@
+//! The big glyphs the clocks are drawn with, from the fonts in `src/font/` or from files
+//! named the same way.
+
+use std::borrow::Cow;
+
+use serde::Deserialize;
+
+use crate::i18n::Numerals;
+
+/// The characters of a font, in the order of its glyphs.
+const CHARS: [char; 12] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.', ':'];
+/// The files of the glyphs of `0` to `9`, `.` and `:` in the directory of a font, without
+/// `.txt`.
+pub const FILES: [&str; 12] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "dot", "colon",
+];
+
+/// The font in `src/font/$dir`, one file per character.
+macro_rules! bundled {
+    ($dir:literal, numerals: $numerals:literal) => {
+        Font {
+            glyphs: [
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "0.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "1.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "2.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "3.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "4.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "5.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "6.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "7.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "8.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "9.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "dot.txt"))),
+                Cow::Borrowed(include_str!(concat!("font/", $dir, "colon.txt"))),
+            ],
+            numerals: $numerals,
+        }
+    };
+}
+
+static DEFAULT: Font = bundled!("", numerals: true);
+static SLIM: Font = bundled!("slim/", numerals: false);
+static BLOCK: Font = bundled!("block/", numerals: false);
+static SEVEN_SEGMENT: Font = bundled!("7-segment/", numerals: false);
+
+/// Big glyphs of the digits and of the separators `.` and `:`, all of the same height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Font {
+    glyphs: [Cow<'static, str>; 12],
+    // Whether the digits of the numerals of the language replace the Latin ones
+    numerals: bool,
+}
+
+impl Font {
+    /// A font of the glyphs of [`FILES`], in their order.
+    ///
+    /// Glyphs of different heights would draw a time off its row, they are an error.
+    pub fn new(glyphs: [String; 12]) -> Result<Self, String> {
+        let height = glyphs[0].lines().count();
+        if height == 0 {
+            return Err("the glyph of '0' is empty".to_string());
+        }
+        for (c, glyph) in CHARS.iter().zip(&glyphs) {
+            let rows = glyph.lines().count();
+            if rows != height {
+                return Err(format!(
+                    "the glyph of '{c}' is {rows} rows high, the one of '0' {height}"
+                ));
+            }
+        }
+        Ok(Self {
+            glyphs: glyphs.map(Cow::Owned),
+            numerals: false,
+        })
+    }
+
+    /// The glyph of a digit or separator.
+    ///
+    /// # Panics
+    ///
+    /// If `c` is none of them.
+    pub fn render(&self, c: char) -> &str {
+        // The glyphs of 0 to 9, separated by empty lines
+        let digits = match Numerals::current() {
+            _ if !self.numerals => None,
+            Numerals::Latin => None,
+            Numerals::EasternArabic => Some(include_str!("font/eastern-arabic.txt")),
+            Numerals::Devanagari => Some(include_str!("font/devanagari.txt")),
+        };
+        let digit = digits
+            .zip(c.to_digit(10))
+            .and_then(|(digits, d)| digits.split("\n\n").nth(d as usize));
+        if let Some(glyph) = digit {
+            return glyph;
+        }
+        match CHARS.iter().position(|&other| other == c) {
+            Some(i) => &self.glyphs[i],
+            None => unreachable!("no glyph for {c:?}"),
+        }
+    }
+
+    /// The rows of the glyphs.
+    pub fn height(&self) -> usize {
+        self.glyphs[0].lines().count()
+    }
+
+    /// `text` in big glyphs side by side, e.g. a [`Time`](crate::clock::Time).
+    pub fn text(&self, text: &str) -> String {
+        let glyphs: Vec<Vec<&str>> = text
+            .chars()
+            .map(|c| self.render(c).lines().collect())
+            .collect();
+        let lines: Vec<_> = (0..self.height())
+            .map(|i| {
+                let row: Vec<_> = glyphs
+                    .iter()
+                    .map(|glyph| glyph.get(i).copied().unwrap_or_default())
+                    .collect();
+                row.join(" ")
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+/// The bundled fonts, picked by name in the config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FontName {
+    /// Half blocks, in the numerals of the language.
+    #[default]
+    Default,
+    /// Thin lines.
+    Slim,
+    /// Full blocks, five rows high.
+    Block,
+    /// The segments of a digital clock.
+    #[serde(rename = "7-segment")]
+    SevenSegment,
+}
+
+impl FontName {
+    pub fn font(self) -> &'static Font {
+        match self {
+            FontName::Default => &DEFAULT,
+            FontName::Slim => &SLIM,
+            FontName::Block => &BLOCK,
+            FontName::SevenSegment => &SEVEN_SEGMENT,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, List, ListState, Padding, Paragraph, StatefulWidget, Widget,
+    },
+};
+
+use crate::{
+    clock::Time,
+    history::GameRecord,
+    i18n::{tr, trf},
+    tabs,
+};
+
+/// Width of the details of the game picked, borders included.
+const DETAILS_WIDTH: u16 = 34;
+
+/// The finished games of the history, newest first, with the details of the one picked.
+#[derive(Debug, Clone, Default)]
+pub struct GamesView {
+    games: Vec<GameRecord>,
+    selected: usize,
+}
+
+impl GamesView {
+    /// The view of `games`, given oldest first as they are stored.
+    pub fn new(mut games: Vec<GameRecord>) -> Self {
+        games.reverse();
+        Self { games, selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) {
+        let last = self.games.len().saturating_sub(1);
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            _ => {}
+        }
+    }
+}
+
+impl Widget for &GamesView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightGreen)
+            .title(format!(" {} ", tr("Game history")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let Some(game) = self.games.get(self.selected) else {
+            Line::from(tr("No game finished yet").dark_gray())
+                .centered()
+                .render(inner, buf);
+            return;
+        };
+
+        let [list, details_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(DETAILS_WIDTH)])
+                .areas(inner);
+        let list_widget = List::new(self.games.iter().map(GameRecord::summary))
+            .highlight_style(Style::default().fg(Color::LightGreen).bold())
+            .highlight_symbol("> ");
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        StatefulWidget::render(list_widget, list, buf, &mut state);
+        details(game).render(details_area, buf);
+    }
+}
+
+/// The players, result and length of `game`, then the time each move took.
+fn details(game: &GameRecord) -> Paragraph<'_> {
+    let ctrl =
+        tabs::parse_pgn(&game.time_control).map_or_else(|| game.time_control.clone(), tabs::label);
+    let mut lines = vec![
+        Line::from(format!("{} - {}", game.white, game.black).bold()),
+        Line::from(format!("{}   {ctrl}", game.result)),
+        Line::from(trf("Moves: {}", &[&game.move_count])),
+        Line::default(),
+    ];
+    // White's move first in each pair, like the moves are stored
+    for (i, pair) in game.move_times_ms.chunks(2).enumerate() {
+        let [white, black] = [pair.first(), pair.get(1)].map(|ms| {
+            ms.map(|&ms| Time(Duration::from_millis(ms)).to_string())
+                .unwrap_or_default()
+        });
+        lines.push(Line::from(format!("{:>3}. {white:<9}{black}", i + 1)));
+    }
+    let block = Block::new()
+        .borders(Borders::LEFT)
+        .border_style(Color::DarkGray)
+        .padding(Padding::horizontal(1));
+    Paragraph::new(lines).block(block)
+}
@@ -0,0 +1,136 @@
+use std::{net::SocketAddr, pin::Pin, time::Duration};
+
+use anyhow::Context;
+use futures::{Stream, stream};
+use tokio::time::MissedTickBehavior;
+use tonic::{Request, Response, Status, transport::Server, transport::server::TcpIncoming};
+
+use crate::{
+    clock::{Player, Snapshot},
+    command::{self, Command},
+    event::Event,
+    remote::Remote,
+};
+
+mod proto {
+    tonic::include_proto!("chessclock.v1");
+}
+
+use proto::{
+    Ack, AdjustRequest, GetStateRequest, HitRequest, PauseRequest, SetTimeControlRequest, State,
+    WatchStateRequest,
+    clock_server::{Clock, ClockServer},
+};
+
+/// Default interval between two states streamed by `WatchState`.
+const WATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Serves the `chessclock.v1.Clock` service of `proto/chessclock.proto` on `addr`.
+pub async fn serve(addr: SocketAddr, remote: Remote) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("could not listen on {addr}"))?;
+    let server = Server::builder().add_service(ClockServer::new(Service { remote }));
+    tokio::spawn(server.serve_with_incoming(TcpIncoming::from(listener)));
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Service {
+    remote: Remote,
+}
+
+impl Service {
+    fn send(&self, command: Command) -> Result<Response<Ack>, Status> {
+        self.remote
+            .events
+            .send(Event::Command(command))
+            .map_err(|_| Status::unavailable("the clock is shutting down"))?;
+        Ok(Response::new(Ack {}))
+    }
+}
+
+#[tonic::async_trait]
+impl Clock for Service {
+    type WatchStateStream = Pin<Box<dyn Stream<Item = Result<State, Status>> + Send>>;
+
+    async fn get_state(&self, _: Request<GetStateRequest>) -> Result<Response<State>, Status> {
+        Ok(Response::new(state(&self.remote.state.borrow())))
+    }
+
+    async fn watch_state(
+        &self,
+        request: Request<WatchStateRequest>,
+    ) -> Result<Response<Self::WatchStateStream>, Status> {
+        let interval = match request.into_inner().interval_ms {
+            0 => WATCH_INTERVAL,
+            ms => Duration::from_millis(ms.max(10).into()),
+        };
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut receiver = self.remote.state.clone();
+        receiver.mark_changed();
+        let states = stream::unfold((receiver, ticker), |(mut receiver, mut ticker)| async {
+            ticker.tick().await;
+            receiver.changed().await.ok()?;
+            let state = state(&receiver.borrow_and_update());
+            Some((Ok(state), (receiver, ticker)))
+        });
+        Ok(Response::new(Box::pin(states)))
+    }
+
+    async fn hit(&self, request: Request<HitRequest>) -> Result<Response<Ack>, Status> {
+        self.send(Command::Hit(player(request.into_inner().player)))
+    }
+
+    async fn pause(&self, _: Request<PauseRequest>) -> Result<Response<Ack>, Status> {
+        self.send(Command::Pause)
+    }
+
+    async fn adjust(&self, request: Request<AdjustRequest>) -> Result<Response<Ack>, Status> {
+        let request = request.into_inner();
+        let player = player(request.player)
+            .ok_or_else(|| Status::invalid_argument("the player is missing"))?;
+        self.send(Command::Adjust(player, request.delta_ms))
+    }
+
+    async fn set_time_control(
+        &self,
+        request: Request<SetTimeControlRequest>,
+    ) -> Result<Response<Ack>, Status> {
+        let command = command::parse_set(&request.into_inner().time_control)
+            .map_err(Status::invalid_argument)?;
+        self.send(command)
+    }
+}
+
+fn state(snapshot: &Snapshot) -> State {
+    State {
+        running: snapshot.running,
+        paused: snapshot.paused,
+        turn: proto_player(snapshot.turn),
+        player1_ms: snapshot.player1_ms,
+        player2_ms: snapshot.player2_ms,
+        move_number: snapshot.move_number,
+        flagged: proto_player(snapshot.flagged),
+        white: proto_player(Some(snapshot.white)),
+        time_control: snapshot.time_control.clone(),
+    }
+}
+
+fn proto_player(player: Option<Player>) -> i32 {
+    let player = match player {
+        Some(Player::Player1) => proto::Player::Player1,
+        Some(Player::Player2) => proto::Player::Player2,
+        None => proto::Player::Unspecified,
+    };
+    player.into()
+}
+
+fn player(player: i32) -> Option<Player> {
+    match proto::Player::try_from(player) {
+        Ok(proto::Player::Player1) => Some(Player::Player1),
+        Ok(proto::Player::Player2) => Some(Player::Player2),
+        _ => None,
+    }
+}
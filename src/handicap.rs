@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use crate::{
+    clock::{Player, Snapshot},
+    config::HandicapConfig,
+};
+
+/// Time odds for a rating difference: the stronger player gets a share of the weaker
+/// player's time, see [`share`].
+pub fn suggest(config: &HandicapConfig, snapshot: &Snapshot, ratings: [u32; 2]) -> Suggestion {
+    let (stronger, percent) = share(config, ratings);
+    let weaker_ms = match stronger {
+        Player::Player1 => snapshot.player2_ms,
+        Player::Player2 => snapshot.player1_ms,
+    };
+    Suggestion {
+        stronger,
+        time: Duration::from_millis((weaker_ms as f64 * percent / 100.0) as u64),
+    }
+}
+
+/// The stronger player and the percent of the weaker player's time they get, reduced by
+/// `percent_per_100` for every 100 points between them and never below `min_percent`.
+pub fn share(config: &HandicapConfig, ratings: [u32; 2]) -> (Player, f64) {
+    let stronger = if ratings[0] >= ratings[1] {
+        Player::Player1
+    } else {
+        Player::Player2
+    };
+    let gap = ratings[0].abs_diff(ratings[1]) as f64;
+    let percent = (100.0 - config.percent_per_100 * gap / 100.0).max(config.min_percent);
+    (stronger, percent)
+}
+
+/// Times suggested for a game between players of different strength.
+#[derive(Debug, Clone, Copy)]
+pub struct Suggestion {
+    pub stronger: Player,
+    /// Time of the stronger player.
+    pub time: Duration,
+}
+
+impl Suggestion {
+    /// Milliseconds to add to the stronger player's current time in `snapshot`.
+    pub fn delta_ms(&self, snapshot: &Snapshot) -> i64 {
+        let current = match self.stronger {
+            Player::Player1 => snapshot.player1_ms,
+            Player::Player2 => snapshot.player2_ms,
+        };
+        self.time.as_millis() as i64 - current as i64
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::{Duration, Instant};
+
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    sync::mpsc,
+};
+
+use crate::{
+    clock::{Clock, Snapshot, TIMER_TICK},
+    command::Command,
+    event::Event,
+    remote::{Publisher, RemoteArgs},
+    tabs::TimeCtrl,
+};
+
+/// How state updates are written to stdout.
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    /// One JSON object per line.
+    Json,
+    /// One UCI `go` command with the times per line.
+    Uci,
+}
+
+/// Runs the clock without a TUI.
+///
+/// Commands are read from stdin, one per line, and state updates are written to stdout in
+/// `format` until stdin is closed. An update is emitted right after every command
+/// and otherwise at most once per `interval` while the clock is running. Commands are accepted
+/// from the servers requested in `remote_args` as well.
+pub async fn run(
+    ctrl: TimeCtrl,
+    interval: Duration,
+    format: Format,
+    remote_args: &RemoteArgs,
+) -> anyhow::Result<()> {
+    let mut clock = Clock::default();
+    clock.set_preset(ctrl);
+
+    let (events, mut remote) = mpsc::unbounded_channel();
+    let publisher = Publisher::new(clock.snapshot());
+    let _servers = remote_args.start(&publisher.remote(events)).await?;
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut timer = tokio::time::interval(Duration::from_millis(TIMER_TICK));
+    let mut last = clock.snapshot();
+    emit(&last, format)?;
+    let mut last_emit = Instant::now();
+    loop {
+        let command = tokio::select! {
+            _ = timer.tick() => {
+                clock.tick_timer();
+                None
+            }
+            line = lines.next_line() => {
+                let Some(line) = line? else { break };
+                line.parse::<Command>()
+                    .inspect_err(|e| eprintln!("{e}"))
+                    .ok()
+            }
+            Some(Event::Command(command)) = remote.recv() => Some(command),
+        };
+
+        let forced = command.is_some();
+        match command {
+            Some(Command::Hit(None) | Command::Move(_)) => clock.hit(),
+            Some(Command::Hit(Some(player))) => clock.hit_by(player),
+            Some(Command::Pause) => clock.pause(clock.curr_player().unwrap_or_default()),
+            Some(Command::First(player)) => clock.set_first_to_move(player),
+            Some(Command::Adjust(player, delta_ms)) => clock.adjust(player, delta_ms),
+            Some(Command::Set(ctrl)) => clock.set_preset(ctrl),
+            Some(Command::SetCustom(ctrl)) => clock.set(ctrl),
+            Some(Command::Quit) => break,
+            None => {}
+        }
+
+        let snapshot = clock.snapshot();
+        publisher.publish(snapshot.clone());
+        if forced || (last_emit.elapsed() >= interval && snapshot != last) {
+            emit(&snapshot, format)?;
+            last = snapshot;
+            last_emit = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+fn emit(snapshot: &Snapshot, format: Format) -> anyhow::Result<()> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string(snapshot)?),
+        Format::Uci => println!("{}", snapshot.uci()),
+    }
+    Ok(())
+}
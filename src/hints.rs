@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use crate::i18n::tr;
+
+/// Time the bar stays once the game is under way.
+const SHOWN_FOR: Duration = Duration::from_secs(5);
+/// Last part of [`SHOWN_FOR`] during which the bar is dimmed before it goes.
+const FADE: Duration = Duration::from_secs(1);
+
+/// A bar with the keys of the current screen, hidden a few seconds into the game for a clean
+/// look and shown again on demand.
+#[derive(Debug)]
+pub struct HintBar {
+    shown_at: Instant,
+    /// Whether the bar is dimmed before it goes.
+    pub fade: bool,
+}
+
+impl Default for HintBar {
+    fn default() -> Self {
+        Self {
+            shown_at: Instant::now(),
+            fade: true,
+        }
+    }
+}
+
+impl HintBar {
+    pub fn show(&mut self) {
+        self.shown_at = Instant::now();
+    }
+
+    /// Keeps the bar up while nothing is `playing`, the countdown starts with the game.
+    pub fn update(&mut self, playing: bool) {
+        if !playing {
+            self.show();
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.shown_at.elapsed() < SHOWN_FOR
+    }
+
+    /// The bar listing `keys`, each a key and what it does.
+    fn line(&self, keys: &[(String, &str)]) -> Line<'static> {
+        let fading = self.fade && self.shown_at.elapsed() > SHOWN_FOR - FADE;
+        let (key_color, text_color) = if fading {
+            (Color::DarkGray, Color::DarkGray)
+        } else {
+            (Color::LightGreen, Color::Gray)
+        };
+        let mut spans = vec![];
+        for (key, action) in keys {
+            spans.push(Span::from(format!(" {key} ")).fg(key_color).bold());
+            spans.push(Span::from(format!("{}  ", tr(action))).fg(text_color));
+        }
+        Line::from(spans).centered()
+    }
+
+    pub fn render(&self, keys: &[(String, &str)], area: Rect, buf: &mut Buffer) {
+        if self.is_visible() {
+            self.line(keys).render(area, buf);
+        }
+    }
+}
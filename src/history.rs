@@ -0,0 +1,300 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::{clock::Player, config::HistoryConfig, date, paths};
+
+/// Tournament details attached to a game.
+#[derive(Debug, Clone, Default, PartialEq, Args, Serialize, Deserialize)]
+pub struct GameMeta {
+    /// Name of the event or tournament
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    /// Round of the event
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round: Option<String>,
+    /// Board number
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<String>,
+}
+
+impl GameMeta {
+    /// Whether every field set in `filter` matches this game.
+    pub fn matches(&self, filter: &GameMeta) -> bool {
+        fn field(value: &Option<String>, wanted: &Option<String>) -> bool {
+            wanted.is_none() || value == wanted
+        }
+        field(&self.event, &filter.event)
+            && field(&self.round, &filter.round)
+            && field(&self.board, &filter.board)
+    }
+}
+
+/// A finished game as stored in the history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// Unix timestamp of the end of the game.
+    pub finished_at: u64,
+    /// Time control in PGN notation, e.g. `180+2`.
+    pub time_control: String,
+    pub white: String,
+    pub black: String,
+    /// Result in PGN notation, e.g. `1-0`.
+    pub result: String,
+    #[serde(default)]
+    pub meta: GameMeta,
+    /// Events of the game besides the moves, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log: Vec<LogEntry>,
+    /// The moves of both players in order, white's first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub moves: Vec<MoveRecord>,
+    /// Moves played, a move of White alone counting as one.
+    #[serde(default)]
+    pub move_count: u32,
+    /// Time each move took in milliseconds, in order, white's first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub move_times_ms: Vec<u64>,
+}
+
+/// A move and the clock of the player who made it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveRecord {
+    /// Time left to the player after the move, increment included.
+    pub clock_ms: u64,
+    /// The move in SAN, when it was typed by a scorer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub san: Option<String>,
+}
+
+/// An entry of the game event log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Unix timestamp.
+    pub at: u64,
+    pub move_number: u32,
+    pub player: Player,
+    pub event: GameEvent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, strum::Display, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameEvent {
+    #[strum(to_string = "offered a draw")]
+    DrawOffered,
+    #[strum(to_string = "retracted the draw offer")]
+    DrawRetracted,
+    /// The opponent moved instead of accepting the offer.
+    #[strum(to_string = "declined the draw offer")]
+    DrawDeclined,
+    #[strum(to_string = "accepted the draw offer")]
+    DrawAccepted,
+    #[strum(to_string = "resigned")]
+    Resigned,
+}
+
+impl std::fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} move {}: {} {}",
+            date::time_of_day(self.at),
+            self.move_number,
+            self.player,
+            self.event
+        )
+    }
+}
+
+impl GameRecord {
+    /// Renders the game as a PGN skeleton with the seven tag roster.
+    pub fn pgn(&self) -> String {
+        let tag = |name: &str, value: Option<&str>| {
+            format!("[{name} \"{}\"]\n", value.unwrap_or("?").replace('"', "\\\""))
+        };
+        let mut pgn = String::new();
+        pgn += &tag("Event", self.meta.event.as_deref());
+        pgn += &tag("Site", None);
+        pgn += &tag("Date", Some(&date::format(self.finished_at, '.')));
+        pgn += &tag("Round", self.meta.round.as_deref());
+        pgn += &tag("White", Some(&self.white));
+        pgn += &tag("Black", Some(&self.black));
+        pgn += &tag("Result", Some(&self.result));
+        if let Some(board) = &self.meta.board {
+            pgn += &tag("Board", Some(board));
+        }
+        pgn += &tag("TimeControl", Some(&self.time_control));
+        pgn.push('\n');
+        for entry in &self.log {
+            pgn += &format!("{{{entry}}}\n");
+        }
+        pgn += &self.movetext();
+        pgn += &format!("{}\n", self.result);
+        pgn
+    }
+
+    /// The moves with their clocks like lichess writes them, e.g.
+    /// `1. e4 { [%clk 0:02:58] } 1... e5 { [%clk 0:02:59] }`. Moves that weren't scored are
+    /// null moves, `--`, to be replaced by the real ones later.
+    fn movetext(&self) -> String {
+        let mut movetext = String::new();
+        for (i, record) in self.moves.iter().enumerate() {
+            // The comment before Black's move hides the number of the move
+            let number = if i % 2 == 0 { "." } else { "..." };
+            let san = record.san.as_deref().unwrap_or("--");
+            let secs = record.clock_ms / 1000;
+            movetext += &format!(
+                "{}{number} {san} {{ [%clk {}:{:02}:{:02}] }} ",
+                i / 2 + 1,
+                secs / 3600,
+                secs % 3600 / 60,
+                secs % 60
+            );
+        }
+        movetext
+    }
+
+    /// One line summary used by `chessclock history`.
+    pub fn summary(&self) -> String {
+        let meta = [&self.meta.event, &self.meta.round, &self.meta.board]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" / ");
+        format!(
+            "{}  {:>7}  {} - {}  {:<7}  {}",
+            date::format(self.finished_at, '-'),
+            self.time_control,
+            self.white,
+            self.black,
+            self.result,
+            meta
+        )
+    }
+}
+
+pub fn path() -> PathBuf {
+    paths::data_dir().join("history.jsonl")
+}
+
+/// Appends a finished game to the history file.
+pub fn append(record: &GameRecord) -> anyhow::Result<()> {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// The lines of the history file, none if there is no file yet.
+fn read() -> anyhow::Result<String> {
+    match fs::read_to_string(path()) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Loads all stored games, oldest first. Lines that fail to parse are skipped.
+pub fn load() -> anyhow::Result<Vec<GameRecord>> {
+    Ok(read()?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Rewrites the history file with `content`.
+fn write(content: &str) -> anyhow::Result<()> {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Write to a temporary file first so an interrupted write can't lose the history
+    let tmp = path.with_extension("jsonl.tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+/// Which games to drop from the history.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Retention {
+    /// Keep only this many most recent games.
+    pub keep_last: Option<usize>,
+    /// Drop games finished before this Unix timestamp.
+    pub before: Option<u64>,
+}
+
+impl From<&HistoryConfig> for Retention {
+    fn from(config: &HistoryConfig) -> Self {
+        Retention {
+            keep_last: config.keep_last,
+            before: config
+                .max_age_days
+                .map(|days| date::now().saturating_sub(days * 86_400)),
+        }
+    }
+}
+
+/// Applies `retention` to the stored history, returning the number of removed games.
+pub fn prune(retention: Retention) -> anyhow::Result<usize> {
+    let content = read()?;
+    // Lines that fail to parse, e.g. written by a newer version, are kept as they are
+    let mut lines: Vec<(&str, Option<u64>)> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let record = serde_json::from_str::<GameRecord>(line).ok();
+            (line, record.map(|r| r.finished_at))
+        })
+        .collect();
+    let total = lines.len();
+    if let Some(before) = retention.before {
+        lines.retain(|(_, finished_at)| finished_at.is_none_or(|at| at >= before));
+    }
+    if let Some(keep) = retention.keep_last {
+        let games = lines.iter().filter(|(_, at)| at.is_some()).count();
+        let mut excess = games.saturating_sub(keep);
+        lines.retain(|(_, finished_at)| {
+            let oldest = excess > 0 && finished_at.is_some();
+            excess -= usize::from(oldest);
+            !oldest
+        });
+    }
+
+    let removed = total - lines.len();
+    if removed > 0 {
+        let kept: String = lines.iter().map(|(line, _)| format!("{line}\n")).collect();
+        write(&kept)?;
+    }
+    Ok(removed)
+}
+
+/// Prints stored games matching `filter`, either as a list, with their event logs if `log`
+/// is set, or as PGN.
+pub fn print(filter: &GameMeta, pgn: bool, log: bool) -> anyhow::Result<()> {
+    for record in load()?.iter().filter(|r| r.meta.matches(filter)) {
+        if pgn {
+            println!("{}", record.pgn());
+            continue;
+        }
+        println!("{}", record.summary());
+        if log {
+            for entry in &record.log {
+                println!("    {entry}");
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Context;
+use axum::{
+    Json, Router,
+    extract::{
+        Path, Query, Request, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    clock::{ClockEvent, Snapshot},
+    command::Command,
+    event::Event,
+    metrics::Metrics,
+    remote::Remote,
+};
+
+/// Default interval between two state messages on the WebSocket.
+const WS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone)]
+struct ServerState {
+    remote: Remote,
+    token: Option<String>,
+    metrics: Arc<Mutex<Metrics>>,
+}
+
+/// Serves the HTTP API on `addr`.
+///
+/// `GET /state` returns the clock state as JSON, while any [`Command`] can be posted with its
+/// words as path segments: `POST /hit/1`, `POST /pause`, `POST /adjust/2/-30`, `POST /set/3+2`.
+/// `GET /ws` upgrades to a WebSocket streaming state and events, see [`stream`], and `GET /`
+/// serves a spectator page showing both clocks. `GET /metrics` exposes Prometheus metrics.
+/// When `token` is set, requests must carry it as a bearer token or a `token` query parameter.
+pub fn serve(addr: SocketAddr, token: Option<String>, remote: Remote) -> anyhow::Result<()> {
+    let listener =
+        std::net::TcpListener::bind(addr).with_context(|| format!("could not listen on {addr}"))?;
+    listener.set_nonblocking(true)?;
+    let listener = tokio::net::TcpListener::from_std(listener)?;
+
+    let state = ServerState {
+        metrics: Metrics::collect(&remote),
+        remote,
+        token,
+    };
+    let router = Router::new()
+        .route("/", get(spectator_page))
+        .route("/state", get(get_state))
+        .route("/ws", get(ws))
+        .route("/metrics", get(metrics))
+        .route("/{*command}", post(post_command))
+        .layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .with_state(state);
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    Ok(())
+}
+
+async fn spectator_page() -> Html<&'static str> {
+    Html(include_str!("web/index.html"))
+}
+
+async fn get_state(State(state): State<ServerState>) -> Json<Snapshot> {
+    Json(state.remote.state.borrow().clone())
+}
+
+async fn metrics(State(state): State<ServerState>) -> Response {
+    let snapshot = state.remote.state.borrow().clone();
+    let body = state.metrics.lock().unwrap().render(&snapshot);
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        body,
+    )
+        .into_response()
+}
+
+async fn post_command(State(state): State<ServerState>, Path(command): Path<String>) -> Response {
+    match command.replace('/', " ").parse::<Command>() {
+        Ok(command) => {
+            let _ = state.remote.events.send(Event::Command(command));
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WsQuery {
+    /// Interval between state messages in milliseconds.
+    interval: Option<u64>,
+}
+
+/// Message sent over the WebSocket.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "lowercase")]
+enum WsMessage {
+    State(Snapshot),
+    Event(ClockEvent),
+}
+
+async fn ws(
+    upgrade: WebSocketUpgrade,
+    State(state): State<ServerState>,
+    Query(query): Query<WsQuery>,
+) -> Response {
+    let interval = query
+        .interval
+        .map(|ms| Duration::from_millis(ms.max(10)))
+        .unwrap_or(WS_INTERVAL);
+    upgrade.on_upgrade(move |socket| stream(socket, state.remote, interval))
+}
+
+/// Sends the state whenever it changed, at most once per `interval`, and every clock event
+/// as soon as it happens. Text messages from the client are run as commands.
+async fn stream(mut socket: WebSocket, remote: Remote, interval: Duration) {
+    let mut state = remote.state.clone();
+    state.mark_changed();
+    let mut clock_events = remote.clock_events.subscribe();
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        let message = tokio::select! {
+            _ = ticker.tick() => {
+                if !state.has_changed().unwrap_or(false) {
+                    continue;
+                }
+                WsMessage::State(state.borrow_and_update().clone())
+            }
+            event = clock_events.recv() => match event {
+                Ok(event) => WsMessage::Event(event),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            },
+            received = socket.recv() => match received {
+                Some(Ok(Message::Text(text))) => {
+                    if let Ok(command) = text.parse::<Command>() {
+                        let _ = remote.events.send(Event::Command(command));
+                    }
+                    continue;
+                }
+                Some(Ok(_)) => continue,
+                _ => break,
+            },
+        };
+        let Ok(json) = serde_json::to_string(&message) else {
+            continue;
+        };
+        if socket.send(Message::Text(json.into())).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+async fn authorize(
+    State(state): State<ServerState>,
+    Query(query): Query<TokenQuery>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(token) = &state.token else {
+        return next.run(request).await;
+    };
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if bearer == Some(token) || query.token.as_ref() == Some(token) {
+        next.run(request).await
+    } else {
+        StatusCode::UNAUTHORIZED.into_response()
+    }
+}
@@ -0,0 +1,314 @@
+//! Translations of the interface and numerals of the clocks, picked from the config or the
+//! locale.
+//!
+//! Texts are looked up by their English version, a text without a translation is shown in
+//! English. Saved data such as the history and PGN stays in English.
+
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use serde::Deserialize;
+
+use crate::clock::{Outcome, Player};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, strum::FromRepr)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    Es,
+    De,
+    Ru,
+    Fr,
+}
+
+static LANG: AtomicUsize = AtomicUsize::new(Lang::En as usize);
+
+impl Lang {
+    /// The language of the locale, see [`locale`].
+    pub fn detect() -> Self {
+        match locale().get(..2) {
+            Some("es") => Lang::Es,
+            Some("de") => Lang::De,
+            Some("ru") => Lang::Ru,
+            Some("fr") => Lang::Fr,
+            _ => Lang::En,
+        }
+    }
+
+    pub fn current() -> Self {
+        Lang::from_repr(LANG.load(Ordering::Relaxed)).unwrap_or_default()
+    }
+
+    /// Makes the interface speak this language from the next frame on.
+    pub fn set(self) {
+        LANG.store(self as usize, Ordering::Relaxed);
+    }
+}
+
+/// Digits drawn by the clocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, strum::FromRepr)]
+#[serde(rename_all = "kebab-case")]
+pub enum Numerals {
+    #[default]
+    Latin,
+    /// ٠١٢٣٤٥٦٧٨٩
+    EasternArabic,
+    /// ०१२३४५६७८९
+    Devanagari,
+}
+
+static NUMERALS: AtomicUsize = AtomicUsize::new(Numerals::Latin as usize);
+
+impl Numerals {
+    /// The numerals of the locale's language, see [`locale`].
+    pub fn detect() -> Self {
+        match locale().get(..2) {
+            Some("ar") => Numerals::EasternArabic,
+            Some("hi" | "mr" | "ne") => Numerals::Devanagari,
+            _ => Numerals::Latin,
+        }
+    }
+
+    pub fn current() -> Self {
+        Numerals::from_repr(NUMERALS.load(Ordering::Relaxed)).unwrap_or_default()
+    }
+
+    pub fn set(self) {
+        NUMERALS.store(self as usize, Ordering::Relaxed);
+    }
+}
+
+/// The locale in `LC_ALL`, `LC_MESSAGES` or `LANG`, e.g. `de_DE.UTF-8`.
+fn locale() -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|locale| !locale.is_empty())
+        .unwrap_or_default()
+}
+
+/// `text` in the current language.
+pub fn tr(text: &str) -> &str {
+    let lang = Lang::current() as usize;
+    if lang == 0 {
+        return text;
+    }
+    TEXTS
+        .iter()
+        .find(|texts| texts[0] == text)
+        .map_or(text, |texts| texts[lang])
+}
+
+/// [`tr`] with the `{}` of the translation replaced by `args` in order.
+pub fn trf(text: &str, args: &[&dyn Display]) -> String {
+    let mut parts = tr(text).split("{}");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for (part, arg) in parts.zip(args) {
+        out.push_str(&arg.to_string());
+        out.push_str(part);
+    }
+    out
+}
+
+/// The name of `player` on screen.
+pub fn player(player: Player) -> String {
+    let number = match player {
+        Player::Player1 => 1,
+        Player::Player2 => 2,
+    };
+    trf("Player {}", &[&number])
+}
+
+pub fn outcome(outcome: Outcome) -> String {
+    match outcome {
+        Outcome::Checkmate { winner } => trf("checkmate, {} wins", &[&player(winner)]),
+        Outcome::Resignation { winner } => trf("resignation, {} wins", &[&player(winner)]),
+        Outcome::Stalemate => tr("stalemate").to_string(),
+        Outcome::DrawAgreed => tr("draw agreed").to_string(),
+        Outcome::NoMatingMaterial => tr("draw, no mating material against the flag").to_string(),
+    }
+}
+
+/// Every text in English, Spanish, German, Russian and French, in the order of [`Lang`].
+#[rustfmt::skip]
+const TEXTS: &[[&str; 5]] = &[
+    // Clocks
+    ["Player {}", "Jugador {}", "Spieler {}", "Игрок {}", "Joueur {}"],
+    ["Hit <space> to start", "Pulsa <espacio> para empezar", "<Leertaste> drücken zum Starten", "Нажмите <пробел>, чтобы начать", "Appuyez sur <espace> pour commencer"],
+    ["{}. Hit <enter> to continue", "{}. Pulsa <enter> para continuar", "{}. <Enter> drücken zum Fortfahren", "{}. Нажмите <enter>, чтобы продолжить", "{}. Appuyez sur <entrée> pour continuer"],
+    ["{} lost on time. Hit <enter> to continue", "{} perdió por tiempo. Pulsa <enter> para continuar", "{} hat auf Zeit verloren. <Enter> drücken zum Fortfahren", "{}: поражение по времени. Нажмите <enter>, чтобы продолжить", "{} a perdu au temps. Appuyez sur <entrée> pour continuer"],
+    ["Time out. Hit <enter> to continue", "Tiempo agotado. Pulsa <enter> para continuar", "Zeit abgelaufen. <Enter> drücken zum Fortfahren", "Время вышло. Нажмите <enter>, чтобы продолжить", "Temps écoulé. Appuyez sur <entrée> pour continuer"],
+    ["Pause. Hit 'p' to resume", "Pausa. Pulsa 'p' para reanudar", "Pause. 'p' drücken zum Fortsetzen", "Пауза. Нажмите 'p', чтобы продолжить", "Pause. Appuyez sur 'p' pour reprendre"],
+    ["first to move", "mueve primero", "zieht zuerst", "ходит первым", "joue en premier"],
+    ["checkmate, {} wins", "jaque mate, gana {}", "Schachmatt, {} gewinnt", "мат, побеждает {}", "échec et mat, {} gagne"],
+    ["stalemate", "ahogado", "Patt", "пат", "pat"],
+    ["resignation, {} wins", "abandono, gana {}", "Aufgabe, {} gewinnt", "сдача, побеждает {}", "abandon, {} gagne"],
+    ["draw agreed", "tablas acordadas", "Remis vereinbart", "ничья по соглашению", "nulle par accord"],
+    ["Game over", "Partida terminada", "Partie beendet", "Партия окончена", "Partie terminée"],
+    ["draw, no mating material against the flag", "tablas, sin material para dar mate", "Remis, kein Material zum Mattsetzen", "ничья, нет материала для мата", "nulle, pas de matériel pour mater"],
+    ["{}: {}, to move", "{}: {}, mueve", "{}: {}, am Zug", "{}: {}, ход", "{}: {}, au trait"],
+    ["{} moves left", "quedan {} jugadas", "noch {} Züge", "осталось ходов: {}", "encore {} coups"],
+    ["hourglass", "reloj de arena", "Sanduhr", "песочные часы", "sablier"],
+    ["Hourglass {}", "Reloj de arena {}", "Sanduhr {}", "Песочные часы {}", "Sablier {}"],
+    ["delay {}s", "demora {}s", "Verzögerung {}s", "задержка {} с", "délai {}s"],
+    ["Move {}", "Jugada {}", "Zug {}", "Ход {}", "Coup {}"],
+    ["PAUSED", "EN PAUSA", "PAUSIERT", "ПАУЗА", "EN PAUSE"],
+    ["terminal too small", "terminal demasiado pequeño", "Terminal zu klein", "терминал слишком мал", "terminal trop petit"],
+    ["draw odds", "tablas ganan", "Remis gewinnt", "ничья в пользу", "nulle gagnante"],
+    ["{}, {} wins on draw odds", "{}, gana {} con las tablas", "{}, {} gewinnt durch Remis", "{}, побеждает {} благодаря ничьей", "{}, {} gagne sur la nulle"],
+    // Time control picker
+    ["Select time control", "Elige el control de tiempo", "Bedenkzeit wählen", "Выберите контроль времени", "Choisissez la cadence"],
+    ["HELP", "AYUDA", "HILFE", "СПРАВКА", "AIDE"],
+    ["pause and resume the clock", "pausar y reanudar el reloj", "Uhr anhalten und fortsetzen", "пауза и продолжение", "mettre en pause et reprendre"],
+    ["give the first move to the opponent", "ceder el primer movimiento al rival", "dem Gegner den ersten Zug geben", "отдать первый ход сопернику", "donner le premier coup à l'adversaire"],
+    ["start or hit the clock", "iniciar o pulsar el reloj", "Uhr starten oder drücken", "запустить или нажать часы", "lancer ou frapper la pendule"],
+    ["increment: {}", "incremento: {}", "Inkrement: {}", "добавление: {}", "incrément : {}"],
+    ["Custom", "Personalizado", "Eigene", "Своё", "Personnalisée"],
+    ["Players", "Jugadores", "Spieler", "Игроки", "Joueurs"],
+    ["{} to start, empty names are left out", "{} para empezar, los nombres vacíos se omiten", "{} zum Starten, leere Namen entfallen", "{} — начать, пустые имена не показываются", "{} pour commencer, les noms vides sont omis"],
+    ["Armageddon", "Armagedón", "Armageddon", "Армагеддон", "Armageddon"],
+    ["Custom time control", "Control de tiempo personalizado", "Eigene Bedenkzeit", "Свой контроль времени", "Cadence personnalisée"],
+    ["Base time: {} minutes", "Tiempo base: {} minutos", "Grundzeit: {} Minuten", "Основное время: {} мин", "Temps de base : {} minutes"],
+    ["Increment: {} seconds", "Incremento: {} segundos", "Inkrement: {} Sekunden", "Добавление: {} с", "Incrément : {} secondes"],
+    ["Type the base time in minutes", "Escribe el tiempo base en minutos", "Grundzeit in Minuten eingeben", "Введите основное время в минутах", "Saisissez le temps de base en minutes"],
+    ["The base time is at most {} minutes", "El tiempo base es de {} minutos como máximo", "Die Grundzeit beträgt höchstens {} Minuten", "Основное время не больше {} мин", "Le temps de base est d'au plus {} minutes"],
+    ["The increment is at most {} seconds", "El incremento es de {} segundos como máximo", "Das Inkrement beträgt höchstens {} Sekunden", "Добавление не больше {} с", "L'incrément est d'au plus {} secondes"],
+    ["{}, {} to start", "{}, {} para empezar", "{}, {} zum Starten", "{}, {} — начать", "{}, {} pour démarrer"],
+    ["Save as: {}", "Guardar como: {}", "Speichern als: {}", "Сохранить как: {}", "Enregistrer sous : {}"],
+    ["{}, {} to save and start", "{}, {} para guardar y empezar", "{}, {} zum Speichern und Starten", "{}, {} — сохранить и начать", "{}, {} pour enregistrer et démarrer"],
+    // Round and focus timers
+    ["Round {}", "Ronda {}", "Runde {}", "Тур {}", "Ronde {}"],
+    ["Round", "Ronda", "Runde", "Тур", "Ronde"],
+    ["Round over. Hit <enter> to continue", "Ronda terminada. Pulsa <enter> para continuar", "Runde vorbei. <Enter> drücken zum Fortfahren", "Тур окончен. Нажмите <enter>, чтобы продолжить", "Ronde terminée. Appuyez sur <entrée> pour continuer"],
+    ["Hit <space> to start the round", "Pulsa <espacio> para empezar la ronda", "<Leertaste> drücken zum Starten der Runde", "Нажмите <пробел>, чтобы начать тур", "Appuyez sur <espace> pour commencer la ronde"],
+    ["Pause. Hit <space> to resume", "Pausa. Pulsa <espacio> para reanudar", "Pause. <Leertaste> drücken zum Fortsetzen", "Пауза. Нажмите <пробел>, чтобы продолжить", "Pause. Appuyez sur <espace> pour reprendre"],
+    ["1 minute left", "queda 1 minuto", "noch 1 Minute", "осталась 1 минута", "il reste 1 minute"],
+    ["{} minutes left", "quedan {} minutos", "noch {} Minuten", "осталось минут: {}", "il reste {} minutes"],
+    ["Focus {}+{}", "Enfoque {}+{}", "Fokus {}+{}", "Фокус {}+{}", "Focus {}+{}"],
+    ["Hit <space> to pause, 's' to skip", "Pulsa <espacio> para pausar, 's' para saltar", "<Leertaste> zum Anhalten, 's' zum Überspringen", "<пробел> — пауза, 's' — пропустить", "<espace> pour mettre en pause, 's' pour passer"],
+    ["Hit <space> to start working", "Pulsa <espacio> para empezar a trabajar", "<Leertaste> drücken zum Arbeiten", "Нажмите <пробел>, чтобы начать работу", "Appuyez sur <espace> pour commencer à travailler"],
+    ["Work, session {}", "Trabajo, sesión {}", "Arbeit, Einheit {}", "Работа, сессия {}", "Travail, session {}"],
+    ["Break, 1 session done", "Descanso, 1 sesión hecha", "Pause, 1 Einheit geschafft", "Перерыв, 1 сессия пройдена", "Pause, 1 session terminée"],
+    ["Break, {} sessions done", "Descanso, {} sesiones hechas", "Pause, {} Einheiten geschafft", "Перерыв, сессий пройдено: {}", "Pause, {} sessions terminées"],
+    // Events
+    ["Events", "Eventos", "Ereignisse", "События", "Événements"],
+    ["Move times", "Tiempos por jugada", "Zugzeiten", "Время на ходы", "Temps par coup"],
+    ["new game {}", "nueva partida {}", "neue Partie {}", "новая партия {}", "nouvelle partie {}"],
+    ["{} started the clock", "{} puso en marcha el reloj", "{} hat die Uhr gestartet", "{}: часы запущены", "{} a lancé la pendule"],
+    ["{}. {} moved in {}", "{}. {} movió en {}", "{}. {} zog in {}", "{}. {}: ход за {}", "{}. {} a joué en {}"],
+    ["paused", "en pausa", "angehalten", "пауза", "en pause"],
+    ["resumed", "reanudado", "fortgesetzt", "продолжено", "repris"],
+    ["{} flagged", "a {} se le cayó la bandera", "{}: Blättchen gefallen", "{}: флажок упал", "{} est tombé au temps"],
+    ["{} penalised -{}", "{} penalizado -{}", "{} bestraft -{}", "{}: штраф -{}", "{} pénalisé -{}"],
+    ["{} given +{}", "{} recibe +{}", "{} erhält +{}", "{}: добавлено +{}", "{} reçoit +{}"],
+    ["{} offered a draw", "{} ofreció tablas", "{} bietet Remis an", "{}: предложение ничьей", "{} propose la nulle"],
+    ["{} retracted the draw offer", "{} retiró la oferta de tablas", "{} zieht das Remisangebot zurück", "{}: предложение ничьей отозвано", "{} retire la proposition de nulle"],
+    ["{} declined the draw offer", "{} rechazó las tablas", "{} lehnt das Remisangebot ab", "{}: ничья отклонена", "{} refuse la nulle"],
+    ["{} accepted the draw offer", "{} aceptó las tablas", "{} nimmt das Remisangebot an", "{}: ничья принята", "{} accepte la nulle"],
+    ["{} resigned", "{} abandonó", "{} gibt auf", "{}: сдача", "{} abandonne"],
+    // Announcements
+    ["{}, {} remaining", "{}, quedan {}", "{}, noch {}", "{}, осталось {}", "{}, il reste {}"],
+    ["White {}, Black {}", "Blancas {}, negras {}", "Weiß {}, Schwarz {}", "Белые {}, чёрные {}", "Blancs {}, Noirs {}"],
+    ["White", "Blancas", "Weiß", "Белые", "Blancs"],
+    ["Black", "Negras", "Schwarz", "Чёрные", "Noirs"],
+    ["{} to move", "mueven {}", "{} am Zug", "ход: {}", "{} au trait"],
+    ["1 hour", "1 hora", "1 Stunde", "1 час", "1 heure"],
+    ["{} hours", "{} horas", "{} Stunden", "{} ч", "{} heures"],
+    ["1 minute", "1 minuto", "1 Minute", "1 минута", "1 minute"],
+    ["{} minutes", "{} minutos", "{} Minuten", "{} мин", "{} minutes"],
+    ["1 second", "1 segundo", "1 Sekunde", "1 секунда", "1 seconde"],
+    ["{} seconds", "{} segundos", "{} Sekunden", "{} с", "{} secondes"],
+    // Matches
+    ["Game {} of {}", "Partida {} de {}", "Partie {} von {}", "Партия {} из {}", "Partie {} sur {}"],
+    ["Armageddon, Black wins on a draw", "Armagedón, las tablas ganan para las negras", "Armageddon, Remis gewinnt für Schwarz", "Армагеддон, ничья в пользу чёрных", "Armageddon, la nulle fait gagner les Noirs"],
+    ["{} wins the match", "{} gana el match", "{} gewinnt den Wettkampf", "{} выигрывает матч", "{} gagne le match"],
+    ["The match is drawn", "El match termina en tablas", "Der Wettkampf endet remis", "Матч закончился вничью", "Le match est nul"],
+    // Arbiter
+    ["Boards, Enter to open one", "Tableros, Enter para abrir uno", "Bretter, Enter zum Öffnen", "Доски, Enter — открыть", "Échiquiers, Entrée pour en ouvrir un"],
+    ["move {}", "jugada {}", "Zug {}", "ход {}", "coup {}"],
+    ["{}, move {}", "{}, jugada {}", "{}, Zug {}", "{}, ход {}", "{}, coup {}"],
+    // Key hints
+    ["quit", "salir", "beenden", "выход", "quitter"],
+    ["events", "eventos", "Ereignisse", "события", "événements"],
+    ["hit", "pulsar", "drücken", "нажать", "frapper"],
+    ["pause", "pausa", "Pause", "пауза", "pause"],
+    ["start", "empezar", "Start", "старт", "démarrer"],
+    ["swap sides", "cambiar lados", "Seiten tauschen", "поменять стороны", "changer de côté"],
+    ["handicap", "hándicap", "Vorgabe", "фора", "handicap"],
+    ["score", "planilla", "Partieformular", "бланк", "feuille"],
+    ["engine move", "jugada al motor", "Zug gegen die Engine", "ход против движка", "coup contre le moteur"],
+    ["eco", "eco", "Eco", "эко", "éco"],
+    ["mute", "silenciar", "stumm", "без звука", "muet"],
+    ["delete", "borrar", "löschen", "удалить", "supprimer"],
+    ["export CSV/JSON", "exportar CSV/JSON", "CSV/JSON exportieren", "экспорт CSV/JSON", "exporter CSV/JSON"],
+    ["Move times written to {}", "Tiempos por jugada guardados en {}", "Zugzeiten gespeichert in {}", "Время ходов записано в {}", "Temps par coup écrits dans {}"],
+    ["history", "historial", "Verlauf", "история", "historique"],
+    ["game not saved to the history: {}", "partida no guardada en el historial: {}", "Partie nicht im Verlauf gespeichert: {}", "партия не сохранена в историю: {}", "partie non enregistrée dans l'historique : {}"],
+    ["history not pruned: {}", "historial no depurado: {}", "Verlauf nicht bereinigt: {}", "история не очищена: {}", "historique non élagué : {}"],
+    ["keys", "teclas", "Tasten", "клавиши", "touches"],
+    ["Key bindings", "Teclas asignadas", "Tastenbelegung", "Назначение клавиш", "Raccourcis clavier"],
+    ["Game history", "Historial de partidas", "Partieverlauf", "История партий", "Historique des parties"],
+    ["No game finished yet", "Aún no hay partidas terminadas", "Noch keine beendete Partie", "Пока нет завершённых партий", "Aucune partie terminée"],
+    ["Moves: {}", "Jugadas: {}", "Züge: {}", "Ходов: {}", "Coups : {}"],
+    ["resume", "reanudar", "fortsetzen", "продолжить", "reprendre"],
+    ["discard", "descartar", "verwerfen", "отбросить", "abandonner"],
+    ["Resume previous game", "Reanudar la partida anterior", "Letzte Partie fortsetzen", "Продолжить прошлую партию", "Reprendre la partie précédente"],
+    ["{} to resume, {} to discard", "{} para reanudar, {} para descartar", "{} zum Fortsetzen, {} zum Verwerfen", "{} — продолжить, {} — отбросить", "{} pour reprendre, {} pour abandonner"],
+    ["config file ignored: {}", "archivo de configuración ignorado: {}", "Konfigurationsdatei ignoriert: {}", "файл настроек пропущен: {}", "fichier de configuration ignoré : {}"],
+    ["draw", "tablas", "Remis", "ничья", "nulle"],
+    ["moretime", "más tiempo", "mehr Zeit", "добавить время", "plus de temps"],
+    ["resign", "abandonar", "aufgeben", "сдаться", "abandonner"],
+    ["reset", "reiniciar", "neu starten", "сброс", "recommencer"],
+    ["choose", "elegir", "wählen", "выбор", "choisir"],
+    ["new game", "nueva partida", "neue Partie", "новая партия", "nouvelle partie"],
+    ["rematch", "revancha", "Revanche", "реванш", "revanche"],
+    ["join", "unirse", "beitreten", "подключиться", "rejoindre"],
+    ["open", "abrir", "öffnen", "открыть", "ouvrir"],
+    ["start/pause", "empezar/pausa", "Start/Pause", "старт/пауза", "démarrer/pause"],
+    ["skip", "saltar", "überspringen", "пропустить", "passer"],
+    ["times", "tiempos", "Zeiten", "время", "temps"],
+    ["next game", "siguiente partida", "nächste Partie", "следующая партия", "partie suivante"],
+    ["increment", "incremento", "Inkrement", "добавление", "incrément"],
+    ["next field", "siguiente campo", "nächstes Feld", "следующее поле", "champ suivant"],
+    ["back", "volver", "zurück", "назад", "retour"],
+    ["confirm", "confirmar", "bestätigen", "подтвердить", "confirmer"],
+    ["help", "ayuda", "Hilfe", "справка", "aide"],
+    ["hints", "sugerencias", "Hinweise", "подсказки", "indications"],
+    ["previous", "anterior", "vorherige", "предыдущий", "précédent"],
+    ["next", "siguiente", "nächste", "следующий", "suivant"],
+    ["more time", "más tiempo", "mehr Zeit", "добавить время", "plus de temps"],
+    ["resign player1", "abandona el jugador 1", "Spieler 1 gibt auf", "сдаётся игрок 1", "abandon du joueur 1"],
+    ["resign player2", "abandona el jugador 2", "Spieler 2 gibt auf", "сдаётся игрок 2", "abandon du joueur 2"],
+    ["move times", "tiempos por jugada", "Zugzeiten", "время ходов", "temps par coup"],
+    ["export csv", "exportar CSV", "CSV exportieren", "экспорт CSV", "exporter CSV"],
+    ["export json", "exportar JSON", "JSON exportieren", "экспорт JSON", "exporter JSON"],
+    ["any key to close", "cualquier tecla para cerrar", "beliebige Taste zum Schließen", "любая клавиша — закрыть", "une touche pour fermer"],
+    // Popups
+    ["Help", "Ayuda", "Hilfe", "Справка", "Aide"],
+    ["draw offered", "tablas ofrecidas", "Remis angeboten", "предложена ничья", "nulle proposée"],
+    ["wall clock ran {}s ahead of the game clock", "el reloj del sistema se adelantó {}s al de la partida", "die Systemuhr lief der Partieuhr {}s voraus", "системные часы ушли вперёд на {} с", "l'horloge système a avancé de {}s sur la pendule"],
+    ["wall clock went {}s back", "el reloj del sistema retrocedió {}s", "die Systemuhr ging {}s zurück", "системные часы отстали на {} с", "l'horloge système a reculé de {}s"],
+    ["Wall clock", "Reloj del sistema", "Systemuhr", "Системные часы", "Horloge système"],
+    ["the computer may have slept", "puede que el ordenador se haya suspendido", "der Computer war vielleicht im Ruhezustand", "возможно, компьютер засыпал", "l'ordinateur s'est peut-être mis en veille"],
+    ["Take {}s off the clock of {}? ({}/{})", "¿Quitar {}s del reloj de {}? ({}/{})", "{}s von der Uhr von {} abziehen? ({}/{})", "Снять {} с с часов: {}? ({}/{})", "Retirer {}s de la pendule de {} ? ({}/{})"],
+    ["Flag", "Bandera", "Blättchen", "Флажок", "Drapeau"],
+    ["The flag of {} fell", "Cayó la bandera de {}", "Das Blättchen von {} ist gefallen", "Флажок упал: {}", "Le drapeau de {} est tombé"],
+    ["Confirm the win of {}? ({}/{})", "¿Confirmar la victoria de {}? ({}/{})", "Sieg von {} bestätigen? ({}/{})", "Подтвердить победу: {}? ({}/{})", "Confirmer la victoire de {} ? ({}/{})"],
+    ["{} if they can't mate, the game is drawn", "{} si no puede dar mate, la partida es tablas", "{}, wenn kein Matt möglich ist: Remis", "{}, если мат невозможен: ничья", "{} s'il ne peut pas mater, la partie est nulle"],
+    ["Reset", "Reiniciar", "Neustart", "Сброс", "Recommencer"],
+    ["Abort the game and start it again?", "¿Abortar la partida y empezarla de nuevo?", "Partie abbrechen und neu beginnen?", "Прервать партию и начать заново?", "Abandonner la partie et la recommencer ?"],
+    ["{} to reset, {} to go on", "{} para reiniciar, {} para seguir", "{} zum Neustart, {} zum Weiterspielen", "{} — сброс, {} — продолжить", "{} pour recommencer, {} pour continuer"],
+    ["rating: {}", "Elo: {}", "Wertung: {}", "рейтинг: {}", "classement : {}"],
+    ["{} gets {}% of the time", "{} recibe el {}% del tiempo", "{} bekommt {}% der Zeit", "{} получает {}% времени", "{} reçoit {}% du temps"],
+    ["Equal ratings, no time odds", "Elos iguales, sin hándicap", "Gleiche Wertung, keine Zeitvorgabe", "Рейтинги равны, форы нет", "Classements égaux, pas de handicap"],
+    ["Type both ratings for time odds", "Escribe los dos Elos para el hándicap", "Beide Wertungen für eine Zeitvorgabe eingeben", "Введите оба рейтинга для форы", "Saisissez les deux classements pour un handicap"],
+    ["Join a game", "Unirse a una partida", "Einer Partie beitreten", "Присоединиться к партии", "Rejoindre une partie"],
+    ["searching...", "buscando...", "suche...", "поиск...", "recherche..."],
+    ["looking for games on the local network", "buscando partidas en la red local", "suche Partien im lokalen Netz", "поиск партий в локальной сети", "recherche de parties sur le réseau local"],
+    // Score sheet and board
+    ["'{}' isn't a move in SAN", "'{}' no es una jugada en SAN", "'{}' ist kein Zug in SAN", "'{}' — не ход в SAN", "'{}' n'est pas un coup en SAN"],
+    ["{} isn't legal here", "{} no es legal aquí", "{} ist hier nicht erlaubt", "{} здесь невозможен", "{} n'est pas légal ici"],
+];
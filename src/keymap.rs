@@ -0,0 +1,295 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{
+        Alignment,
+        Constraint::{Fill, Length},
+        Flex, Layout, Rect,
+    },
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Padding, Paragraph, Widget},
+};
+use serde::Deserialize;
+use strum::IntoEnumIterator;
+
+use crate::i18n::tr;
+
+/// What a key does. Each screen only looks up the actions it has, so a key can be bound to
+/// actions of different screens, e.g. `h` to the handicap on the clocks and to the history in
+/// the picker.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, strum::Display, strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Action {
+    Hit,
+    Pause,
+    /// Gives the first move to the opponent before the game.
+    FirstToMove,
+    /// The time control on the left in the picker, or the left player to move first.
+    Previous,
+    Next,
+    SwapSides,
+    Draw,
+    MoreTime,
+    Reset,
+    ResignPlayer1,
+    ResignPlayer2,
+    Handicap,
+    Events,
+    MoveTimes,
+    Times,
+    Score,
+    /// Opens the field to type a move against the engine.
+    EngineMove,
+    /// Starts the time control picked.
+    Start,
+    Increment,
+    History,
+    /// Deletes the saved preset picked.
+    Delete,
+    /// Shows the key bindings.
+    Keys,
+    NewGame,
+    Rematch,
+    ExportCsv,
+    ExportJson,
+    /// Ends the current period of the focus timer.
+    Skip,
+    Quit,
+    Hints,
+    /// Shows the keys of the current screen over it.
+    Help,
+    Eco,
+    Mute,
+    /// Answers a question with yes, e.g. to confirm a reset.
+    Yes,
+    No,
+    /// Takes what's typed in a text field, e.g. the names to start the game with.
+    Confirm,
+    /// Leaves a text field or a screen for the one before.
+    Back,
+    /// The game above in the list of games found on the network.
+    Up,
+    Down,
+}
+
+impl Action {
+    /// The keys of the action unless the config binds others.
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Hit => &["space"],
+            Action::Pause => &["p", "esc"],
+            Action::FirstToMove => &["r", "R"],
+            Action::Previous => &["left"],
+            Action::Next => &["right"],
+            Action::SwapSides => &["s"],
+            Action::Draw => &["d"],
+            Action::MoreTime => &["+"],
+            Action::Reset => &["n"],
+            Action::ResignPlayer1 => &["1"],
+            Action::ResignPlayer2 => &["2"],
+            Action::Handicap => &["h"],
+            Action::Events => &["l"],
+            Action::MoveTimes => &["v"],
+            Action::Times => &["t"],
+            Action::Score => &["m"],
+            Action::EngineMove => &["u"],
+            Action::Start => &["space", "enter"],
+            Action::Increment => &["i"],
+            Action::History => &["h"],
+            Action::Delete => &["d"],
+            Action::Keys => &["k"],
+            Action::NewGame => &["R", "r", "enter"],
+            Action::Rematch => &["n"],
+            Action::ExportCsv => &["c"],
+            Action::ExportJson => &["j"],
+            Action::Skip => &["s"],
+            Action::Quit => &["q"],
+            Action::Hints => &["tab"],
+            Action::Help => &["?"],
+            Action::Eco => &["E"],
+            Action::Mute => &["M"],
+            Action::Yes => &["y", "enter"],
+            Action::No => &["n", "esc"],
+            Action::Confirm => &["enter"],
+            Action::Back => &["esc"],
+            Action::Up => &["up", "k"],
+            Action::Down => &["down", "j"],
+        }
+    }
+}
+
+/// A key as written in the config, e.g. `space`, `p` or `ctrl-z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Key {
+    code: KeyCode,
+    ctrl: bool,
+}
+
+impl Key {
+    /// Whether `event` is a press of this key. Other modifiers than Ctrl are ignored, e.g.
+    /// Shift for capitals.
+    fn matches(&self, event: &KeyEvent) -> bool {
+        event.code == self.code && (!self.ctrl || event.modifiers.contains(KeyModifiers::CONTROL))
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ctrl, name) = match s.split_once('-') {
+            Some((ctrl, name)) if ctrl.eq_ignore_ascii_case("ctrl") && !name.is_empty() => {
+                (true, name)
+            }
+            _ => (false, s),
+        };
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match name.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                f => match f.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{s}'")),
+                },
+            },
+        };
+        Ok(Self { code, ctrl })
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            code => write!(f, "{code}"),
+        }
+    }
+}
+
+/// The keys bound to each action, the defaults with those of the config over them.
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    keys: BTreeMap<Action, Vec<Key>>,
+}
+
+impl KeyMap {
+    pub fn new(config: &BTreeMap<Action, Vec<Key>>) -> Self {
+        let keys = Action::iter()
+            .map(|action| {
+                let keys = config.get(&action).cloned().unwrap_or_else(|| {
+                    let keys = action.default_keys().iter();
+                    keys.filter_map(|key| key.parse().ok()).collect()
+                });
+                (action, keys)
+            })
+            .collect();
+        Self { keys }
+    }
+
+    /// The first of `actions` bound to the key of `event`.
+    pub fn find(&self, actions: &[Action], event: &KeyEvent) -> Option<Action> {
+        actions
+            .iter()
+            .copied()
+            .find(|action| self.keys[action].iter().any(|key| key.matches(event)))
+    }
+
+    /// Every key bound to `action`, e.g. `p, Esc` for the pause.
+    pub fn bound(&self, action: Action) -> String {
+        let keys: Vec<_> = self.keys[&action].iter().map(Key::to_string).collect();
+        keys.join(", ")
+    }
+
+    /// The first key of each of `actions`, for the hints, e.g. `1/2` for the resignations.
+    pub fn label(&self, actions: &[Action]) -> String {
+        let keys: Vec<_> = actions
+            .iter()
+            .filter_map(|action| self.keys[action].first())
+            .map(Key::to_string)
+            .collect();
+        keys.join("/")
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new())
+    }
+}
+
+/// The bindings of every action in two columns, under the names the config uses.
+impl Widget for &KeyMap {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightGreen)
+            .padding(Padding::horizontal(1))
+            .title(format!(" {} ", tr("Key bindings")))
+            .title_alignment(Alignment::Center);
+        let width = self
+            .keys
+            .keys()
+            .map(|a| a.to_string().len())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let mut lines: Vec<_> = self
+            .keys
+            .keys()
+            .map(|&action| {
+                Line::from(vec![
+                    format!("{:<width$}", action.to_string()).gray(),
+                    self.bound(action).light_green().bold(),
+                ])
+            })
+            .collect();
+        let rows = lines.len().div_ceil(2);
+        let [area] = Layout::vertical([Length(rows as u16 + 2)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::horizontal([Length(80)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [left, right] = Layout::horizontal([Fill(1), Fill(1)]).areas(block.inner(area));
+        block.render(area, buf);
+        let second = lines.split_off(rows);
+        Paragraph::new(lines).render(left, buf);
+        Paragraph::new(second).render(right, buf);
+    }
+}
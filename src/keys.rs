@@ -0,0 +1,44 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+
+/// A press of a key that was not released within this time after its last press is a new one,
+/// in case the console missed the release.
+const REPEAT_WINDOW: Duration = Duration::from_millis(600);
+
+/// Lets only the first press of a key through.
+///
+/// Windows consoles report key releases, and auto-repeat as more presses while the key is held,
+/// so a single press of the space bar would otherwise hit the clock twice or more.
+#[derive(Debug, Default)]
+pub struct KeyFilter {
+    /// Keys pressed and not released yet, with the time of their last press. Only tracked where
+    /// releases are reported.
+    held: HashMap<KeyCode, Instant>,
+}
+
+impl KeyFilter {
+    pub fn accept(&mut self, key: &KeyEvent) -> bool {
+        match key.kind {
+            KeyEventKind::Press => {
+                if !cfg!(windows) {
+                    return true;
+                }
+                let now = Instant::now();
+                let repeat = self
+                    .held
+                    .insert(key.code, now)
+                    .is_some_and(|last| now - last < REPEAT_WINDOW);
+                !repeat
+            }
+            KeyEventKind::Repeat => false,
+            KeyEventKind::Release => {
+                self.held.remove(&key.code);
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    DefaultTerminal,
+    crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers},
+};
+
+use crate::{
+    i18n::{Lang, Numerals},
+    keys::KeyFilter,
+    screens::{Input, Screen, Screens},
+};
+
+/// Interval between two frames.
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// The local clock without the tokio runtime, for builds without the `async` feature.
+#[derive(Debug)]
+struct Kiosk {
+    running: bool,
+    screens: Screens,
+    ticked_at: Instant,
+}
+
+/// Runs the clock in the terminal: a thread reads the keys, the main thread ticks the clock and
+/// draws it.
+pub fn run() -> anyhow::Result<()> {
+    Lang::detect().set();
+    Numerals::detect().set();
+    let (sender, keys) = mpsc::channel();
+    thread::spawn(move || {
+        let mut filter = KeyFilter::default();
+        while let Ok(event) = event::read() {
+            if let CrosstermEvent::Key(key) = event
+                && filter.accept(&key)
+                && sender.send(key).is_err()
+            {
+                return;
+            }
+        }
+    });
+
+    let mut kiosk = Kiosk {
+        running: true,
+        screens: Screens::default(),
+        ticked_at: Instant::now(),
+    };
+    let terminal = ratatui::init();
+    let result = kiosk.run(terminal, &keys);
+    ratatui::restore();
+    result
+}
+
+impl Kiosk {
+    fn run(
+        &mut self,
+        mut terminal: DefaultTerminal,
+        keys: &mpsc::Receiver<KeyEvent>,
+    ) -> anyhow::Result<()> {
+        while self.running {
+            terminal.draw(|frame| self.screens.render(frame))?;
+            match keys.recv_timeout(FRAME_INTERVAL) {
+                Ok(key) => self.handle_key_events(key),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            let now = Instant::now();
+            self.screens.tick(now - self.ticked_at);
+            self.ticked_at = now;
+        }
+        Ok(())
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent) {
+        if let KeyCode::Char('c' | 'C') = key_event.code
+            && key_event.modifiers == KeyModifiers::CONTROL
+        {
+            self.running = false;
+        }
+
+        let input = match key_event.code {
+            KeyCode::Char('q') if self.screens.screen != Screen::Clocks => {
+                self.running = false;
+                return;
+            }
+            KeyCode::Char(' ') => Input::Hit,
+            KeyCode::Enter => Input::Confirm,
+            KeyCode::Char('p') => Input::Pause,
+            KeyCode::Char('r' | 'R') => Input::Flip,
+            KeyCode::Right => Input::Next,
+            KeyCode::Left => Input::Previous,
+            _ => return,
+        };
+        self.screens.handle_input(input);
+    }
+}
@@ -0,0 +1,10 @@
+//! The clock widgets and screens, shared by the terminal app and the web build, around the logic
+//! of `chessclock-core`.
+
+pub mod clock;
+pub mod font;
+pub mod i18n;
+pub mod screens;
+pub mod tabs;
+
+pub use chessclock_core::sim;
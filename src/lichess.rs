@@ -0,0 +1,387 @@
+use std::{
+    io::{BufRead, BufReader},
+    thread,
+    time::Duration,
+};
+
+use anyhow::bail;
+use serde::Deserialize;
+use tokio::sync::mpsc;
+
+use crate::{
+    clock::{IncrementKind, Overtime, Player, Snapshot},
+    command::Command,
+    event::Event,
+    net::NetEvent,
+    remote::Remote,
+    tabs::{self, TimeCtrl},
+};
+
+const API: &str = "https://lichess.org/api";
+/// How long to wait before looking for the next game of a followed user.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// What to mirror.
+#[derive(Debug, Clone)]
+pub enum Source {
+    Game(String),
+    /// The current game of a user, then the next one and so on.
+    User(String),
+}
+
+/// A line of the game stream: the game itself first and when it ends, a move in between.
+#[derive(Debug, Default, Deserialize)]
+struct GameLine {
+    id: Option<String>,
+    /// The full FEN in the first line, only its board in the move lines.
+    fen: Option<String>,
+    /// The last move, in the move lines.
+    lm: Option<String>,
+    /// Remaining time of white and black in seconds.
+    wc: Option<f64>,
+    bc: Option<f64>,
+    players: Option<Players>,
+    clock: Option<ClockSettings>,
+    status: Option<Status>,
+    winner: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Players {
+    white: GamePlayer,
+    black: GamePlayer,
+}
+
+#[derive(Debug, Deserialize)]
+struct GamePlayer {
+    user: Option<User>,
+    rating: Option<u32>,
+}
+
+impl GamePlayer {
+    fn label(&self) -> String {
+        let name = self.user.as_ref().map_or("Anonymous", |u| &u.name);
+        match self.rating {
+            Some(rating) => format!("{name} ({rating})"),
+            None => name.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClockSettings {
+    initial: u64,
+    increment: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Status {
+    name: String,
+}
+
+impl Status {
+    fn finished(&self) -> bool {
+        !matches!(self.name.as_str(), "created" | "started")
+    }
+}
+
+/// Mirrors `source` on the clock, emitting its states as [`NetEvent::Sync`].
+pub fn follow(source: Source, events: mpsc::UnboundedSender<Event>) {
+    let send = move |event| {
+        let _ = events.send(Event::Net(event));
+    };
+    thread::spawn(move || {
+        let mut last_finished = None;
+        loop {
+            let id = match &source {
+                Source::Game(id) => Ok(id.clone()),
+                Source::User(name) => current_game(name),
+            };
+            let result = id.and_then(|id| {
+                if last_finished.as_ref() == Some(&id) {
+                    send(NetEvent::Status("waiting for the next game".to_string()));
+                    return Ok(());
+                }
+                stream(&id, &send)?;
+                last_finished = Some(id);
+                Ok(())
+            });
+            if let Err(e) = result {
+                send(NetEvent::Status(format!("lichess: {e:#}")));
+            }
+            if let Source::Game(_) = source {
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+fn current_game(user: &str) -> anyhow::Result<String> {
+    let game: GameLine = ureq::get(format!("{API}/user/{user}/current-game"))
+        .header("Accept", "application/json")
+        .call()?
+        .body_mut()
+        .read_json()?;
+    match game.id {
+        Some(id) => Ok(id),
+        None => bail!("{user} has no current game"),
+    }
+}
+
+/// Streams the game `id` until it ends.
+fn stream(id: &str, send: &impl Fn(NetEvent)) -> anyhow::Result<()> {
+    let response = ureq::get(format!("{API}/stream/game/{id}")).call()?;
+    let reader = BufReader::new(response.into_body().into_reader());
+    let mut game = Mirror::default();
+    for line in reader.lines() {
+        let line = line?;
+        // Empty lines keep the connection alive
+        let Ok(line) = serde_json::from_str::<GameLine>(&line) else {
+            continue;
+        };
+        if let Some(players) = &line.players {
+            send(NetEvent::Status(format!(
+                "lichess.org/{id}  {} - {}",
+                players.white.label(),
+                players.black.label()
+            )));
+        }
+        game.update(line);
+        send(NetEvent::Sync(game.snapshot()));
+    }
+    Ok(())
+}
+
+/// The state of the mirrored game.
+#[derive(Debug, Default)]
+struct Mirror {
+    time_control: String,
+    increment_ms: u64,
+    white_ms: u64,
+    black_ms: u64,
+    /// Half-moves played, from the FEN of the first line and counted on since.
+    plies: u32,
+    finished: bool,
+    flagged: Option<Player>,
+}
+
+impl Mirror {
+    fn update(&mut self, line: GameLine) {
+        if let Some(clock) = line.clock {
+            self.time_control = format!("{}+{}", clock.initial, clock.increment);
+            self.increment_ms = clock.increment * 1000;
+            self.white_ms = clock.initial * 1000;
+            self.black_ms = clock.initial * 1000;
+        }
+        // Move lines only carry the board, the turn is counted from the full FEN on
+        let mut fields = line
+            .fen
+            .iter()
+            .flat_map(|fen| fen.split_whitespace().skip(1));
+        if let Some(side) = fields.next() {
+            let move_number: u32 = fields.nth(3).and_then(|n| n.parse().ok()).unwrap_or(1);
+            self.plies = move_number.saturating_sub(1) * 2 + u32::from(side == "b");
+        } else if line.lm.is_some() {
+            self.plies += 1;
+        }
+        if let Some(wc) = line.wc {
+            self.white_ms = (wc * 1000.0) as u64;
+        }
+        if let Some(bc) = line.bc {
+            self.black_ms = (bc * 1000.0) as u64;
+        }
+        if let Some(status) = line.status {
+            self.finished = status.finished();
+            if status.name == "outoftime" {
+                self.flagged = match line.winner.as_deref() {
+                    Some("white") => Some(Player::Player2),
+                    Some("black") => Some(Player::Player1),
+                    _ => None,
+                };
+            }
+        }
+    }
+
+    /// White plays the left clock.
+    fn snapshot(&self) -> Snapshot {
+        let move_number = self.plies / 2 + 1;
+        let turn = if self.plies % 2 == 1 {
+            Player::Player2
+        } else {
+            Player::Player1
+        };
+        let mut snapshot = Snapshot {
+            // Lichess starts the clock after both sides made their first move
+            running: !self.finished && move_number > 1,
+            paused: self.finished,
+            turn: (move_number > 1 || self.finished).then_some(turn),
+            player1_ms: self.white_ms,
+            player2_ms: self.black_ms,
+            move_number,
+            flagged: self.flagged,
+            outcome: None,
+            white: Player::Player1,
+            time_control: self.time_control.clone(),
+            player2_time_control: None,
+            stages: vec![],
+            increment_ms: [self.increment_ms; 2],
+            increment_kind: IncrementKind::default(),
+            overtime: Overtime::None,
+            overtime_left: [None; 2],
+            hourglass: false,
+            armageddon: false,
+            max_ms: None,
+            names: Default::default(),
+        };
+        match self.flagged {
+            Some(Player::Player1) => snapshot.player1_ms = 0,
+            Some(Player::Player2) => snapshot.player2_ms = 0,
+            None => {}
+        }
+        snapshot
+    }
+}
+
+/// A line of a Board API game stream.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum BoardLine {
+    GameFull {
+        clock: Option<ClockSettings>,
+        black: BoardPlayer,
+        state: BoardState,
+    },
+    GameState(BoardState),
+    #[serde(other)]
+    Other,
+}
+
+/// A side of a Board API game, without an id when the AI plays it.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct BoardPlayer {
+    id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BoardState {
+    /// Moves in UCI notation, separated by spaces.
+    moves: String,
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Playing {
+    now_playing: Vec<PlayingGame>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlayingGame {
+    game_id: String,
+}
+
+/// Hits the clock on every move of the account's ongoing game, or of `game`, played through
+/// the Board API. The account plays Player 1's clock.
+pub fn drive(token: String, game: Option<String>, remote: Remote) {
+    thread::spawn(move || {
+        if let Err(e) = drive_game(&token, game, &remote) {
+            let status = NetEvent::Status(format!("lichess: {e:#}"));
+            let _ = remote.events.send(Event::Net(status));
+        }
+    });
+}
+
+fn drive_game(token: &str, game: Option<String>, remote: &Remote) -> anyhow::Result<()> {
+    let auth = format!("Bearer {token}");
+    let id = match game {
+        Some(id) => id,
+        None => {
+            let playing: Playing = ureq::get(format!("{API}/account/playing"))
+                .header("Authorization", &auth)
+                .call()?
+                .body_mut()
+                .read_json()?;
+            match playing.now_playing.into_iter().next() {
+                Some(game) => game.game_id,
+                None => bail!("no ongoing game"),
+            }
+        }
+    };
+    let account: Account = ureq::get(format!("{API}/account"))
+        .header("Authorization", &auth)
+        .call()?
+        .body_mut()
+        .read_json()?;
+    let response = ureq::get(format!("{API}/board/game/stream/{id}"))
+        .header("Authorization", &auth)
+        .call()?;
+    let send = |event| {
+        let _ = remote.events.send(event);
+    };
+    send(Event::Net(NetEvent::Status(format!("lichess.org/{id}"))));
+
+    let mut plies = 0;
+    // The app may not have taken the commands sent yet, so its state can't tell
+    let mut white = Player::Player1;
+    for line in BufReader::new(response.into_body().into_reader()).lines() {
+        let state = match serde_json::from_str(&line?) {
+            Ok(BoardLine::GameFull {
+                clock,
+                black,
+                state,
+            }) => {
+                if black.id.as_ref() == Some(&account.id) {
+                    white = Player::Player2;
+                }
+                // Time controls that aren't built in are set up as custom ones
+                let set = clock.map(|c| {
+                    let ctrl = (
+                        Duration::from_millis(c.initial),
+                        Duration::from_millis(c.increment),
+                    );
+                    TimeCtrl::from_pgn(&tabs::pgn(ctrl))
+                        .map_or(Command::SetCustom(ctrl), Command::Set)
+                });
+                let ctrl = set.is_some();
+                if let Some(set) = set {
+                    send(Event::Command(set));
+                }
+                send(Event::Command(Command::First(white)));
+                // Start with white to move, the moves already played are caught up below
+                if ctrl || remote.state.borrow().turn.is_none() {
+                    send(Event::Command(Command::Hit(None)));
+                }
+                state
+            }
+            Ok(BoardLine::GameState(state)) => state,
+            _ => continue,
+        };
+        let moves: Vec<_> = state.moves.split_whitespace().collect();
+        for (ply, uci) in moves.iter().enumerate().skip(plies) {
+            let mover = if ply % 2 == 0 { white } else { white.opponent() };
+            send(Event::Command(Command::Hit(Some(mover))));
+            send(Event::Net(NetEvent::Moved(uci.to_string())));
+        }
+        plies = plies.max(moves.len());
+        if !matches!(state.status.as_str(), "created" | "started") {
+            if remote.state.borrow().running {
+                send(Event::Command(Command::Pause));
+            }
+            break;
+        }
+    }
+    Ok(())
+}
@@ -1,16 +1,209 @@
+#[cfg(feature = "async")]
 use anyhow::Result;
+#[cfg(feature = "async")]
+use clap::Parser;
 
-use crate::app::App;
+#[cfg(feature = "async")]
+use chessclock::font;
+use chessclock::i18n;
+#[cfg(not(feature = "async"))]
+use chessclock::screens;
+#[cfg(feature = "async")]
+use chessclock::{clock, tabs};
 
+#[cfg(feature = "async")]
+use crate::{
+    app::App,
+    cli::{Cli, Command},
+    config::Config,
+    i18n::{Lang, Numerals, trf},
+};
+
+#[cfg(feature = "async")]
+mod announce;
+#[cfg(feature = "async")]
 mod app;
-mod clock;
+#[cfg(feature = "async")]
+mod arbiter;
+#[cfg(feature = "async")]
+mod backup;
+#[cfg(feature = "async")]
+mod bench;
+#[cfg(feature = "board")]
+mod board;
+#[cfg(feature = "callbacks")]
+mod callback;
+#[cfg(feature = "async")]
+mod cli;
+#[cfg(feature = "async")]
+mod command;
+#[cfg(feature = "async")]
+mod config;
+#[cfg(feature = "async")]
+mod control;
+#[cfg(feature = "async")]
+mod custom;
+#[cfg(feature = "async")]
+mod date;
+#[cfg(feature = "dgt")]
+mod dgt;
+#[cfg(feature = "dbus")]
+mod dbus;
+#[cfg(feature = "async")]
+mod discovery;
+#[cfg(feature = "async")]
+mod eco;
+#[cfg(feature = "async")]
+mod engine;
+#[cfg(feature = "async")]
+mod feed;
+#[cfg(feature = "async")]
+mod focus;
+#[cfg(feature = "async")]
 mod event;
-mod tabs;
+#[cfg(feature = "async")]
+mod export;
+#[cfg(feature = "async")]
+mod games;
+#[cfg(feature = "grpc")]
+mod grpc;
+#[cfg(feature = "async")]
+mod handicap;
+#[cfg(feature = "async")]
+mod headless;
+#[cfg(feature = "async")]
+mod hints;
+#[cfg(feature = "async")]
+mod history;
+#[cfg(not(any(feature = "termion", feature = "termwiz")))]
+mod keys;
+#[cfg(feature = "async")]
+mod keymap;
+#[cfg(not(feature = "async"))]
+mod kiosk;
+#[cfg(feature = "lichess")]
+mod lichess;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+mod metrics;
+#[cfg(feature = "async")]
+mod moves;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+#[cfg(feature = "async")]
+mod names;
+#[cfg(feature = "async")]
+mod net;
+#[cfg(feature = "async")]
+mod obs;
+#[cfg(feature = "async")]
+mod paths;
+#[cfg(feature = "plugins")]
+mod plugin;
+#[cfg(feature = "async")]
+mod presets;
+#[cfg(feature = "async")]
+mod remote;
+#[cfg(feature = "async")]
+mod resume;
+#[cfg(feature = "async")]
+mod round;
+#[cfg(feature = "async")]
+mod score;
+#[cfg(feature = "lua")]
+mod script;
+#[cfg(feature = "async")]
+mod series;
+#[cfg(feature = "async")]
+mod sound;
+#[cfg(feature = "ssh")]
+mod ssh;
+#[cfg(feature = "async")]
+mod statusbar;
+#[cfg(feature = "async")]
+mod term;
+#[cfg(feature = "async")]
+mod wallclock;
 
+#[cfg(feature = "async")]
 #[tokio::main]
 async fn main() -> Result<()> {
-    let terminal = ratatui::init();
-    let result = App::new().run(terminal).await;
-    ratatui::restore();
+    let cli = Cli::parse();
+    let start = cli.start()?;
+    let network = match cli.command {
+        Some(
+            command @ (Command::Host { .. }
+            | Command::Join { .. }
+            | Command::Spectate { .. }
+            | Command::Arbiter { .. }),
+        ) => Some(command),
+        #[cfg(feature = "lichess")]
+        Some(command @ Command::Lichess { .. }) => Some(command),
+        Some(command) => return command.run(),
+        None => None,
+    };
+
+    if cli.headless {
+        let format = if cli.uci {
+            headless::Format::Uci
+        } else {
+            headless::Format::Json
+        };
+        return headless::run(cli.control, cli.interval, format, &cli.remote).await;
+    }
+
+    // A broken config file mustn't keep the clock from starting
+    let (config, config_error) = match Config::load() {
+        Ok(config) => (config, None),
+        Err(e) => (Config::default(), Some(e)),
+    };
+    config.language.unwrap_or_else(Lang::detect).set();
+    config.numerals.unwrap_or_else(Numerals::detect).set();
+    let mut app = App::new(config, cli.meta);
+    if let Some(e) = config_error {
+        // Parse errors go on with the faulty line, only the position fits the status line
+        let error = format!("{e:#}");
+        let error = error.lines().next().unwrap_or_default();
+        app.show_status(trf("config file ignored: {}", &[&error]));
+    }
+    app.serve(&cli.remote).await?;
+    #[cfg(feature = "plugins")]
+    app.load_plugins();
+    #[cfg(feature = "lua")]
+    if let Some(script) = &cli.script {
+        app.script(script)?;
+    }
+    if let Some(games) = cli.best_of {
+        app.best_of(games)?;
+    }
+    if let Some(engine) = &cli.engine {
+        app.engine(engine, cli.engine_plays).await?;
+    }
+    if let Some(ctrl) = start {
+        app.start_with(ctrl);
+    }
+    match network {
+        Some(Command::Host { port, name }) => app.host(port, &name).await?,
+        Some(Command::Join { addr: Some(addr) }) => app.join(&addr).await?,
+        Some(Command::Join { addr: None }) => app.discover()?,
+        Some(Command::Spectate { addr }) => app.spectate(&addr).await?,
+        Some(Command::Arbiter { addrs }) => app.arbiter(&addrs).await?,
+        #[cfg(feature = "lichess")]
+        Some(Command::Lichess { game, user }) => app.mirror_lichess(match (game, user) {
+            (_, Some(user)) => lichess::Source::User(user),
+            (game, None) => lichess::Source::Game(game.unwrap_or_default()),
+        }),
+        _ => {}
+    }
+
+    let terminal = term::init()?;
+    let result = app.run(terminal).await;
+    term::restore();
     result
 }
+
+#[cfg(not(feature = "async"))]
+fn main() -> anyhow::Result<()> {
+    kiosk::run()
+}
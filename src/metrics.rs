@@ -0,0 +1,151 @@
+use std::{
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    clock::{ClockEvent, IncrementKind, Player, Snapshot},
+    remote::Remote,
+};
+
+/// Counters collected from the clock events, rendered in the Prometheus text format.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    games_started: u64,
+    games_flagged: u64,
+    hits: u64,
+    /// Wall time minus time taken off the clocks during the current game, in seconds.
+    drift: f64,
+    /// The US delay may still be running in the current turn, the clock not counting down yet.
+    delayed: bool,
+    last: Option<(Snapshot, Instant)>,
+}
+
+impl Metrics {
+    /// Starts collecting metrics from `remote` in the background.
+    pub fn collect(remote: &Remote) -> Arc<Mutex<Metrics>> {
+        let metrics = Arc::new(Mutex::new(Metrics::default()));
+        let shared = metrics.clone();
+        let mut state = remote.state.clone();
+        let mut events = remote.clock_events.subscribe();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = state.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                        let snapshot = state.borrow_and_update().clone();
+                        shared.lock().unwrap().observe(snapshot, Instant::now());
+                    }
+                    event = events.recv() => match event {
+                        Ok(event) => shared.lock().unwrap().count(&event),
+                        Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => return,
+                    },
+                }
+            }
+        });
+        metrics
+    }
+
+    fn count(&mut self, event: &ClockEvent) {
+        match event {
+            ClockEvent::Start { .. } => self.games_started += 1,
+            ClockEvent::Hit { .. } => self.hits += 1,
+            ClockEvent::Flag { .. } => self.games_flagged += 1,
+            ClockEvent::NewGame { .. } => self.drift = 0.0,
+            ClockEvent::Pause | ClockEvent::Resume | ClockEvent::End { .. } => {}
+        }
+    }
+
+    /// Compares the time taken off the running clock with the wall time since the last state.
+    /// Time that isn't counted down, during a US delay, or added within a turn, by overtime
+    /// periods and stages, is left out.
+    fn observe(&mut self, snapshot: Snapshot, now: Instant) {
+        if let Some((last, at)) = &self.last
+            && last.running
+            && snapshot.running
+            && last.turn == snapshot.turn
+        {
+            let remaining = |s: &Snapshot| match s.turn {
+                Some(Player::Player2) => s.player2_ms,
+                _ => s.player1_ms,
+            };
+            let added = remaining(&snapshot) > remaining(last)
+                || snapshot.overtime_left != last.overtime_left
+                || snapshot.increment_ms != last.increment_ms;
+            let taken = remaining(last) as f64 - remaining(&snapshot) as f64;
+            // The first time taken off ends the delay, part of this sample was still in it
+            let delayed = self.delayed;
+            if taken > 0.0 {
+                self.delayed = false;
+            }
+            if !added && !delayed {
+                self.drift += now.duration_since(*at).as_secs_f64() - taken / 1000.0;
+            }
+        } else {
+            self.delayed = snapshot.increment_kind == IncrementKind::Delay;
+        }
+        self.last = Some((snapshot, now));
+    }
+
+    pub fn render(&self, state: &Snapshot) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, f64)]| {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} {kind}");
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{name}{labels} {value}");
+            }
+        };
+        metric(
+            "chessclock_games_started_total",
+            "counter",
+            "Games started since launch.",
+            &[("", self.games_started as f64)],
+        );
+        metric(
+            "chessclock_games_flagged_total",
+            "counter",
+            "Games ended by a flag fall since launch.",
+            &[("", self.games_flagged as f64)],
+        );
+        metric(
+            "chessclock_hits_total",
+            "counter",
+            "Clock hits since launch.",
+            &[("", self.hits as f64)],
+        );
+        metric(
+            "chessclock_remaining_seconds",
+            "gauge",
+            "Remaining time of each player.",
+            &[
+                ("{player=\"1\"}", state.player1_ms as f64 / 1000.0),
+                ("{player=\"2\"}", state.player2_ms as f64 / 1000.0),
+            ],
+        );
+        metric(
+            "chessclock_running",
+            "gauge",
+            "Whether a clock is running.",
+            &[("", f64::from(u8::from(state.running)))],
+        );
+        metric(
+            "chessclock_move_number",
+            "gauge",
+            "Number of the move being played.",
+            &[("", f64::from(state.move_number))],
+        );
+        metric(
+            "chessclock_drift_seconds",
+            "gauge",
+            "Wall time minus time taken off the clocks in the current game.",
+            &[("", self.drift)],
+        );
+        out
+    }
+}
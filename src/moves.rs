@@ -0,0 +1,71 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph, Widget},
+};
+
+use crate::{
+    clock::{MoveTime, Player, Time},
+    i18n::{self, tr},
+};
+
+/// Width of the panel, borders included.
+pub const WIDTH: u16 = 30;
+
+/// The time each player took for the latest moves, newest first, left and right like the
+/// clocks.
+#[derive(Debug, Clone, Copy)]
+pub struct MoveTimesPanel<'a> {
+    times: &'a [MoveTime],
+}
+
+impl<'a> MoveTimesPanel<'a> {
+    pub fn new(times: &'a [MoveTime]) -> Self {
+        Self { times }
+    }
+
+    /// The move numbers with the time of each player, newest first.
+    fn rows(&self) -> Vec<(u32, [Option<Time>; 2])> {
+        let mut rows: Vec<(u32, [Option<Time>; 2])> = vec![];
+        for time in self.times {
+            let index = time.player.index();
+            match rows.last_mut() {
+                Some((number, row)) if *number == time.move_number && row[index].is_none() => {
+                    row[index] = Some(Time(time.time));
+                }
+                _ => {
+                    let mut row = [None; 2];
+                    row[index] = Some(Time(time.time));
+                    rows.push((time.move_number, row));
+                }
+            }
+        }
+        rows.reverse();
+        rows
+    }
+}
+
+impl Widget for MoveTimesPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::DarkGray)
+            .title(format!(" {} ", tr("Move times")));
+        let column = |text: String| format!("{text:<11}");
+        let header = format!(
+            "     {}{}",
+            column(i18n::player(Player::Player1)),
+            i18n::player(Player::Player2)
+        );
+        let mut lines = vec![Line::from(header.dark_gray())];
+        // Only the rows that fit under the header are kept
+        let room = area.height.saturating_sub(3) as usize;
+        lines.extend(self.rows().into_iter().take(room).map(|(number, row)| {
+            let [p1, p2] = row.map(|time| time.map(|time| time.to_string()).unwrap_or_default());
+            Line::from(format!("{number:>3}. {}{p2}", column(p1)))
+        }));
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
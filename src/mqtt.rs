@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::remote::Remote;
+
+/// Publishes every clock event to `<topic>/event/<kind>` (e.g. `chessclock/event/flag`) and the
+/// state to `<topic>/state`, retained, once per `interval`.
+pub fn spawn(
+    broker: &str,
+    topic: String,
+    interval: Duration,
+    remote: &Remote,
+) -> anyhow::Result<()> {
+    let (host, port) = match broker.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().context("invalid MQTT broker port")?),
+        None => (broker, 1883),
+    };
+    let mut options = MqttOptions::new(format!("chessclock-{}", std::process::id()), host, port);
+    options.set_keep_alive(Duration::from_secs(30));
+    let (client, mut connection) = AsyncClient::new(options, 16);
+
+    // Polling drives the connection, and reconnects after errors
+    tokio::spawn(async move {
+        loop {
+            if connection.poll().await.is_err() {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    });
+
+    let state = remote.state.clone();
+    let mut events = remote.clock_events.subscribe();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            let (subtopic, payload, retain) = tokio::select! {
+                _ = ticker.tick() => {
+                    ("state".to_string(), serde_json::to_value(&*state.borrow()), true)
+                }
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        let payload = serde_json::to_value(&event);
+                        let kind = payload.as_ref().ok().and_then(|p| p["event"].as_str());
+                        (format!("event/{}", kind.unwrap_or_default()), payload, false)
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return,
+                },
+            };
+            let Ok(payload) = payload else { continue };
+            let _ = client
+                .publish(
+                    format!("{topic}/{subtopic}"),
+                    QoS::AtLeastOnce,
+                    retain,
+                    payload.to_string(),
+                )
+                .await;
+        }
+    });
+    Ok(())
+}
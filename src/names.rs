@@ -0,0 +1,57 @@
+use ratatui::crossterm::event::KeyCode;
+
+use crate::clock::Player;
+
+/// Longest name that can be typed.
+const MAX_LEN: usize = 20;
+/// Most digits of a rating.
+const MAX_RATING_LEN: usize = 4;
+
+/// The names and ratings of Player 1 and Player 2 being typed, kept for the next games.
+#[derive(Debug, Clone, Default)]
+pub struct NamesInput {
+    pub fields: [String; 2],
+    /// Ratings the time odds are taken from, see [`crate::handicap`].
+    pub ratings: [String; 2],
+    /// Index of the field being typed: Player 1's name and rating, then Player 2's.
+    pub focused: usize,
+}
+
+impl NamesInput {
+    pub fn handle_key(&mut self, code: KeyCode) {
+        let (player, rating) = (self.focused / 2, self.focused % 2 == 1);
+        match code {
+            KeyCode::Char(c @ '0'..='9')
+                if rating && self.ratings[player].len() < MAX_RATING_LEN =>
+            {
+                self.ratings[player].push(c)
+            }
+            KeyCode::Char(c) if !rating && self.fields[player].chars().count() < MAX_LEN => {
+                self.fields[player].push(c)
+            }
+            KeyCode::Backspace if rating => {
+                self.ratings[player].pop();
+            }
+            KeyCode::Backspace => {
+                self.fields[player].pop();
+            }
+            KeyCode::Tab | KeyCode::Down => self.focused = (self.focused + 1) % 4,
+            KeyCode::BackTab | KeyCode::Up => self.focused = (self.focused + 3) % 4,
+            _ => {}
+        }
+    }
+
+    /// The name typed for `player`, none if left empty.
+    pub fn name(&self, player: Player) -> Option<&str> {
+        Some(self.fields[player.index()].trim()).filter(|name| !name.is_empty())
+    }
+
+    pub fn names(&self) -> [Option<&str>; 2] {
+        [self.name(Player::Player1), self.name(Player::Player2)]
+    }
+
+    /// Both ratings, once they're typed.
+    pub fn ratings(&self) -> Option<[u32; 2]> {
+        Some([self.ratings[0].parse().ok()?, self.ratings[1].parse().ok()?])
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{
+        TcpListener, TcpStream,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+    sync::{Mutex, mpsc},
+};
+
+use crate::{
+    clock::{Player, Snapshot},
+    command::Command,
+    discovery::Host,
+    event::Event,
+    remote::Remote,
+};
+
+pub const DEFAULT_PORT: u16 = 7878;
+/// The host always plays the left clock, the joining player the right one.
+pub const HOST_PLAYER: Player = Player::Player1;
+/// Minimum interval between two states sent to the client.
+const SYNC_INTERVAL: Duration = Duration::from_millis(50);
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+/// Number of recent pings the offset to the host's clock is estimated from.
+const PING_WINDOW: usize = 8;
+
+/// How this instance takes part in a game.
+#[derive(Debug, Default)]
+pub enum Role {
+    #[default]
+    Local,
+    /// Owns the clock, the opponent can only stop their own clock.
+    Host,
+    /// Mirrors the host's clock and forwards key presses to it.
+    Client(mpsc::UnboundedSender<String>),
+    /// Mirrors a clock without being able to change it.
+    Spectator,
+}
+
+/// Messages sent from the host to the client, one JSON object per line. Times are in
+/// microseconds, `at` on the host's clock.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "lowercase")]
+enum HostMessage {
+    /// The state as it was at `at`.
+    State { state: Box<Snapshot>, at: u64 },
+    /// Answers the ping sent at `sent` on the client's clock.
+    Pong { sent: u64, at: u64 },
+}
+
+/// Events of the network connection, handled by the app.
+#[derive(Debug, Clone)]
+pub enum NetEvent {
+    /// Connection status shown on screen.
+    Status(String),
+    /// A state received from the host, already compensated for the network latency.
+    Sync(Snapshot),
+    /// A game hosted on the local network was discovered.
+    Found(Host),
+    /// A discovered game went away, identified by its mDNS name.
+    Lost(String),
+    /// A move in UCI notation, played on a DGT board, on Lichess or by the engine.
+    // Only shown on the board panel
+    #[cfg_attr(not(feature = "board"), allow(dead_code))]
+    Moved(String),
+}
+
+/// Accepts an opponent on `port`. Their `hit` only stops their own clock.
+///
+/// Any number of spectators, which send `spectate` first, can follow the game on the same port.
+pub async fn host(port: u16, remote: Remote) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(SocketAddr::from(([0, 0, 0, 0], port)))
+        .await
+        .with_context(|| format!("could not listen on port {port}"))?;
+    let events = remote.events.clone();
+    let status = move |s: String| {
+        let _ = events.send(Event::Net(NetEvent::Status(s)));
+    };
+    status(format!("waiting for opponent on port {port}"));
+    let opponent = Arc::new(Mutex::new(()));
+    let epoch = Instant::now();
+
+    tokio::spawn(async move {
+        while let Ok((stream, addr)) = listener.accept().await {
+            let (remote, status, opponent) = (remote.clone(), status.clone(), opponent.clone());
+            tokio::spawn(async move {
+                let (reader, writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                let Ok(Some(first)) = lines.next_line().await else {
+                    return;
+                };
+                if first == "spectate" {
+                    let _ = serve_client(lines, writer, remote, epoch, None).await;
+                    return;
+                }
+                // Only one opponent can play at a time
+                let Ok(_playing) = opponent.try_lock() else {
+                    return;
+                };
+                status(format!("opponent {addr} connected"));
+                let _ = serve_client(lines, writer, remote, epoch, Some(first)).await;
+                status(format!("opponent disconnected, waiting on port {port}"));
+            });
+        }
+    });
+    Ok(())
+}
+
+/// Sends the state to a connected client, timestamped on the host's clock started at `epoch`.
+/// Requests are only accepted from the opponent, whose `first` request has already been read.
+async fn serve_client(
+    mut lines: Lines<BufReader<OwnedReadHalf>>,
+    mut writer: OwnedWriteHalf,
+    remote: Remote,
+    epoch: Instant,
+    first: Option<String>,
+) -> anyhow::Result<()> {
+    let now = || epoch.elapsed().as_micros() as u64;
+    let opponent = first.is_some();
+    let mut pending = first;
+    let mut state = remote.state.clone();
+    state.mark_changed();
+    loop {
+        let message = tokio::select! {
+            changed = state.changed() => {
+                changed?;
+                tokio::time::sleep(SYNC_INTERVAL).await;
+                HostMessage::State {
+                    state: Box::new(state.borrow_and_update().clone()),
+                    at: now(),
+                }
+            }
+            line = async {
+                match pending.take() {
+                    Some(line) => Ok(Some(line)),
+                    None => lines.next_line().await,
+                }
+            } => {
+                let Some(line) = line? else { return Ok(()) };
+                let mut words = line.split_whitespace();
+                match (words.next(), words.next()) {
+                    // A malformed ping goes unanswered rather than dropping the peer
+                    (Some("ping"), Some(sent)) => match sent.parse() {
+                        Ok(sent) => HostMessage::Pong { sent, at: now() },
+                        Err(_) => continue,
+                    },
+                    (Some("hit"), None) if opponent => {
+                        let hit = Command::Hit(Some(HOST_PLAYER.opponent()));
+                        let _ = remote.events.send(Event::Command(hit));
+                        continue;
+                    }
+                    (Some("pause"), None) if opponent => {
+                        let _ = remote.events.send(Event::Command(Command::Pause));
+                        continue;
+                    }
+                    _ => continue,
+                }
+            }
+        };
+        let line = serde_json::to_string(&message)? + "\n";
+        writer.write_all(line.as_bytes()).await?;
+    }
+}
+
+/// Connects to a host, as a spectator if `spectate` is set. Lines sent to the returned channel
+/// are forwarded to the host, received states are emitted as [`NetEvent::Sync`].
+pub async fn join(
+    addr: &str,
+    spectate: bool,
+    events: mpsc::UnboundedSender<Event>,
+) -> anyhow::Result<mpsc::UnboundedSender<String>> {
+    let addr = if addr.contains(':') {
+        addr.to_string()
+    } else {
+        format!("{addr}:{DEFAULT_PORT}")
+    };
+    let mut stream = TcpStream::connect(&addr)
+        .await
+        .with_context(|| format!("could not connect to {addr}"))?;
+    stream.set_nodelay(true)?;
+    if spectate {
+        stream.write_all(b"spectate\n").await?;
+    }
+    let (commands, mut outgoing) = mpsc::unbounded_channel::<String>();
+
+    tokio::spawn(async move {
+        let send = |event| {
+            let _ = events.send(Event::Net(event));
+        };
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut ping = tokio::time::interval(PING_INTERVAL);
+        let start = Instant::now();
+        let now = || start.elapsed().as_micros() as u64;
+        let mut host_clock = HostClock::default();
+        loop {
+            let line = tokio::select! {
+                _ = ping.tick() => format!("ping {}", now()),
+                Some(command) = outgoing.recv() => command,
+                received = lines.next_line() => {
+                    let Ok(Some(received)) = received else { break };
+                    match serde_json::from_str(&received) {
+                        Ok(HostMessage::State { state, at }) => {
+                            send(NetEvent::Sync(compensate(*state, host_clock.age(at, now()))));
+                        }
+                        Ok(HostMessage::Pong { sent, at }) => {
+                            let latency = host_clock.add(sent, at, now());
+                            send(NetEvent::Status(format!("{addr}, latency {} ms", latency.as_millis())));
+                        }
+                        Err(_) => {}
+                    }
+                    continue;
+                }
+            };
+            if writer.write_all((line + "\n").as_bytes()).await.is_err() {
+                break;
+            }
+        }
+        send(NetEvent::Status(format!("disconnected from {addr}")));
+    });
+    Ok(commands)
+}
+
+/// Estimates the host's clock from the pings: the offset of the host's clock to ours, measured
+/// in the middle of a round trip, comes from the fastest recent ping since it waited the least.
+#[derive(Debug, Default)]
+struct HostClock {
+    /// Round trip and offset in microseconds of the last pings.
+    pings: VecDeque<(u64, i64)>,
+}
+
+impl HostClock {
+    /// Records the pong to the ping `sent` on our clock, answered by the host `at` on its clock
+    /// and received `now`. Returns the estimated one-way latency.
+    fn add(&mut self, sent: u64, at: u64, now: u64) -> Duration {
+        let rtt = now.saturating_sub(sent);
+        let offset = at as i64 - (sent + rtt / 2) as i64;
+        if self.pings.len() == PING_WINDOW {
+            self.pings.pop_front();
+        }
+        self.pings.push_back((rtt, offset));
+        Duration::from_micros(self.best().map_or(rtt, |(rtt, _)| rtt) / 2)
+    }
+
+    fn best(&self) -> Option<(u64, i64)> {
+        self.pings.iter().min_by_key(|(rtt, _)| *rtt).copied()
+    }
+
+    /// How long ago the host's clock showed `at`, zero until the first pong.
+    fn age(&self, at: u64, now: u64) -> Duration {
+        let Some((_, offset)) = self.best() else {
+            return Duration::ZERO;
+        };
+        let age = now as i64 + offset - at as i64;
+        Duration::from_micros(age.max(0) as u64)
+    }
+}
+
+/// Takes the time since the host took the state off the running clock.
+fn compensate(mut snapshot: Snapshot, latency: Duration) -> Snapshot {
+    if snapshot.running {
+        let latency = latency.as_millis() as u64;
+        match snapshot.turn {
+            Some(Player::Player1) => snapshot.player1_ms = snapshot.player1_ms.saturating_sub(latency),
+            Some(Player::Player2) => snapshot.player2_ms = snapshot.player2_ms.saturating_sub(latency),
+            None => {}
+        }
+    }
+    snapshot
+}
@@ -0,0 +1,68 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::Path,
+    time::Duration,
+};
+
+use crate::{
+    clock::{Player, Snapshot, Time},
+    remote::Remote,
+};
+
+/// Writes the clock state into text files in `dir` on every change, for OBS "Text from file"
+/// sources: `player1.txt`/`player2.txt` (times), `player1_name.txt`/`player2_name.txt` and
+/// `result.txt` (empty until the game is over).
+pub fn spawn(dir: &Path, remote: &Remote) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)?;
+    let dir = dir.to_owned();
+    let mut state = remote.state.clone();
+    tokio::spawn(async move {
+        let mut written = HashMap::new();
+        loop {
+            let snapshot = state.borrow_and_update().clone();
+            if write_all(&dir, &snapshot, &mut written).is_err() {
+                return;
+            }
+            if state.changed().await.is_err() {
+                return;
+            }
+        }
+    });
+    Ok(())
+}
+
+fn write_all(
+    dir: &Path,
+    snapshot: &Snapshot,
+    written: &mut HashMap<&'static str, String>,
+) -> io::Result<()> {
+    let time = |ms| Time(Duration::from_millis(ms)).to_string();
+    // Which player they are when no name was typed
+    let name = |player: Player| {
+        snapshot.names[player.index()]
+            .clone()
+            .unwrap_or_else(|| player.to_string())
+    };
+    let files = [
+        ("player1.txt", time(snapshot.player1_ms)),
+        ("player2.txt", time(snapshot.player2_ms)),
+        ("player1_name.txt", name(Player::Player1)),
+        ("player2_name.txt", name(Player::Player2)),
+        ("result.txt", snapshot.result().unwrap_or_default().to_string()),
+    ];
+    for (name, content) in files {
+        if written.get(name) != Some(&content) {
+            write_atomic(&dir.join(name), &content)?;
+            written.insert(name, content);
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the file in one step, so OBS never reads a half written file.
+fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, path)
+}
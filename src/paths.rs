@@ -0,0 +1,26 @@
+use std::{env, path::PathBuf};
+
+const APP_DIR: &str = "chessclock";
+
+/// Directory holding `config.toml` and saved presets.
+pub fn config_dir() -> PathBuf {
+    base_dir("XDG_CONFIG_HOME", ".config").join(APP_DIR)
+}
+
+/// Directory for persistent application data such as the game history.
+pub fn data_dir() -> PathBuf {
+    base_dir("XDG_DATA_HOME", ".local/share").join(APP_DIR)
+}
+
+fn base_dir(xdg_var: &str, home_fallback: &str) -> PathBuf {
+    if let Some(dir) = env::var_os(xdg_var).filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    if let Some(dir) = env::var_os("APPDATA") {
+        return PathBuf::from(dir);
+    }
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(home_fallback)
+}
@@ -0,0 +1,118 @@
+//! Time controls from dynamic libraries in the `plugins` config directory.
+//!
+//! A plugin exports these C functions, version 1 of the interface:
+//!
+//! ```c
+//! uint32_t chessclock_plugin_abi(void);         // returns 1
+//! const char *chessclock_plugin_name(void);     // label in the picker, static and UTF-8
+//! uint64_t chessclock_plugin_start_ms(void);    // time of both players at the start
+//! // `player` (1 or 2) finished move `move_number`, returns the ms added to their time
+//! int64_t chessclock_plugin_on_hit(uint8_t player, uint64_t player1_ms, uint64_t player2_ms,
+//!                                  uint32_t move_number);
+//! ```
+
+use std::{
+    env::consts::DLL_EXTENSION,
+    ffi::{CStr, c_char},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, bail};
+use libloading::{Library, Symbol};
+
+use crate::{
+    clock::{Clock, ClockEvent, Player, Snapshot},
+    paths,
+};
+
+/// Version of the plugin interface described above.
+const ABI_VERSION: u32 = 1;
+
+type OnHit = unsafe extern "C" fn(u8, u64, u64, u32) -> i64;
+
+#[derive(Debug)]
+pub struct Plugin {
+    pub name: String,
+    pub start: Duration,
+    on_hit: OnHit,
+    // Keeps `on_hit` loaded
+    _library: Library,
+}
+
+pub fn dir() -> PathBuf {
+    paths::config_dir().join("plugins")
+}
+
+/// Loads the plugins found in [`dir`], sorted by file name. Plugins that fail to load are
+/// returned as errors so the others can still be used.
+pub fn discover() -> Vec<anyhow::Result<Plugin>> {
+    let Ok(entries) = fs::read_dir(dir()) else {
+        return vec![];
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == DLL_EXTENSION))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| Plugin::load(path).with_context(|| format!("plugin {}", path.display())))
+        .collect()
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> anyhow::Result<Plugin> {
+        // SAFETY: plugins are trusted like any program the user installs; loading one runs its
+        // initialisers and the symbols are checked against the interface version before use.
+        unsafe {
+            let library = Library::new(path)?;
+            let abi: Symbol<unsafe extern "C" fn() -> u32> =
+                library.get(b"chessclock_plugin_abi")?;
+            let abi = abi();
+            if abi != ABI_VERSION {
+                bail!("interface version {abi} is not supported, expected {ABI_VERSION}");
+            }
+            let name: Symbol<unsafe extern "C" fn() -> *const c_char> =
+                library.get(b"chessclock_plugin_name")?;
+            let name = name();
+            if name.is_null() {
+                bail!("no name");
+            }
+            let name = CStr::from_ptr(name).to_str()?.to_string();
+            let start_ms: Symbol<unsafe extern "C" fn() -> u64> =
+                library.get(b"chessclock_plugin_start_ms")?;
+            let start_ms = start_ms();
+            let on_hit = *library.get::<OnHit>(b"chessclock_plugin_on_hit")?;
+            Ok(Plugin {
+                name,
+                start: Duration::from_millis(start_ms),
+                on_hit,
+                _library: library,
+            })
+        }
+    }
+
+    /// Applies the plugin's rule to the hits between `prev` and the current state of `clock`.
+    pub fn clock_changed(&self, prev: &Snapshot, clock: &mut Clock) {
+        for event in ClockEvent::between(prev, &clock.snapshot()) {
+            if let ClockEvent::Hit {
+                player,
+                move_number,
+            } = event
+            {
+                let state = clock.snapshot();
+                let number = match player {
+                    Player::Player1 => 1,
+                    Player::Player2 => 2,
+                };
+                // SAFETY: the symbol was checked to belong to a version 1 plugin
+                let delta_ms = unsafe {
+                    (self.on_hit)(number, state.player1_ms, state.player2_ms, move_number)
+                };
+                clock.adjust(player, delta_ms);
+            }
+        }
+    }
+}
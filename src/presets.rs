@@ -0,0 +1,41 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{paths, tabs::Preset};
+
+/// Layout of `presets.toml`, a `[[presets]]` table for each saved time control.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PresetsFile {
+    #[serde(default)]
+    presets: Vec<Preset>,
+}
+
+pub fn path() -> PathBuf {
+    paths::config_dir().join("presets.toml")
+}
+
+/// Loads the saved time controls, in the order they were saved.
+pub fn load() -> anyhow::Result<Vec<Preset>> {
+    match fs::read_to_string(path()) {
+        Ok(content) => Ok(toml::from_str::<PresetsFile>(&content)?.presets),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Rewrites the presets file with `presets`.
+pub fn save(presets: &[Preset]) -> anyhow::Result<()> {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = toml::to_string(&PresetsFile {
+        presets: presets.to_vec(),
+    })?;
+    // Write to a temporary file first so an interrupted write can't lose the presets
+    let tmp = path.with_extension("toml.tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
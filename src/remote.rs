@@ -0,0 +1,202 @@
+#[cfg(any(feature = "http", feature = "grpc", feature = "ssh"))]
+use std::net::SocketAddr;
+use std::path::PathBuf;
+#[cfg(feature = "mqtt")]
+use std::time::Duration;
+
+use clap::Args;
+use tokio::sync::{broadcast, mpsc, watch};
+
+use crate::{
+    clock::{ClockEvent, Snapshot},
+    control::{self, ControlSocket},
+    event::Event,
+    obs, statusbar,
+};
+
+/// Handles shared by the servers that let other programs observe and drive the clock.
+#[derive(Debug, Clone)]
+pub struct Remote {
+    /// Commands received by a server are sent here as [`Event::Command`].
+    pub events: mpsc::UnboundedSender<Event>,
+    /// The latest state of the clock.
+    pub state: watch::Receiver<Snapshot>,
+    /// Discrete clock events, call `subscribe` to receive them.
+    pub clock_events: broadcast::Sender<ClockEvent>,
+}
+
+/// Publishes the clock state, and the events derived from its changes, to the servers.
+#[derive(Debug)]
+pub struct Publisher {
+    state: watch::Sender<Snapshot>,
+    clock_events: broadcast::Sender<ClockEvent>,
+}
+
+impl Publisher {
+    pub fn new(snapshot: Snapshot) -> Self {
+        Self {
+            state: watch::Sender::new(snapshot),
+            clock_events: broadcast::Sender::new(64),
+        }
+    }
+
+    /// Handles for servers that send their commands to `events`.
+    pub fn remote(&self, events: mpsc::UnboundedSender<Event>) -> Remote {
+        Remote {
+            events,
+            state: self.state.subscribe(),
+            clock_events: self.clock_events.clone(),
+        }
+    }
+
+    /// Updates the state if it changed, emitting the events that lead to it. Subscribers to
+    /// the events see the new state.
+    pub fn publish(&self, snapshot: Snapshot) {
+        let mut events = vec![];
+        self.state.send_if_modified(|state| {
+            if *state == snapshot {
+                return false;
+            }
+            events = ClockEvent::between(state, &snapshot);
+            *state = snapshot;
+            true
+        });
+        for event in events {
+            let _ = self.clock_events.send(event);
+        }
+    }
+}
+
+/// Command line options enabling the remote control servers and outputs.
+#[derive(Debug, Clone, Default, Args)]
+pub struct RemoteArgs {
+    /// Accept remote commands on a Unix socket, or a named pipe on Windows
+    #[arg(long, value_name = "PATH")]
+    pub socket: Option<Option<PathBuf>>,
+    /// Serve the HTTP API on this address, e.g. 0.0.0.0:8080
+    #[cfg(feature = "http")]
+    #[arg(long, value_name = "ADDR")]
+    pub http: Option<SocketAddr>,
+    /// Require this token for HTTP requests
+    #[cfg(feature = "http")]
+    #[arg(long, value_name = "TOKEN", requires = "http")]
+    pub http_token: Option<String>,
+    /// Serve the gRPC service of proto/chessclock.proto on this address, e.g. 0.0.0.0:50051
+    #[cfg(feature = "grpc")]
+    #[arg(long, value_name = "ADDR")]
+    pub grpc: Option<SocketAddr>,
+    /// Keep the times, names and result in text files in this directory (for OBS)
+    #[arg(long, value_name = "DIR")]
+    pub obs_dir: Option<PathBuf>,
+    /// Keep a one line status in this file or FIFO for desktop status bars
+    #[arg(long, value_name = "PATH")]
+    pub status_bar: Option<PathBuf>,
+    /// Format of the status bar line
+    #[arg(long, value_name = "FORMAT", default_value = "plain", requires = "status_bar")]
+    pub status_format: statusbar::Format,
+    /// Publish clock events to this MQTT broker, e.g. localhost:1883
+    #[cfg(feature = "mqtt")]
+    #[arg(long, value_name = "HOST[:PORT]")]
+    pub mqtt: Option<String>,
+    /// Prefix of the MQTT topics
+    #[cfg(feature = "mqtt")]
+    #[arg(long, value_name = "TOPIC", default_value = "chessclock")]
+    pub mqtt_topic: String,
+    /// Interval between two MQTT state messages in seconds
+    #[cfg(feature = "mqtt")]
+    #[arg(long, value_name = "SECS", default_value = "1", value_parser = parse_secs)]
+    pub mqtt_interval: Duration,
+    /// Register a service on the D-Bus session bus
+    #[cfg(feature = "dbus")]
+    #[arg(long)]
+    pub dbus: bool,
+    /// Share the clock with `ssh` clients on this address, e.g. 0.0.0.0:2222
+    #[cfg(feature = "ssh")]
+    #[arg(long, value_name = "ADDR")]
+    pub ssh: Option<SocketAddr>,
+    /// Only let ssh clients watch the clock
+    #[cfg(feature = "ssh")]
+    #[arg(long, requires = "ssh")]
+    pub ssh_read_only: bool,
+    /// Hit the clock on legal moves made on the DGT board at this serial port, e.g. /dev/ttyACM0
+    #[cfg(feature = "dgt")]
+    #[arg(long, value_name = "PORT")]
+    pub dgt: Option<String>,
+    /// Show the times on the DGT 3000 clock attached to the board
+    #[cfg(feature = "dgt")]
+    #[arg(long, requires = "dgt")]
+    pub dgt_clock: bool,
+    /// Hit the clock on the moves of your ongoing Lichess Board API game, or of GAME. The API
+    /// token is read from LICHESS_TOKEN
+    #[cfg(feature = "lichess")]
+    #[arg(long, value_name = "GAME")]
+    pub lichess_board: Option<Option<String>>,
+}
+
+#[cfg(feature = "mqtt")]
+fn parse_secs(s: &str) -> Result<Duration, String> {
+    let secs: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    match Duration::try_from_secs_f64(secs) {
+        Ok(interval) if !interval.is_zero() => Ok(interval),
+        _ => Err(format!("'{s}' isn't a positive number of seconds")),
+    }
+}
+
+/// The running servers, shut down when dropped.
+#[derive(Debug, Default)]
+pub struct Servers {
+    _control: Option<ControlSocket>,
+}
+
+impl RemoteArgs {
+    /// Starts every server requested on the command line.
+    pub async fn start(&self, remote: &Remote) -> anyhow::Result<Servers> {
+        let mut servers = Servers::default();
+        if let Some(path) = &self.socket {
+            let path = path.clone().unwrap_or_else(control::default_path);
+            servers._control = Some(control::listen(&path, remote.clone())?);
+        }
+        #[cfg(feature = "http")]
+        if let Some(addr) = self.http {
+            crate::http::serve(addr, self.http_token.clone(), remote.clone())?;
+        }
+        #[cfg(feature = "grpc")]
+        if let Some(addr) = self.grpc {
+            crate::grpc::serve(addr, remote.clone()).await?;
+        }
+        if let Some(dir) = &self.obs_dir {
+            obs::spawn(dir, remote)?;
+        }
+        if let Some(path) = &self.status_bar {
+            statusbar::spawn(path, self.status_format, remote)?;
+        }
+        #[cfg(feature = "mqtt")]
+        if let Some(broker) = &self.mqtt {
+            crate::mqtt::spawn(
+                broker,
+                self.mqtt_topic.clone(),
+                self.mqtt_interval,
+                remote,
+            )?;
+        }
+        #[cfg(feature = "dbus")]
+        if self.dbus {
+            crate::dbus::serve(remote).await?;
+        }
+        #[cfg(feature = "dgt")]
+        if let Some(port) = &self.dgt {
+            crate::dgt::connect(port, self.dgt_clock, remote.clone())?;
+        }
+        #[cfg(feature = "lichess")]
+        if let Some(game) = &self.lichess_board {
+            let token = std::env::var("LICHESS_TOKEN")
+                .map_err(|_| anyhow::anyhow!("--lichess-board needs a token in LICHESS_TOKEN"))?;
+            crate::lichess::drive(token, game.clone(), remote.clone());
+        }
+        #[cfg(feature = "ssh")]
+        if let Some(addr) = self.ssh {
+            crate::ssh::serve(addr, self.ssh_read_only, remote.clone()).await?;
+        }
+        Ok(servers)
+    }
+}
@@ -0,0 +1,35 @@
+use std::{fs, path::PathBuf};
+
+use crate::{clock::Snapshot, paths};
+
+pub fn path() -> PathBuf {
+    paths::data_dir().join("game.json")
+}
+
+/// Saves the state of the game being played, replacing the one saved before.
+pub fn save(snapshot: &Snapshot) -> anyhow::Result<()> {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Write to a temporary file first so a crash while writing leaves the last state
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string(snapshot)?)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+/// The game left unfinished when the app was last closed, none if there's no such game or
+/// its state can't be read.
+pub fn load() -> Option<Snapshot> {
+    let content = fs::read_to_string(path()).ok()?;
+    serde_json::from_str(&content)
+        .ok()
+        .filter(|snapshot: &Snapshot| snapshot.turn.is_some() && snapshot.result().is_none())
+}
+
+/// Forgets the saved game, once it's over or another one starts.
+pub fn clear() {
+    // Nothing to forget when no game was saved
+    let _ = fs::remove_file(path());
+}
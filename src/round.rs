@@ -0,0 +1,151 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{
+        Constraint::{Fill, Length},
+        Layout, Rect,
+    },
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use crate::{
+    clock::Time,
+    config::RoundConfig,
+    font::FontName,
+    i18n::{tr, trf},
+};
+
+/// A single countdown for a whole tournament round, independent of the players' clocks.
+#[derive(Debug, Clone, Default)]
+pub struct RoundTimer {
+    remaining: Duration,
+    started: bool,
+    running: bool,
+    /// Remaining times to announce, the largest first.
+    announcements: Vec<Duration>,
+    /// The last announcement reached.
+    announced: Option<Duration>,
+    /// Last update by [`RoundTimer::tick_timer`].
+    ticked_at: Option<Instant>,
+}
+
+impl RoundTimer {
+    pub fn new(config: &RoundConfig) -> Self {
+        let remaining = Duration::from_secs(config.minutes * 60);
+        let mut announcements: Vec<_> = config
+            .announce
+            .iter()
+            .map(|minutes| Duration::from_secs(minutes * 60))
+            .filter(|&at| at < remaining)
+            .collect();
+        announcements.sort_by(|a, b| b.cmp(a));
+        Self {
+            remaining,
+            announcements,
+            ..Self::default()
+        }
+    }
+
+    /// Label of the timer in the time control picker.
+    pub fn label(config: &RoundConfig) -> String {
+        trf("Round {}", &[&config.minutes])
+    }
+
+    /// Starts the round, then pauses and resumes it.
+    pub fn toggle(&mut self) {
+        if !self.is_over() {
+            self.started = true;
+            self.running = !self.running;
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.remaining == Duration::ZERO
+    }
+
+    /// Whether the round is counting down or paused.
+    pub fn is_started(&self) -> bool {
+        self.started && !self.is_over()
+    }
+
+    /// Counts down the time elapsed since the last tick, so the timer keeps to wall time
+    /// however late the ticks come.
+    pub fn tick_timer(&mut self) {
+        let now = Instant::now();
+        let elapsed = self.ticked_at.map_or(Duration::ZERO, |at| now - at);
+        self.ticked_at = Some(now);
+        if !self.running {
+            return;
+        }
+        self.remaining = self.remaining.saturating_sub(elapsed);
+        while self
+            .announcements
+            .first()
+            .is_some_and(|&at| self.remaining <= at)
+        {
+            self.announced = Some(self.announcements.remove(0));
+        }
+        if self.is_over() {
+            self.running = false;
+        }
+    }
+}
+
+impl Widget for &RoundTimer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let hint = if self.is_over() {
+            tr("Round over. Hit <enter> to continue")
+        } else if !self.started {
+            tr("Hit <space> to start the round")
+        } else if !self.running {
+            tr("Pause. Hit <space> to resume")
+        } else {
+            tr("Round")
+        };
+        let style = if self.is_over() {
+            Style::default().fg(Color::LightRed)
+        } else if self.running {
+            Style::default().fg(Color::LightGreen)
+        } else {
+            Style::default().fg(Color::from_u32(0x003f3f3f))
+        };
+        let caption = self.announced.filter(|_| !self.is_over()).map(|at| {
+            let text = match at.as_secs() / 60 {
+                1 => tr("1 minute left").to_string(),
+                minutes => trf("{} minutes left", &[&minutes]),
+            };
+            Line::from(format!(" {text} ").fg(Color::LightYellow).bold())
+        });
+        render_countdown(self.remaining, style, caption, hint, area, buf);
+    }
+}
+
+/// Renders a single timer in the big digits of the clocks, with a `caption` below it and a
+/// `hint` at the bottom.
+pub fn render_countdown(
+    time: Duration,
+    style: Style,
+    caption: Option<Line>,
+    hint: &str,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let instructions = Line::from(format!(" {hint} ").fg(Color::LightGreen).bold());
+    Block::default()
+        .title_bottom(instructions.centered())
+        .render(area, buf);
+
+    let [_, digits, caption_area, _] =
+        Layout::vertical([Fill(3), Length(4), Length(1), Fill(2)]).areas(area);
+    let time = Text::styled(
+        FontName::default().font().text(&Time(time).to_string()),
+        style,
+    );
+    Paragraph::new(time).centered().render(digits, buf);
+    if let Some(caption) = caption {
+        caption.centered().render(caption_area, buf);
+    }
+}
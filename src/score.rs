@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+#[cfg(feature = "board")]
+use crate::board::BoardPanel;
+use crate::{
+    clock::{ClockEvent, Player, Snapshot},
+    history::MoveRecord,
+    i18n::trf,
+};
+
+/// Moves shown before the input field.
+const SHOWN_MOVES: usize = 6;
+
+/// The moves of the game with their clocks, and the SAN a scorer types for them.
+#[derive(Debug, Clone, Default)]
+pub struct Scoresheet {
+    moves: Vec<MoveRecord>,
+    /// Moves typed before the clock was hit for them.
+    pending: VecDeque<String>,
+    /// The move being typed, when the field is open.
+    input: Option<String>,
+    /// Why the last move typed was refused.
+    error: Option<String>,
+    /// The position after the moves scored.
+    #[cfg(feature = "board")]
+    board: BoardPanel,
+}
+
+impl Scoresheet {
+    pub fn moves(&self) -> &[MoveRecord] {
+        &self.moves
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn open(&mut self) {
+        self.input = Some(String::new());
+    }
+
+    /// Records the moves made between the `prev` and `next` states of the clock.
+    pub fn clock_changed(&mut self, prev: &Snapshot, next: &Snapshot) {
+        for event in ClockEvent::between(prev, next) {
+            match event {
+                ClockEvent::NewGame { .. } => {
+                    self.moves.clear();
+                    self.pending.clear();
+                    #[cfg(feature = "board")]
+                    self.board.clear();
+                    if let Some(input) = &mut self.input {
+                        input.clear();
+                    }
+                }
+                ClockEvent::Hit { player, .. } => self.moves.push(MoveRecord {
+                    clock_ms: match player {
+                        Player::Player1 => next.player1_ms,
+                        Player::Player2 => next.player2_ms,
+                    },
+                    san: self.pending.pop_front(),
+                }),
+                // The clock isn't hit for the move ending the game
+                ClockEvent::End { .. } => {
+                    let clock_ms = match next.turn {
+                        Some(Player::Player2) => next.player2_ms,
+                        _ => next.player1_ms,
+                    };
+                    let pending = self.pending.drain(..);
+                    self.moves.extend(pending.map(|san| MoveRecord {
+                        clock_ms,
+                        san: Some(san),
+                    }));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Edits the move being typed, returning whether the key was used for it.
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        let Some(input) = &mut self.input else {
+            return false;
+        };
+        match code {
+            // Space stays the clock's, no move contains it
+            KeyCode::Char(' ') => return false,
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace if input.is_empty() => self.undo(),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter if !input.is_empty() => {
+                let san = std::mem::take(input);
+                if !is_san(&san) {
+                    self.error = Some(trf("'{}' isn't a move in SAN", &[&san]));
+                    return true;
+                }
+                #[cfg(feature = "board")]
+                let san = match self.board.play_san(&san) {
+                    Ok(san) => san,
+                    Err(e) => {
+                        self.error = Some(e);
+                        return true;
+                    }
+                };
+                self.error = None;
+                self.score(san);
+            }
+            KeyCode::Esc => {
+                self.input = None;
+                self.error = None;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Attaches `san` to the first move made but not scored yet, or to the next one.
+    fn score(&mut self, san: String) {
+        match self.moves.iter_mut().find(|m| m.san.is_none()) {
+            Some(record) if self.pending.is_empty() => record.san = Some(san),
+            _ => self.pending.push_back(san),
+        }
+    }
+
+    /// Removes the last move typed.
+    fn undo(&mut self) {
+        if self.pending.pop_back().is_none()
+            && let Some(record) = self.moves.iter_mut().rev().find(|m| m.san.is_some())
+        {
+            record.san = None;
+        }
+        #[cfg(feature = "board")]
+        self.board.undo();
+    }
+
+    /// Scores a move in UCI notation played on a connected board, online or by the engine.
+    #[cfg(feature = "board")]
+    pub fn played(&mut self, uci: &str) -> Result<(), String> {
+        let san = self.board.play_uci(uci)?;
+        self.score(san);
+        Ok(())
+    }
+
+    /// The position, once moves are scored or being typed.
+    #[cfg(feature = "board")]
+    pub fn board(&self) -> Option<&BoardPanel> {
+        (self.is_open() || !self.board.is_empty()).then_some(&self.board)
+    }
+}
+
+/// Whether `san` is written like a move in Standard Algebraic Notation, e.g. `Nbd7`, `exd5`,
+/// `e8=Q+` or `O-O`. Its legality isn't checked.
+pub fn is_san(san: &str) -> bool {
+    let san = san.trim_end_matches(['+', '#']);
+    if matches!(san, "O-O" | "O-O-O") {
+        return true;
+    }
+    let (body, promotion) = match san.split_once('=') {
+        Some((body, "Q" | "R" | "B" | "N")) => (body, true),
+        Some(_) => return false,
+        None => (san, false),
+    };
+    let is_file = |c: &u8| (b'a'..=b'h').contains(c);
+    let is_rank = |c: &u8| (b'1'..=b'8').contains(c);
+    let [rest @ .., file, rank] = body.as_bytes() else {
+        return false;
+    };
+    if !is_file(file) || !is_rank(rank) {
+        return false;
+    }
+    let (rest, capture) = match rest {
+        [rest @ .., b'x'] => (rest, true),
+        rest => (rest, false),
+    };
+    match rest {
+        [] => !capture && (!promotion || matches!(rank, b'1' | b'8')),
+        [from] if is_file(from) => capture && (!promotion || matches!(rank, b'1' | b'8')),
+        [piece, from @ ..] if b"KQRBN".contains(piece) && !promotion => match from {
+            [] => true,
+            [c] => is_file(c) || is_rank(c),
+            [f, r] => is_file(f) && is_rank(r),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl Widget for &Scoresheet {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(input) = &self.input else {
+            return;
+        };
+        let scored: Vec<_> = self
+            .moves
+            .iter()
+            .map_while(|m| m.san.clone())
+            .chain(self.pending.iter().cloned())
+            .collect();
+        let mut spans = vec![];
+        for (i, san) in scored
+            .iter()
+            .enumerate()
+            .skip(scored.len().saturating_sub(SHOWN_MOVES))
+        {
+            if i % 2 == 0 {
+                spans.push(format!("{}. ", i / 2 + 1).dark_gray());
+            }
+            spans.push(Span::from(format!("{san} ")));
+        }
+        if scored.len() % 2 == 0 {
+            spans.push(format!("{}. ", scored.len() / 2 + 1).dark_gray());
+        }
+        spans.push(format!("{input}_").fg(Color::LightYellow).bold());
+        if let Some(error) = &self.error {
+            spans.push(format!("  {error}").fg(Color::LightRed));
+        }
+        Line::from(spans).centered().render(area, buf);
+    }
+}
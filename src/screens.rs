@@ -0,0 +1,108 @@
+//! The clock screens of the kiosk and web builds: a time control is picked, played until a flag
+//! falls and picked again.
+
+use std::time::Duration;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    widgets::Widget,
+};
+
+use crate::{
+    clock::{Clock, ClockWidget},
+    tabs::{self, TimeCtrl},
+};
+
+#[derive(Debug, Default, PartialEq)]
+pub enum Screen {
+    Clocks,
+    #[default]
+    SelectTimeCtrl,
+    TimeOut,
+}
+
+/// What the keys do, whatever reads them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Input {
+    /// The space bar.
+    Hit,
+    /// Enter.
+    Confirm,
+    Pause,
+    /// `r`: who moves first before the start, a new game after a flag fell.
+    Flip,
+    Next,
+    Previous,
+}
+
+#[derive(Debug, Default)]
+pub struct Screens {
+    pub screen: Screen,
+    pub clock: Clock,
+    pub time_ctrl_selecter: TimeCtrl,
+}
+
+impl Screens {
+    pub fn handle_input(&mut self, input: Input) {
+        match self.screen {
+            Screen::Clocks => match input {
+                Input::Hit => self.clock.hit(),
+                Input::Pause => self
+                    .clock
+                    .pause(self.clock.curr_player().unwrap_or_default()),
+                Input::Flip => self.clock.flip_first_to_move(),
+                _ => {}
+            },
+            Screen::SelectTimeCtrl => match input {
+                Input::Hit | Input::Confirm => self.start(),
+                Input::Next => self.time_ctrl_selecter.next(),
+                Input::Previous => self.time_ctrl_selecter.previous(),
+                _ => {}
+            },
+            Screen::TimeOut => {
+                if let Input::Flip | Input::Confirm = input {
+                    self.screen = Screen::SelectTimeCtrl;
+                }
+            }
+        }
+    }
+
+    /// A tap anywhere does what the space bar does, so a tablet needs no keyboard.
+    pub fn handle_tap(&mut self) {
+        match self.screen {
+            Screen::Clocks => self.clock.hit(),
+            Screen::SelectTimeCtrl => self.start(),
+            Screen::TimeOut => self.screen = Screen::SelectTimeCtrl,
+        }
+    }
+
+    fn start(&mut self) {
+        self.clock.set_preset(self.time_ctrl_selecter);
+        self.screen = Screen::Clocks;
+    }
+
+    /// Takes `elapsed` off the running clock, showing the flag once it falls.
+    pub fn tick(&mut self, elapsed: Duration) {
+        self.clock.tick(elapsed);
+        if self.clock.is_time_out() && self.screen == Screen::Clocks {
+            self.screen = Screen::TimeOut;
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        match self.screen {
+            Screen::Clocks | Screen::TimeOut => {
+                ClockWidget::new(&self.clock).render(frame.area(), frame.buffer_mut());
+            }
+            Screen::SelectTimeCtrl => {
+                let vertical = Layout::vertical([Constraint::Length(3)]).flex(Flex::Center);
+                let horizontal =
+                    Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
+                let [area] = vertical.areas(frame.area());
+                let [area] = horizontal.areas(area);
+                tabs::render_time_ctrl(self.time_ctrl_selecter, area, frame.buffer_mut());
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Lua hooks run on clock events, for homebrew rules that need no rebuild.
+//!
+//! A script defines any of these global functions:
+//!
+//! ```lua
+//! function on_hit(player, clock) end  -- `player` finished their move
+//! function on_tick(clock) end         -- every 10ms while a clock runs
+//! function on_flag(player, clock) end -- `player`'s time ran out
+//! ```
+//!
+//! `player` is `"player1"` or `"player2"` and `clock` the state as served by the JSON APIs.
+//! Hooks change the times with `add_time(player, ms)`, negative `ms` take time away.
+
+use std::{fs, path::Path};
+
+use mlua::{Function, Lua, LuaSerdeExt, Value};
+
+use crate::clock::{Clock, ClockEvent, Player, Snapshot};
+
+#[derive(Debug)]
+pub struct Script {
+    lua: Lua,
+}
+
+impl Script {
+    /// Loads the script at `path`, running its top level once.
+    pub fn load(path: &Path) -> anyhow::Result<Script> {
+        let lua = Lua::new();
+        let source = fs::read_to_string(path)?;
+        lua.load(source)
+            .set_name(path.display().to_string())
+            .exec()?;
+        Ok(Script { lua })
+    }
+
+    /// Calls the hooks for the events between `prev` and the current state of `clock`.
+    pub fn clock_changed(&self, prev: &Snapshot, clock: &mut Clock) -> anyhow::Result<()> {
+        for event in ClockEvent::between(prev, &clock.snapshot()) {
+            match event {
+                ClockEvent::Hit { player, .. } => self.call("on_hit", Some(player), clock)?,
+                ClockEvent::Flag { player } => self.call("on_flag", Some(player), clock)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn tick(&self, clock: &mut Clock) -> anyhow::Result<()> {
+        if clock.snapshot().running {
+            self.call("on_tick", None, clock)?;
+        }
+        Ok(())
+    }
+
+    fn call(&self, hook: &str, player: Option<Player>, clock: &mut Clock) -> anyhow::Result<()> {
+        let Ok(hook) = self.lua.globals().get::<Function>(hook) else {
+            return Ok(());
+        };
+        let state = self.lua.to_value(&clock.snapshot())?;
+        let player = match player {
+            Some(player) => self.lua.to_value(&player)?,
+            None => Value::Nil,
+        };
+        self.lua.scope(|scope| {
+            let add_time = scope.create_function_mut(|lua, (player, ms): (Value, i64)| {
+                clock.adjust(lua.from_value(player)?, ms);
+                Ok(())
+            })?;
+            self.lua.globals().set("add_time", add_time)?;
+            match player {
+                Value::Nil => hook.call::<()>(state),
+                player => hook.call::<()>((player, state)),
+            }
+        })?;
+        Ok(())
+    }
+}
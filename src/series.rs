@@ -0,0 +1,144 @@
+use crate::{
+    clock::{Player, Snapshot},
+    config::SeriesConfig,
+    i18n::{self, tr, trf},
+    tabs::TimeCtrl,
+};
+
+/// A game of a [`Series`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Game {
+    /// Counted from 1, the Armageddon game comes after the last one.
+    pub number: u32,
+    /// Time control of the game, the one picked at the start if none is set for it.
+    pub control: Option<TimeCtrl>,
+    pub white: Player,
+    /// Black has a fifth less time and wins on a draw.
+    pub armageddon: bool,
+}
+
+/// A best-of-N match: the players change colors every game and the first to score more than
+/// half the points wins, a tie may be broken by an Armageddon game.
+#[derive(Debug, Clone)]
+pub struct Series {
+    games: u32,
+    controls: Vec<TimeCtrl>,
+    armageddon: bool,
+    /// Half points of Player 1 and Player 2.
+    half_points: [u32; 2],
+    played: u32,
+    armageddon_winner: Option<Player>,
+}
+
+impl Series {
+    /// A match of `games` games, failing on a time control of the config that isn't known.
+    pub fn new(games: u32, config: &SeriesConfig) -> anyhow::Result<Self> {
+        let controls = config
+            .controls
+            .iter()
+            .map(|control| TimeCtrl::parse(control))
+            .collect::<Result<_, _>>()
+            .map_err(anyhow::Error::msg)?;
+        Ok(Self {
+            games,
+            controls,
+            armageddon: config.armageddon,
+            half_points: [0; 2],
+            played: 0,
+            armageddon_winner: None,
+        })
+    }
+
+    /// The game to play next, none once the match is decided.
+    pub fn game(&self) -> Option<Game> {
+        if self.is_decided() {
+            return None;
+        }
+        // Player 1 has White in the first game
+        let white = if self.played.is_multiple_of(2) {
+            Player::Player1
+        } else {
+            Player::Player2
+        };
+        let index = (self.played as usize).min(self.controls.len().saturating_sub(1));
+        Some(Game {
+            number: self.played + 1,
+            control: self.controls.get(index).copied(),
+            white,
+            armageddon: self.played == self.games,
+        })
+    }
+
+    /// Scores the finished game of `snapshot`.
+    pub fn record(&mut self, snapshot: &Snapshot) {
+        let Some(game) = self.game() else {
+            return;
+        };
+        let white = snapshot.white;
+        let white_half_points = match snapshot.result() {
+            Some("1-0") => 2,
+            Some("0-1") => 0,
+            Some(_) => 1,
+            None => return,
+        };
+        if game.armageddon {
+            self.armageddon_winner = Some(if white_half_points == 2 {
+                white
+            } else {
+                white.opponent()
+            });
+            return;
+        }
+        self.half_points[white.index()] += white_half_points;
+        self.half_points[white.opponent().index()] += 2 - white_half_points;
+        self.played += 1;
+    }
+
+    /// The player who won the match, once they did.
+    pub fn winner(&self) -> Option<Player> {
+        // More than half the points can't be caught up with
+        let ahead = [Player::Player1, Player::Player2]
+            .into_iter()
+            .find(|&player| self.half_points[player.index()] > self.games);
+        self.armageddon_winner.or(ahead)
+    }
+
+    pub fn is_decided(&self) -> bool {
+        let tied = self.half_points[0] == self.half_points[1];
+        self.winner().is_some() || (self.played == self.games && !(tied && self.armageddon))
+    }
+
+    /// The game under way and the score, or the result of the match.
+    pub fn status(&self) -> String {
+        let score = format!(
+            "{} {}–{} {}",
+            i18n::player(Player::Player1),
+            points(self.half_points[0]),
+            points(self.half_points[1]),
+            i18n::player(Player::Player2),
+        );
+        match (self.game(), self.winner()) {
+            (Some(game), _) if game.armageddon => {
+                format!("{}, {score}", tr("Armageddon, Black wins on a draw"))
+            }
+            (Some(game), _) => format!(
+                "{}, {score}",
+                trf("Game {} of {}", &[&game.number, &self.games])
+            ),
+            (None, Some(winner)) => format!(
+                "{}, {score}",
+                trf("{} wins the match", &[&i18n::player(winner)])
+            ),
+            (None, None) => format!("{}, {score}", tr("The match is drawn")),
+        }
+    }
+}
+
+/// Points with halves, e.g. `2½`.
+fn points(half_points: u32) -> String {
+    match (half_points / 2, half_points % 2) {
+        (0, 1) => "½".to_string(),
+        (points, 1) => format!("{points}½"),
+        (points, _) => points.to_string(),
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use crate::config::SoundConfig;
+
+/// Sounds made by the app.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SoundKind {
+    /// A player's time fell into the last seconds, see [`crate::clock::Clock::burning`].
+    LowTime,
+    /// The click of a physical clock when it's hit.
+    Hit,
+    /// A player ran out of time.
+    Flag,
+}
+
+impl SoundKind {
+    /// Name of the WAV file replacing the built-in sound.
+    #[cfg(feature = "audio")]
+    fn file_name(self) -> &'static str {
+        match self {
+            SoundKind::LowTime => "low_time.wav",
+            SoundKind::Hit => "hit.wav",
+            SoundKind::Flag => "flag.wav",
+        }
+    }
+}
+
+/// Plays sounds on the default audio device with the `audio` feature, and rings the terminal
+/// bell without it or when there's no device. The audio device stays open while it lives.
+///
+/// With audio, a WAV file named after the sound in [`SoundConfig::dir`] is played instead of the
+/// built-in tone.
+pub struct Sound {
+    pub muted: bool,
+    #[cfg(feature = "audio")]
+    device: Option<rodio::MixerDeviceSink>,
+}
+
+impl Sound {
+    pub fn new() -> Self {
+        Self {
+            muted: false,
+            #[cfg(feature = "audio")]
+            device: rodio::DeviceSinkBuilder::open_default_sink()
+                .ok()
+                .map(|mut device| {
+                    // Closing the device mustn't write over the terminal
+                    device.log_on_drop(false);
+                    device
+                }),
+        }
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+    }
+
+    /// Plays the sound of `kind`, unless muted or turned off in the config.
+    pub fn play(&self, config: &SoundConfig, kind: SoundKind) {
+        let enabled = match kind {
+            SoundKind::LowTime => config.low_time,
+            SoundKind::Hit => config.hit,
+            SoundKind::Flag => config.flag,
+        };
+        if self.muted || !enabled || config.volume <= 0.0 {
+            return;
+        }
+        #[cfg(feature = "audio")]
+        if let Some(device) = &self.device {
+            use rodio::Source;
+
+            let volume = config.volume.min(1.0);
+            match custom(config, kind) {
+                Some(decoder) => device.mixer().add(decoder.amplify(volume)),
+                None => device.mixer().add(tone(kind).amplify(volume)),
+            }
+            return;
+        }
+        // Every sound is the same bell in the terminal
+        let _ = kind;
+        let mut stdout = io::stdout();
+        // A sound failing must not take the running app down
+        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+    }
+}
+
+impl Default for Sound {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Sound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sound")
+            .field("muted", &self.muted)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The WAV file replacing the sound of `kind`, none if missing or unreadable.
+#[cfg(feature = "audio")]
+fn custom(
+    config: &SoundConfig,
+    kind: SoundKind,
+) -> Option<rodio::Decoder<std::io::BufReader<std::fs::File>>> {
+    let file = std::fs::File::open(config.dir().join(kind.file_name())).ok()?;
+    rodio::Decoder::try_from(file).ok()
+}
+
+#[cfg(feature = "audio")]
+fn tone(kind: SoundKind) -> impl rodio::Source + Send + 'static {
+    use std::time::Duration;
+
+    use rodio::{Source, source::SineWave};
+
+    let (frequency, millis) = match kind {
+        SoundKind::LowTime => (880.0, 300),
+        SoundKind::Hit => (2_000.0, 15),
+        SoundKind::Flag => (440.0, 800),
+    };
+    SineWave::new(frequency)
+        .take_duration(Duration::from_millis(millis))
+        .amplify(0.2)
+}
@@ -0,0 +1,261 @@
+use std::{
+    fs, io,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Context;
+use ratatui::{
+    Terminal, TerminalOptions, Viewport,
+    backend::CrosstermBackend,
+    crossterm::{
+        cursor, execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    layout::Rect,
+    widgets::Widget,
+};
+use russh::{
+    Channel, ChannelId, Pty,
+    keys::{
+        Algorithm, PrivateKey, PublicKey,
+        ssh_key::{LineEnding, rand_core::OsRng},
+    },
+    server::{Auth, Config, Handle, Handler, Msg, Server, Session},
+};
+use tokio::{net::TcpListener, sync::watch};
+
+use crate::{
+    clock::{Clock, ClockWidget},
+    command::Command,
+    event::Event,
+    paths,
+    remote::Remote,
+};
+
+/// Interval between two frames sent to a client.
+const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Lets the owners of the keys in `~/.ssh/authorized_keys` open the clock with `ssh`.
+pub async fn serve(addr: SocketAddr, read_only: bool, remote: Remote) -> anyhow::Result<()> {
+    let config = Config {
+        keys: vec![host_key()?],
+        auth_rejection_time: Duration::from_secs(1),
+        nodelay: true,
+        ..Default::default()
+    };
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("could not listen on {addr}"))?;
+    let mut server = SshServer {
+        remote,
+        read_only,
+        authorized: Arc::new(authorized_keys()),
+    };
+    tokio::spawn(async move { server.run_on_socket(Arc::new(config), &listener).await });
+    Ok(())
+}
+
+/// Loads the server's key, generating it on first use.
+fn host_key() -> anyhow::Result<PrivateKey> {
+    let path = paths::config_dir().join("ssh_host_ed25519_key");
+    if path.exists() {
+        return PrivateKey::read_openssh_file(&path)
+            .with_context(|| format!("could not read {}", path.display()));
+    }
+    let key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519)?;
+    fs::create_dir_all(paths::config_dir())?;
+    key.write_openssh_file(&path, LineEnding::LF)?;
+    Ok(key)
+}
+
+fn authorized_keys() -> Vec<PublicKey> {
+    let Some(home) = std::env::var_os("HOME") else {
+        return vec![];
+    };
+    let path = PathBuf::from(home).join(".ssh/authorized_keys");
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            // Skip the options some entries start with
+            let start = line.find("ssh-").or_else(|| line.find("ecdsa-"))?;
+            PublicKey::from_openssh(&line[start..]).ok()
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct SshServer {
+    remote: Remote,
+    read_only: bool,
+    authorized: Arc<Vec<PublicKey>>,
+}
+
+impl Server for SshServer {
+    type Handler = Client;
+
+    fn new_client(&mut self, _: Option<SocketAddr>) -> Client {
+        Client {
+            server: self.clone(),
+            size: watch::Sender::new(Rect::default()),
+        }
+    }
+}
+
+/// One `ssh` connection, drawing the clock on the client's terminal.
+struct Client {
+    server: SshServer,
+    size: watch::Sender<Rect>,
+}
+
+impl Client {
+    fn resize(&self, cols: u32, rows: u32) {
+        self.size.send_replace(Rect::new(0, 0, cols as u16, rows as u16));
+    }
+}
+
+impl Handler for Client {
+    type Error = anyhow::Error;
+
+    async fn auth_publickey(&mut self, _: &str, key: &PublicKey) -> anyhow::Result<Auth> {
+        let known = self
+            .server
+            .authorized
+            .iter()
+            .any(|k| k.key_data() == key.key_data());
+        Ok(if known { Auth::Accept } else { Auth::reject() })
+    }
+
+    async fn channel_open_session(
+        &mut self,
+        _: Channel<Msg>,
+        _: &mut Session,
+    ) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+
+    async fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        _: &str,
+        cols: u32,
+        rows: u32,
+        _: u32,
+        _: u32,
+        _: &[(Pty, u32)],
+        session: &mut Session,
+    ) -> anyhow::Result<()> {
+        self.resize(cols, rows);
+        session.channel_success(channel)?;
+        Ok(())
+    }
+
+    async fn window_change_request(
+        &mut self,
+        _: ChannelId,
+        cols: u32,
+        rows: u32,
+        _: u32,
+        _: u32,
+        _: &mut Session,
+    ) -> anyhow::Result<()> {
+        self.resize(cols, rows);
+        Ok(())
+    }
+
+    async fn shell_request(
+        &mut self,
+        channel: ChannelId,
+        session: &mut Session,
+    ) -> anyhow::Result<()> {
+        tokio::spawn(draw(
+            session.handle(),
+            channel,
+            self.size.subscribe(),
+            self.server.remote.clone(),
+        ));
+        session.channel_success(channel)?;
+        Ok(())
+    }
+
+    async fn data(
+        &mut self,
+        channel: ChannelId,
+        data: &[u8],
+        session: &mut Session,
+    ) -> anyhow::Result<()> {
+        for key in data {
+            let command = match key {
+                // q, Esc or Ctrl-C
+                b'q' | 0x1b | 0x03 => {
+                    let mut restore = vec![];
+                    execute!(restore, LeaveAlternateScreen, cursor::Show)?;
+                    session.data(channel, restore.into())?;
+                    session.exit_status_request(channel, 0)?;
+                    session.close(channel)?;
+                    return Ok(());
+                }
+                _ if self.server.read_only => continue,
+                b' ' => Command::Hit(None),
+                b'p' => Command::Pause,
+                _ => continue,
+            };
+            let _ = self.server.remote.events.send(Event::Command(command));
+        }
+        Ok(())
+    }
+}
+
+/// Collects the output of a frame until it is sent to the client.
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Output {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+impl io::Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Draws the clock on the client's terminal until the connection is closed.
+async fn draw(
+    handle: Handle,
+    channel: ChannelId,
+    mut size: watch::Receiver<Rect>,
+    remote: Remote,
+) -> anyhow::Result<()> {
+    let mut output = Output::default();
+    execute!(output, EnterAlternateScreen, cursor::Hide)?;
+    let options = TerminalOptions {
+        viewport: Viewport::Fixed(*size.borrow_and_update()),
+    };
+    let mut terminal = Terminal::with_options(CrosstermBackend::new(output.clone()), options)?;
+    let mut frames = tokio::time::interval(FRAME_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = frames.tick() => {}
+            changed = size.changed() => {
+                changed?;
+                terminal.resize(*size.borrow_and_update())?;
+            }
+        }
+        let clock = Clock::from_snapshot(&remote.state.borrow());
+        terminal.draw(|frame| ClockWidget::new(&clock).render(frame.area(), frame.buffer_mut()))?;
+        if handle.data(channel, output.take().into()).await.is_err() {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::{fs, io, path::Path, time::Duration};
+
+use clap::ValueEnum;
+#[cfg(unix)]
+use tokio::{io::AsyncWriteExt, net::unix::pipe};
+
+use crate::{
+    clock::{Player, Snapshot, Time},
+    remote::Remote,
+};
+
+/// Interval between two attempts to write a line nobody read yet.
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Format of the status line.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum Format {
+    /// `▶ 02:59 │ 03:00`
+    #[default]
+    Plain,
+    /// JSON for i3blocks blocks with `format=json`
+    I3blocks,
+    /// JSON for waybar custom modules with `"return-type": "json"`
+    Waybar,
+    /// tmux `status-right` snippet with the running time highlighted
+    Tmux,
+}
+
+/// Keeps a one line status of the clock in `path` for desktop status bars. A FIFO gets a line on
+/// every change while a bar reads from it, any other file is rewritten.
+pub fn spawn(path: &Path, format: Format, remote: &Remote) -> anyhow::Result<()> {
+    let path = path.to_owned();
+    let mut state = remote.state.clone();
+    tokio::spawn(async move {
+        let mut output = Output::open(&path);
+        let mut written = String::new();
+        let mut retry = tokio::time::interval(RETRY_INTERVAL);
+        loop {
+            let line = render(&state.borrow_and_update(), format);
+            if line != written && output.write(&path, &line).await.is_ok() {
+                written = line;
+            }
+            tokio::select! {
+                changed = state.changed() => if changed.is_err() {
+                    return;
+                },
+                // A FIFO can only be written once a bar reads from it
+                _ = retry.tick() => {}
+            }
+        }
+    });
+    Ok(())
+}
+
+enum Output {
+    File,
+    #[cfg(unix)]
+    Fifo(Option<pipe::Sender>),
+}
+
+impl Output {
+    #[cfg(unix)]
+    fn open(path: &Path) -> Output {
+        use std::os::unix::fs::FileTypeExt;
+        if fs::metadata(path).is_ok_and(|m| m.file_type().is_fifo()) {
+            Output::Fifo(None)
+        } else {
+            Output::File
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn open(_: &Path) -> Output {
+        Output::File
+    }
+
+    async fn write(&mut self, path: &Path, line: &str) -> io::Result<()> {
+        match self {
+            Output::File => {
+                let tmp = path.with_extension("tmp");
+                fs::write(&tmp, format!("{line}\n"))?;
+                fs::rename(tmp, path)
+            }
+            #[cfg(unix)]
+            Output::Fifo(sender) => {
+                let fifo = match sender {
+                    Some(fifo) => fifo,
+                    None => sender.insert(pipe::OpenOptions::new().open_sender(path)?),
+                };
+                let result = fifo.write_all(format!("{line}\n").as_bytes()).await;
+                if result.is_err() {
+                    *sender = None;
+                }
+                result
+            }
+        }
+    }
+}
+
+pub fn render(snapshot: &Snapshot, format: Format) -> String {
+    let time = |ms| Time(Duration::from_millis(ms)).to_string();
+    let mark = match (snapshot.flagged, snapshot.paused, snapshot.turn) {
+        (Some(_), _, _) => "⚑",
+        (None, true, _) => "⏸",
+        (None, false, Some(Player::Player1)) => "▶",
+        (None, false, Some(Player::Player2)) => "◀",
+        (None, false, None) => "■",
+    };
+    let text = format!(
+        "{mark} {} │ {}",
+        time(snapshot.player1_ms),
+        time(snapshot.player2_ms)
+    );
+    let class = if snapshot.flagged.is_some() {
+        "flagged"
+    } else if snapshot.running {
+        "running"
+    } else {
+        "stopped"
+    };
+    match format {
+        Format::Plain => text,
+        Format::I3blocks => {
+            let color = match class {
+                "flagged" => "#FF5555",
+                "running" => "#50FA7B",
+                _ => "#BBBBBB",
+            };
+            serde_json::json!({ "full_text": text, "color": color }).to_string()
+        }
+        Format::Waybar => serde_json::json!({
+            "text": text,
+            "tooltip": format!("{}, move {}", snapshot.time_control, snapshot.move_number),
+            "class": class,
+        })
+        .to_string(),
+        Format::Tmux => {
+            let style = |player| match snapshot.flagged {
+                Some(flagged) if flagged == player => "#[fg=red,bold]",
+                _ if snapshot.running && snapshot.turn == Some(player) => "#[fg=green,bold]",
+                _ => "",
+            };
+            format!(
+                "{mark} {}{}#[default] │ {}{}#[default]",
+                style(Player::Player1),
+                time(snapshot.player1_ms),
+                style(Player::Player2),
+                time(snapshot.player2_ms)
+            )
+        }
+    }
+}
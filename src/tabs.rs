@@ -1,105 +1,92 @@
-use std::time::Duration;
-
-use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     symbols,
     text::{Line, Text},
     widgets::{Block, Padding, Paragraph, Tabs, Widget},
 };
-use strum::{Display, EnumIter, FromRepr, IntoEnumIterator};
+use strum::IntoEnumIterator;
+
+pub use chessclock_core::tabs::*;
 
-pub type CtrlOption = (Duration, Duration);
+use crate::{clock::Theme, i18n::tr};
 
-#[derive(Debug, PartialEq, Default, Clone, Copy, Display, FromRepr, EnumIter)]
-pub enum TimeCtrl {
-    #[default]
-    #[strum(to_string = "5s +0")]
-    Tab0,
-    #[strum(to_string = "1 +0")]
-    Tab1,
-    #[strum(to_string = "3 +2")]
-    Tab2,
-    #[strum(to_string = "5 +3")]
-    Tab3,
-    #[strum(to_string = "10 +0")]
-    Tab4,
+/// Renders the picker with `ctrl` selected, in the default theme.
+pub fn render_time_ctrl(ctrl: TimeCtrl, area: Rect, buf: &mut Buffer) {
+    render_picker(&[], ctrl as usize, Theme::default(), area, buf);
 }
 
-impl TimeCtrl {
-    pub fn previous(&mut self) {
-        let current_index: usize = *self as usize;
-        let previous_index = current_index.saturating_sub(1);
-        *self = Self::from_repr(previous_index).unwrap_or(*self);
-    }
+/// Keys shown under the picker, with what they do on the clocks.
+const HELP: [(&str, &str); 4] = [
+    ("'p'/Esc", "pause and resume the clock"),
+    ("'r'", "give the first move to the opponent"),
+    ("Space", "start or hit the clock"),
+    ("Ctrl-C", "quit"),
+];
 
-    pub fn next(&mut self) {
-        let current_index = *self as usize;
-        let next_index = current_index.saturating_add(1);
-        *self = Self::from_repr(next_index).unwrap_or(*self);
-    }
+/// Renders the picker with `extra` time controls, e.g. from plugins, after the built-in ones.
+/// `selected` counts the built-in controls first.
+pub fn render_picker(
+    extra: &[String],
+    selected: usize,
+    theme: Theme,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    use Constraint::{Fill, Length, Min, Percentage};
 
-    pub fn handle_key_events(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Right => self.next(),
-            KeyCode::Left => self.previous(),
-            _ => {}
-        }
+    let mut titles: Vec<_> = TimeCtrl::iter()
+        .map(|ctrl| title(tr(&ctrl.to_string()), theme))
+        .chain(extra.iter().map(|name| title(name, theme)))
+        .collect();
+    // Titles separated by one space
+    let width = |titles: &[Line]| titles.iter().map(|t| t.width() + 1).sum::<usize>() - 1;
+    // Scrolls the titles that don't fit so the selected one stays visible
+    let room = block(theme).inner(area).width as usize;
+    let mut first = 0;
+    while first < selected && width(&titles[first..=selected]) > room {
+        first += 1;
     }
-
-    pub fn to_duration(&self) -> CtrlOption {
-        match self {
-            TimeCtrl::Tab0 => (Duration::from_secs(5), Duration::from_secs(0)),
-            TimeCtrl::Tab1 => (Duration::from_secs(60), Duration::from_secs(0)),
-            TimeCtrl::Tab2 => (Duration::from_secs(180), Duration::from_secs(2)),
-            TimeCtrl::Tab3 => (Duration::from_secs(300), Duration::from_secs(3)),
-            TimeCtrl::Tab4 => (Duration::from_secs(600), Duration::from_secs(0)),
-        }
+    titles.drain(..first);
+    while titles.len() > selected - first + 1 && width(&titles) > room {
+        titles.pop();
     }
-}
+    let width = width(&titles).min(room);
 
-impl Widget for TimeCtrl {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        use Constraint::{Fill, Length, Min, Percentage};
+    let vertical = Layout::vertical([Length(1), Min(0)]);
+    let [_, tabs_area] = vertical.areas(area);
+    let horizontal = Layout::horizontal([Fill(1), Length(width as u16), Fill(1)]);
+    let [_, tabs_area, _] = horizontal.areas(tabs_area);
 
-        let vertical = Layout::vertical([Length(1), Min(0)]);
-        let [_, tabs_area] = vertical.areas(area);
-        let horizontal = Layout::horizontal([Fill(1), Min(40), Fill(1)]);
-        let [_, tabs_area, _] = horizontal.areas(tabs_area);
+    Tabs::new(titles)
+        .highlight_style(Style::default().fg(theme.accent).bold().underlined())
+        .select(selected - first)
+        .padding("", "")
+        .divider(" ")
+        .render(tabs_area, buf);
+    block(theme).render(area, buf);
 
-        let titles = TimeCtrl::iter().map(TimeCtrl::title);
-        let selected_tab_index = self as usize;
-        Tabs::new(titles)
-            .highlight_style(Style::default().fg(Color::LightGreen).bold().underlined())
-            .select(selected_tab_index)
-            .padding("", "")
-            .divider(" ")
-            .render(tabs_area, buf);
-        self.block().render(area, buf);
-
-        let p = Text::styled(
-            include_str!("help"),
-            Style::default().fg(Color::LightGreen).bold(),
-        );
-        let [_, help] = Layout::vertical([Percentage(60), Percentage(40)]).areas(*buf.area());
-        let [_, help, _] = Layout::horizontal([Fill(1), Length(32), Fill(1)]).areas(help);
-        Paragraph::new(p).left_aligned().render(help, buf);
-    }
+    let mut lines = vec![Line::from(format!("{:>12}", tr("HELP")))];
+    lines.extend(
+        HELP.iter()
+            .map(|(key, action)| Line::from(format!("{key:<8}{}", tr(action)))),
+    );
+    let p = Text::from(lines).style(Style::default().fg(theme.accent).bold());
+    let [_, help] = Layout::vertical([Percentage(60), Percentage(40)]).areas(*buf.area());
+    let [_, help, _] = Layout::horizontal([Fill(1), Length(46), Fill(1)]).areas(help);
+    Paragraph::new(p).left_aligned().render(help, buf);
 }
 
-impl TimeCtrl {
-    pub fn title(self) -> Line<'static> {
-        format!(" {self} ").fg(Color::from_u32(0x007f00)).into()
-    }
+fn title(label: &str, theme: Theme) -> Line<'static> {
+    format!(" {label} ").fg(theme.muted).into()
+}
 
-    /// A block surrounding the tab's content
-    fn block(self) -> Block<'static> {
-        Block::bordered()
-            .border_set(symbols::border::ROUNDED)
-            .padding(Padding::horizontal(1))
-            .border_style(Color::LightGreen)
-            .title(Line::from(" Select time control ").centered())
-    }
+/// A block surrounding the tab's content
+fn block(theme: Theme) -> Block<'static> {
+    Block::bordered()
+        .border_set(symbols::border::ROUNDED)
+        .padding(Padding::horizontal(1))
+        .border_style(theme.accent)
+        .title(Line::from(format!(" {} ", tr("Select time control"))).centered())
 }
@@ -0,0 +1,222 @@
+//! The terminal the app draws on and reads keys from: crossterm, or termion or termwiz when
+//! built with their feature. Keys are passed on as crossterm key events whatever the backend,
+//! clicks and taps only with crossterm.
+
+use tokio::sync::mpsc;
+
+use crate::event::Event;
+
+pub use backend::{Terminal, init, poll_keys, read_keys, restore};
+
+#[cfg(not(any(feature = "termion", feature = "termwiz")))]
+mod backend {
+    use std::io;
+
+    use futures::StreamExt;
+    use ratatui::crossterm::{
+        event::{
+            DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent, EventStream,
+            MouseEventKind,
+        },
+        execute,
+    };
+
+    use super::*;
+    use crate::keys::KeyFilter;
+
+    pub type Terminal = ratatui::DefaultTerminal;
+
+    pub fn init() -> anyhow::Result<Terminal> {
+        let terminal = ratatui::init();
+        execute!(io::stdout(), EnableMouseCapture)?;
+        Ok(terminal)
+    }
+
+    pub fn restore() {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+        ratatui::restore();
+    }
+
+    pub fn read_keys(sender: mpsc::UnboundedSender<Event>) {
+        tokio::spawn(async move {
+            let mut keys = KeyFilter::default();
+            let mut reader = EventStream::new();
+            while let Some(Ok(event)) = reader.next().await {
+                let event = match event {
+                    CrosstermEvent::Key(key) if keys.accept(&key) => Event::Key(key),
+                    CrosstermEvent::Mouse(mouse)
+                        if matches!(mouse.kind, MouseEventKind::Down(_)) =>
+                    {
+                        Event::Mouse(mouse)
+                    }
+                    // The size is taken again when drawing: legacy Windows consoles report their
+                    // buffer here rather than the window
+                    CrosstermEvent::Resize(..) => Event::Resize,
+                    _ => continue,
+                };
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    pub fn poll_keys(_: &mut Terminal, _: &mpsc::UnboundedSender<Event>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "termion")]
+mod backend {
+    use std::{io, thread};
+
+    use ratatui::{
+        backend::TermionBackend,
+        crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+        termion::{
+            event::Key,
+            input::TermRead,
+            raw::{IntoRawMode, RawTerminal},
+            screen::{AlternateScreen, IntoAlternateScreen},
+        },
+    };
+
+    use super::*;
+
+    pub type Terminal = ratatui::Terminal<TermionBackend<AlternateScreen<RawTerminal<io::Stdout>>>>;
+
+    pub fn init() -> anyhow::Result<Terminal> {
+        let screen = io::stdout().into_raw_mode()?.into_alternate_screen()?;
+        let mut terminal = Terminal::new(TermionBackend::new(screen))?;
+        terminal.hide_cursor()?;
+        Ok(terminal)
+    }
+
+    /// The terminal is restored when it's dropped.
+    pub fn restore() {}
+
+    pub fn read_keys(sender: mpsc::UnboundedSender<Event>) {
+        thread::spawn(move || {
+            for key in io::stdin().keys().map_while(Result::ok) {
+                if let Some(key) = convert(key)
+                    && sender.send(Event::Key(key)).is_err()
+                {
+                    return;
+                }
+            }
+        });
+    }
+
+    pub fn poll_keys(_: &mut Terminal, _: &mpsc::UnboundedSender<Event>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn convert(key: Key) -> Option<KeyEvent> {
+        let (code, modifiers) = match key {
+            Key::Char('\n') => (KeyCode::Enter, KeyModifiers::NONE),
+            Key::Char('\t') => (KeyCode::Tab, KeyModifiers::NONE),
+            Key::Char(c) => (KeyCode::Char(c), KeyModifiers::NONE),
+            Key::Ctrl(c) => (KeyCode::Char(c), KeyModifiers::CONTROL),
+            Key::Alt(c) => (KeyCode::Char(c), KeyModifiers::ALT),
+            Key::Left => (KeyCode::Left, KeyModifiers::NONE),
+            Key::Right => (KeyCode::Right, KeyModifiers::NONE),
+            Key::Up => (KeyCode::Up, KeyModifiers::NONE),
+            Key::Down => (KeyCode::Down, KeyModifiers::NONE),
+            Key::Home => (KeyCode::Home, KeyModifiers::NONE),
+            Key::End => (KeyCode::End, KeyModifiers::NONE),
+            Key::PageUp => (KeyCode::PageUp, KeyModifiers::NONE),
+            Key::PageDown => (KeyCode::PageDown, KeyModifiers::NONE),
+            Key::Backspace => (KeyCode::Backspace, KeyModifiers::NONE),
+            Key::Delete => (KeyCode::Delete, KeyModifiers::NONE),
+            Key::Insert => (KeyCode::Insert, KeyModifiers::NONE),
+            Key::BackTab => (KeyCode::BackTab, KeyModifiers::SHIFT),
+            Key::Esc => (KeyCode::Esc, KeyModifiers::NONE),
+            Key::F(n) => (KeyCode::F(n), KeyModifiers::NONE),
+            _ => return None,
+        };
+        Some(KeyEvent::new(code, modifiers))
+    }
+}
+
+// termion wins when both features are enabled
+#[cfg(all(feature = "termwiz", not(feature = "termion")))]
+mod backend {
+    use std::time::Duration;
+
+    use ratatui::{
+        backend::TermwizBackend,
+        crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+        termwiz::{
+            input::{InputEvent, KeyCode as TermwizKey, Modifiers},
+            terminal::Terminal as _,
+        },
+    };
+
+    use super::*;
+
+    pub type Terminal = ratatui::Terminal<TermwizBackend>;
+
+    pub fn init() -> anyhow::Result<Terminal> {
+        let backend = TermwizBackend::new().map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok(Terminal::new(backend)?)
+    }
+
+    /// The terminal is restored when it's dropped.
+    pub fn restore() {}
+
+    /// termwiz reads keys through the terminal, see [`poll_keys`].
+    pub fn read_keys(_: mpsc::UnboundedSender<Event>) {}
+
+    /// Sends the keys typed since the last call as [`Event::Key`].
+    pub fn poll_keys(
+        terminal: &mut Terminal,
+        sender: &mpsc::UnboundedSender<Event>,
+    ) -> anyhow::Result<()> {
+        let buffered = terminal.backend_mut().buffered_terminal_mut();
+        while let Some(input) = buffered.terminal().poll_input(Some(Duration::ZERO))? {
+            match input {
+                InputEvent::Key(key) => {
+                    if let Some(key) = convert(key.key, key.modifiers) {
+                        let _ = sender.send(Event::Key(key));
+                    }
+                }
+                InputEvent::Resized { cols, rows } => buffered.resize(cols, rows),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn convert(key: TermwizKey, modifiers: Modifiers) -> Option<KeyEvent> {
+        let code = match key {
+            TermwizKey::Char(c) => KeyCode::Char(c),
+            TermwizKey::Enter => KeyCode::Enter,
+            TermwizKey::Tab => KeyCode::Tab,
+            TermwizKey::LeftArrow => KeyCode::Left,
+            TermwizKey::RightArrow => KeyCode::Right,
+            TermwizKey::UpArrow => KeyCode::Up,
+            TermwizKey::DownArrow => KeyCode::Down,
+            TermwizKey::Home => KeyCode::Home,
+            TermwizKey::End => KeyCode::End,
+            TermwizKey::PageUp => KeyCode::PageUp,
+            TermwizKey::PageDown => KeyCode::PageDown,
+            TermwizKey::Backspace => KeyCode::Backspace,
+            TermwizKey::Delete => KeyCode::Delete,
+            TermwizKey::Insert => KeyCode::Insert,
+            TermwizKey::Escape => KeyCode::Esc,
+            TermwizKey::Function(n) => KeyCode::F(n),
+            _ => return None,
+        };
+        let mut key_modifiers = KeyModifiers::NONE;
+        for (from, to) in [
+            (Modifiers::CTRL, KeyModifiers::CONTROL),
+            (Modifiers::ALT, KeyModifiers::ALT),
+            (Modifiers::SHIFT, KeyModifiers::SHIFT),
+        ] {
+            if modifiers.contains(from) {
+                key_modifiers |= to;
+            }
+        }
+        Some(KeyEvent::new(code, key_modifiers))
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{clock::Snapshot, date, i18n::trf, paths};
+
+/// Interval between two comparisons of the clocks.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// Discrepancy worth a warning, smaller ones are NTP adjustments and scheduling noise.
+const THRESHOLD: Duration = Duration::from_secs(2);
+
+/// Compares the monotonic time driving the game with the system's wall clock, which keeps
+/// running while the computer sleeps and can be set by the user.
+#[derive(Debug)]
+pub struct WallClock {
+    monotonic: Instant,
+    wall: SystemTime,
+}
+
+impl Default for WallClock {
+    fn default() -> Self {
+        Self {
+            monotonic: Instant::now(),
+            wall: SystemTime::now(),
+        }
+    }
+}
+
+/// A discrepancy between the clocks, in milliseconds the wall clock ran ahead of the game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Discrepancy(pub i64);
+
+impl WallClock {
+    /// Compares the time elapsed on both clocks since the last check, at most once per
+    /// [`CHECK_INTERVAL`], returning a discrepancy larger than [`THRESHOLD`].
+    pub fn check(&mut self) -> Option<Discrepancy> {
+        let monotonic = self.monotonic.elapsed();
+        if monotonic < CHECK_INTERVAL {
+            return None;
+        }
+        let wall = match SystemTime::now().duration_since(self.wall) {
+            Ok(ahead) => ahead.as_millis() as i64,
+            Err(behind) => -(behind.duration().as_millis() as i64),
+        };
+        *self = WallClock::default();
+        let discrepancy = wall - monotonic.as_millis() as i64;
+        (discrepancy.unsigned_abs() > THRESHOLD.as_millis() as u64)
+            .then_some(Discrepancy(discrepancy))
+    }
+}
+
+pub fn log_path() -> PathBuf {
+    paths::data_dir().join("wallclock.log")
+}
+
+/// Appends the discrepancy and the clock state when it was noticed to [`log_path`].
+pub fn log(discrepancy: Discrepancy, snapshot: &Snapshot) -> anyhow::Result<()> {
+    fs::create_dir_all(paths::data_dir())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path())?;
+    let now = date::now();
+    writeln!(
+        file,
+        "{} {} {discrepancy}, {}",
+        date::format(now, '-'),
+        date::time_of_day(now),
+        serde_json::to_string(snapshot)?
+    )?;
+    Ok(())
+}
+
+impl std::fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = format!("{:.1}", self.0.unsigned_abs() as f64 / 1000.0);
+        if self.0 > 0 {
+            f.write_str(&trf("wall clock ran {}s ahead of the game clock", &[&secs]))
+        } else {
+            f.write_str(&trf("wall clock went {}s back", &[&secs]))
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::{cell::RefCell, io, rc::Rc, time::Duration};
+
+use chessclock::screens::{Input, Screens};
+use ratzilla::{
+    DomBackend, WebRenderer,
+    event::{KeyCode, MouseEventKind},
+    ratatui::Terminal,
+    web_sys,
+};
+
+/// The terminal app's clock screens, driven by the browser's keys, taps and frames.
+#[derive(Debug)]
+struct App {
+    screens: Screens,
+    /// Time of the last frame, in ms.
+    last_frame: f64,
+}
+
+impl App {
+    fn handle_key(&mut self, code: KeyCode) {
+        let input = match code {
+            KeyCode::Char(' ') => Input::Hit,
+            KeyCode::Enter => Input::Confirm,
+            KeyCode::Char('p') => Input::Pause,
+            KeyCode::Char('r' | 'R') => Input::Flip,
+            KeyCode::Right => Input::Next,
+            KeyCode::Left => Input::Previous,
+            _ => return,
+        };
+        self.screens.handle_input(input);
+    }
+
+    /// Ticks the clock for the time elapsed since the last frame.
+    fn tick(&mut self, now: f64) {
+        let elapsed = (now - self.last_frame).max(0.0);
+        self.last_frame = now;
+        self.screens.tick(Duration::from_secs_f64(elapsed / 1000.0));
+    }
+}
+
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or(0.0, |performance| performance.now())
+}
+
+fn main() -> io::Result<()> {
+    let app = Rc::new(RefCell::new(App {
+        screens: Screens::default(),
+        last_frame: now(),
+    }));
+
+    let mut terminal = Terminal::new(DomBackend::new()?)?;
+    terminal.on_key_event({
+        let app = app.clone();
+        move |key| app.borrow_mut().handle_key(key.code)
+    })?;
+    terminal.on_mouse_event({
+        let app = app.clone();
+        move |mouse| {
+            if let MouseEventKind::ButtonDown(_) = mouse.kind {
+                app.borrow_mut().screens.handle_tap();
+            }
+        }
+    })?;
+    terminal.draw_web(move |frame| {
+        let mut app = app.borrow_mut();
+        app.tick(now());
+        app.screens.render(frame);
+    });
+    Ok(())
+}